@@ -185,7 +185,10 @@ impl BootstrapAddr {
     }
 }
 
-/// Craft a proper address to avoid any ill formed addresses
+/// Craft a proper address to avoid any ill formed addresses.
+///
+/// UDP addresses keep their QUIC component, TCP addresses their WebSocket one, so QUIC (`/udp/<port>/quic-v1`),
+/// WebSocket (`/tcp/<port>/ws`) and plain TCP (`/tcp/<port>`) addresses are all kept.
 ///
 /// ignore_peer_id is only used for nat-detection contact list
 pub fn craft_valid_multiaddr(addr: &Multiaddr, ignore_peer_id: bool) -> Option<Multiaddr> {
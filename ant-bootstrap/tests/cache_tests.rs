@@ -42,6 +42,42 @@ async fn test_cache_store_operations() -> Result<(), Box<dyn std::error::Error>>
     Ok(())
 }
 
+#[tokio::test]
+async fn test_cache_stores_all_transports() -> Result<(), Box<dyn std::error::Error>> {
+    let _guard = LogBuilder::init_single_threaded_tokio_test("cache_tests", false);
+
+    let temp_dir = TempDir::new()?;
+    let cache_path = temp_dir.path().join("cache.json");
+    let config = BootstrapCacheConfig::empty().with_cache_path(&cache_path);
+    let mut cache_store = BootstrapCacheStore::new(config.clone())?;
+
+    let addrs: Vec<Multiaddr> = [
+        "/ip4/127.0.0.1/udp/8080/quic-v1/p2p/12D3KooWRBhwfeP2Y4TCx1SM6s9rUoHhR5STiGwxBhgFRcw3UERE",
+        "/ip4/127.0.0.1/tcp/8080/p2p/12D3KooWRBhwfeP2Y4TCx1SM6s9rUoHhR5STiGwxBhgFRcw3UERE",
+        "/ip4/127.0.0.1/tcp/8081/ws/p2p/12D3KooWRBhwfeP2Y4TCx1SM6s9rUoHhR5STiGwxBhgFRcw3UERE",
+    ]
+    .iter()
+    .map(|addr| addr.parse())
+    .collect::<Result<_, _>>()?;
+    for addr in &addrs {
+        cache_store.add_addr(addr.clone());
+    }
+    cache_store.write()?;
+
+    let cached = BootstrapCacheStore::load_cache_data(&config)?;
+    let cached_addrs = cached
+        .peers
+        .values()
+        .flat_map(|addrs| addrs.0.iter().map(|addr| addr.addr.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(cached_addrs.len(), addrs.len());
+    for addr in &addrs {
+        assert!(cached_addrs.contains(addr), "Cache should contain {addr}");
+    }
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_cache_max_peers() -> Result<(), Box<dyn std::error::Error>> {
     let _guard = LogBuilder::init_single_threaded_tokio_test("cache_tests", false);
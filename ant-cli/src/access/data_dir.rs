@@ -6,11 +6,12 @@
 // KIND, either express or implied. Please review the Licences for the specific language governing
 // permissions and limitations relating to use of the SAFE Network Software.
 
+use autonomi::client::files::journal::journal_path_for;
 use color_eyre::{
     eyre::{eyre, Context, Result},
     Section,
 };
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 pub fn get_client_data_dir_path() -> Result<PathBuf> {
     let mut home_dirs = dirs_next::data_dir()
@@ -26,3 +27,14 @@ pub fn get_client_data_dir_path() -> Result<PathBuf> {
         })?;
     Ok(home_dirs)
 }
+
+/// Path of the journal tracking the upload of `file`, used to resume an interrupted upload.
+pub fn get_upload_journal_path(file: &Path, public: bool) -> Result<PathBuf> {
+    let journal_dir = get_client_data_dir_path()?.join("upload_journals");
+    Ok(journal_path_for(&journal_dir, file, public))
+}
+
+/// Directory the receipts of the payments made by the client are saved to.
+pub fn get_receipts_dir_path() -> Result<PathBuf> {
+    Ok(get_client_data_dir_path()?.join("receipts"))
+}
@@ -7,6 +7,7 @@
 // permissions and limitations relating to use of the SAFE Network Software.
 
 use crate::wallet::load_wallet_private_key;
+use autonomi::client::graph::SecretKey;
 use autonomi::client::registers::RegisterSecretKey;
 use autonomi::client::vault::VaultSecretKey;
 use autonomi::{get_evm_network_from_env, Wallet};
@@ -21,6 +22,13 @@ const REGISTER_SIGNING_KEY_ENV: &str = "REGISTER_SIGNING_KEY";
 
 const REGISTER_SIGNING_KEY_FILE: &str = "register_signing_key";
 
+const POINTER_SIGNING_KEY_ENV: &str = "POINTER_SIGNING_KEY";
+const POINTER_SIGNING_KEY_FILE: &str = "pointer_signing_key";
+const GRAPH_SIGNING_KEY_ENV: &str = "GRAPH_SIGNING_KEY";
+const GRAPH_SIGNING_KEY_FILE: &str = "graph_signing_key";
+const SCRATCHPAD_SIGNING_KEY_ENV: &str = "SCRATCHPAD_SIGNING_KEY";
+const SCRATCHPAD_SIGNING_KEY_FILE: &str = "scratchpad_signing_key";
+
 /// EVM wallet
 pub fn load_evm_wallet_from_env() -> Result<Wallet> {
     let secret_key =
@@ -93,3 +101,45 @@ pub fn get_register_signing_key_path() -> Result<PathBuf> {
     let file_path = dir.join(REGISTER_SIGNING_KEY_FILE);
     Ok(file_path)
 }
+
+/// Key owning the pointer created by the `pointer` subcommands.
+pub fn get_pointer_signing_key() -> Result<SecretKey> {
+    get_or_create_signing_key(POINTER_SIGNING_KEY_ENV, POINTER_SIGNING_KEY_FILE)
+}
+
+/// Key owning the graph entry created by the `graph` subcommands.
+pub fn get_graph_signing_key() -> Result<SecretKey> {
+    get_or_create_signing_key(GRAPH_SIGNING_KEY_ENV, GRAPH_SIGNING_KEY_FILE)
+}
+
+/// Key owning the scratchpad created by the `scratchpad` subcommands.
+pub fn get_scratchpad_signing_key() -> Result<SecretKey> {
+    get_or_create_signing_key(SCRATCHPAD_SIGNING_KEY_ENV, SCRATCHPAD_SIGNING_KEY_FILE)
+}
+
+/// Load a key from the env var, or from its file in the data dir, generating the file if it does not exist yet.
+fn get_or_create_signing_key(env_var: &str, file_name: &str) -> Result<SecretKey> {
+    if let Ok(key_hex) = env::var(env_var) {
+        return SecretKey::from_hex(&key_hex)
+            .wrap_err(format!(
+                "Failed to parse the key from the {env_var} env var"
+            ))
+            .with_suggestion(|| "the key should be a hex encoded string of a bls secret key");
+    }
+
+    let dir = super::data_dir::get_client_data_dir_path()
+        .wrap_err("Could not access directory to read key from")
+        .with_suggestion(|| format!("make sure you've provided the {env_var} env var"))?;
+    let key_path = dir.join(file_name);
+    if !key_path.exists() {
+        let key = SecretKey::random();
+        fs::write(&key_path, key.to_hex()).wrap_err("Could not write key to file")?;
+        info!("Created new key at: {key_path:?}");
+        return Ok(key);
+    }
+
+    let key_hex = fs::read_to_string(&key_path).wrap_err("Failed to read secret key from file")?;
+    SecretKey::from_hex(key_hex.trim())
+        .wrap_err(format!("Failed to parse the key in {key_path:?}"))
+        .with_suggestion(|| "the key should be a hex encoded string of a bls secret key")
+}
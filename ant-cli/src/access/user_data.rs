@@ -11,15 +11,22 @@ use std::collections::HashMap;
 use autonomi::client::{
     address::{addr_to_str, str_to_addr},
     files::{archive::PrivateArchiveAccess, archive_public::ArchiveAddr},
+    graph::GraphEntryAddress,
+    pointer::PointerAddress,
     registers::RegisterAddress,
+    scratchpad::ScratchpadAddress,
     vault::UserData,
 };
 use color_eyre::eyre::Result;
+use std::path::PathBuf;
 
 use super::data_dir::get_client_data_dir_path;
 
 use serde::{Deserialize, Serialize};
 
+/// Name the register signing key of the data dir is stored under in the user data.
+const REGISTER_KEY_NAME: &str = "default";
+
 #[derive(Serialize, Deserialize)]
 struct PrivateFileArchive {
     name: String,
@@ -30,13 +37,68 @@ pub fn get_local_user_data() -> Result<UserData> {
     let file_archives = get_local_public_file_archives()?;
     let private_file_archives = get_local_private_file_archives()?;
 
+    let mut register_keys = HashMap::new();
+    if let Ok(key) = crate::keys::get_register_signing_key() {
+        register_keys.insert(REGISTER_KEY_NAME.to_string(), key);
+    }
+
     let user_data = UserData {
         file_archives,
         private_file_archives,
+        registers: get_local_registers()?,
+        pointers: get_local_named("pointers", |hex| Ok(PointerAddress::new(str_to_addr(hex)?)))?,
+        graph_roots: get_local_named("graph_roots", |hex| {
+            Ok(GraphEntryAddress::new(str_to_addr(hex)?))
+        })?,
+        scratchpads: get_local_named("scratchpads", |hex| Ok(ScratchpadAddress::from_hex(hex)?))?,
+        register_keys,
     };
     Ok(user_data)
 }
 
+fn user_data_dir(category: &str) -> Result<PathBuf> {
+    let data_dir = get_client_data_dir_path()?;
+    let path = data_dir.join("user_data").join(category);
+    std::fs::create_dir_all(&path)?;
+    Ok(path)
+}
+
+/// Read the addresses of a category stored as files named after the address and containing the name.
+fn get_local_named<K, F>(category: &str, parse: F) -> Result<HashMap<K, String>>
+where
+    K: std::hash::Hash + Eq,
+    F: Fn(&str) -> Result<K>,
+{
+    let mut named = HashMap::new();
+    for entry in walkdir::WalkDir::new(user_data_dir(category)?)
+        .min_depth(1)
+        .max_depth(1)
+    {
+        let entry = entry?;
+        let address = parse(&entry.file_name().to_string_lossy())?;
+        let name = std::fs::read_to_string(entry.path())?;
+        named.insert(address, name);
+    }
+    Ok(named)
+}
+
+fn write_local_named(category: &str, address_hex: String, name: &str) -> Result<()> {
+    std::fs::write(user_data_dir(category)?.join(address_hex), name)?;
+    Ok(())
+}
+
+pub fn write_local_pointer(pointer: &PointerAddress, name: &str) -> Result<()> {
+    write_local_named("pointers", pointer.to_hex(), name)
+}
+
+pub fn write_local_graph_root(root: &GraphEntryAddress, name: &str) -> Result<()> {
+    write_local_named("graph_roots", root.to_hex(), name)
+}
+
+pub fn write_local_scratchpad(scratchpad: &ScratchpadAddress, name: &str) -> Result<()> {
+    write_local_named("scratchpads", scratchpad.to_hex(), name)
+}
+
 pub fn get_local_private_file_archives() -> Result<HashMap<PrivateArchiveAccess, String>> {
     let data_dir = get_client_data_dir_path()?;
     let user_data_path = data_dir.join("user_data");
@@ -120,6 +182,29 @@ pub fn write_local_user_data(user_data: &UserData) -> Result<()> {
         write_local_private_file_archive(archive.to_hex(), archive.address(), name)?;
     }
 
+    for (register, name) in user_data.registers.iter() {
+        write_local_register(register, name)?;
+    }
+
+    for (pointer, name) in user_data.pointers.iter() {
+        write_local_pointer(pointer, name)?;
+    }
+
+    for (root, name) in user_data.graph_roots.iter() {
+        write_local_graph_root(root, name)?;
+    }
+
+    for (scratchpad, name) in user_data.scratchpads.iter() {
+        write_local_scratchpad(scratchpad, name)?;
+    }
+
+    // never replace the register key in use
+    if let Some(key) = user_data.register_keys.get(REGISTER_KEY_NAME) {
+        if crate::keys::get_register_signing_key().is_err() {
+            crate::keys::create_register_signing_key_file(key.clone())?;
+        }
+    }
+
     Ok(())
 }
 
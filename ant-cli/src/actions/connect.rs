@@ -6,15 +6,29 @@
 // KIND, either express or implied. Please review the Licences for the specific language governing
 // permissions and limitations relating to use of the SAFE Network Software.
 
+use autonomi::client::cache::DEFAULT_CHUNK_CACHE_MAX_SIZE;
+use autonomi::client::{ChunkCacheConfig, ClientConfig};
 use autonomi::Client;
 use autonomi::Multiaddr;
-use color_eyre::eyre::bail;
+use color_eyre::eyre::Context;
 use color_eyre::eyre::Result;
 use indicatif::ProgressBar;
+use std::sync::OnceLock;
 use std::time::Duration;
 
+static CHUNK_CACHE_SIZE: OnceLock<u64> = OnceLock::new();
+
+/// Set the maximum size in bytes of the chunk cache, 0 disables it. Must be called before connecting.
+pub fn set_chunk_cache_size(max_size: u64) {
+    let _ = CHUNK_CACHE_SIZE.set(max_size);
+}
+
 pub async fn connect_to_network(peers: Vec<Multiaddr>) -> Result<Client> {
-    let progress_bar = ProgressBar::new_spinner();
+    let progress_bar = if crate::output::is_json() {
+        ProgressBar::hidden()
+    } else {
+        ProgressBar::new_spinner()
+    };
     progress_bar.enable_steady_tick(Duration::from_millis(120));
     progress_bar.set_message("Connecting to The Autonomi Network...");
     let new_style = progress_bar.style().tick_chars("⠁⠂⠄⡀⢀⠠⠐⠈🔗");
@@ -22,7 +36,26 @@ pub async fn connect_to_network(peers: Vec<Multiaddr>) -> Result<Client> {
 
     progress_bar.set_message("Connecting to The Autonomi Network...");
 
-    match Client::init_with_peers(peers).await {
+    // Chunks are immutable, caching them avoids fetching the same archives and files again on every invocation
+    let max_size = CHUNK_CACHE_SIZE
+        .get()
+        .copied()
+        .unwrap_or(DEFAULT_CHUNK_CACHE_MAX_SIZE);
+    let chunk_cache = if max_size > 0 {
+        Some(ChunkCacheConfig {
+            dir: crate::data_dir::get_client_data_dir_path()?.join("chunk_cache"),
+            max_size,
+        })
+    } else {
+        None
+    };
+    let config = ClientConfig {
+        chunk_cache,
+        receipts_dir: Some(crate::data_dir::get_receipts_dir_path()?),
+        ..ClientConfig::from_peers(peers)
+    };
+
+    match Client::init_with_config(config).await {
         Ok(client) => {
             info!("Connected to the Network");
             progress_bar.finish_with_message("Connected to the Network");
@@ -31,7 +64,7 @@ pub async fn connect_to_network(peers: Vec<Multiaddr>) -> Result<Client> {
         Err(e) => {
             error!("Failed to connect to the network: {e}");
             progress_bar.finish_with_message("Failed to connect to the network");
-            bail!("Failed to connect to the network: {e}")
+            Err(e).wrap_err("Failed to connect to the network")
         }
     }
 }
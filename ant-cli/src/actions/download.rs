@@ -71,7 +71,7 @@ async fn download_private(
 
     if all_errs.is_empty() {
         info!("Successfully downloaded private data with local address: {addr}");
-        println!("Successfully downloaded private data with local address: {addr}");
+        human_println!("Successfully downloaded private data with local address: {addr}");
         Ok(())
     } else {
         let err_no = all_errs.len();
@@ -117,7 +117,7 @@ async fn download_public(
 
     if all_errs.is_empty() {
         info!("Successfully downloaded data at: {addr}");
-        println!("Successfully downloaded data at: {addr}");
+        human_println!("Successfully downloaded data at: {addr}");
         Ok(())
     } else {
         let err_no = all_errs.len();
@@ -8,9 +8,13 @@
 
 mod connect;
 mod download;
+#[cfg(feature = "fuse")]
+mod mount;
 mod progress_bar;
 
-pub use connect::connect_to_network;
+pub use connect::{connect_to_network, set_chunk_cache_size};
 pub use download::download;
+#[cfg(feature = "fuse")]
+pub use mount::{ArchiveFs, FileSource};
 
-pub use progress_bar::get_progress_bar;
+pub use progress_bar::{get_progress_bar, get_upload_progress_bar};
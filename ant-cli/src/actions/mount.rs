@@ -0,0 +1,410 @@
+// Copyright 2024 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use autonomi::{
+    client::{
+        data::{DataAddr, DataMapChunk},
+        files::{archive::Metadata, handle::FileHandle},
+    },
+    Bytes, Client,
+};
+use fuser::{
+    FileAttr, FileType, Filesystem, ReplyAttr, ReplyData, ReplyDirectory, ReplyEntry, ReplyOpen,
+    Request,
+};
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    ffi::{OsStr, OsString},
+    path::{Component, PathBuf},
+    time::{Duration, UNIX_EPOCH},
+};
+
+/// How long the kernel may cache attributes and entries. Archives are immutable, so this can be long.
+const TTL: Duration = Duration::from_secs(60);
+const ROOT_INO: u64 = 1;
+/// Files are read from the network in blocks of this size, which are then kept in the block cache.
+const BLOCK_SIZE: u64 = 1024 * 1024;
+/// Maximum number of blocks kept in memory.
+const MAX_CACHED_BLOCKS: usize = 64;
+
+/// Where the content of a file of an archive is stored.
+#[derive(Debug, Clone)]
+pub enum FileSource {
+    Public(DataAddr),
+    Private(DataMapChunk),
+}
+
+enum NodeKind {
+    Dir {
+        children: BTreeMap<OsString, u64>,
+    },
+    File {
+        source: FileSource,
+        metadata: Metadata,
+    },
+}
+
+struct Node {
+    parent: u64,
+    kind: NodeKind,
+}
+
+/// Read-only FUSE filesystem exposing the files of an archive.
+///
+/// The directory tree is built from the archive upfront, file content is fetched on demand.
+pub struct ArchiveFs {
+    client: Client,
+    runtime: tokio::runtime::Handle,
+    nodes: Vec<Node>,
+    handles: HashMap<u64, FileHandle>,
+    cache: BlockCache,
+}
+
+impl ArchiveFs {
+    /// Must be called from within the tokio runtime that will serve the network requests.
+    pub fn new(
+        client: Client,
+        files: impl IntoIterator<Item = (PathBuf, FileSource, Metadata)>,
+    ) -> Self {
+        Self {
+            client,
+            runtime: tokio::runtime::Handle::current(),
+            nodes: build_tree(files),
+            handles: HashMap::new(),
+            cache: BlockCache::default(),
+        }
+    }
+
+    fn node(&self, ino: u64) -> Option<&Node> {
+        ino.checked_sub(1)
+            .and_then(|index| self.nodes.get(index as usize))
+    }
+
+    fn attr(&self, ino: u64, req: &Request<'_>) -> Option<FileAttr> {
+        let node = self.node(ino)?;
+        let secs = |secs: u64| UNIX_EPOCH + Duration::from_secs(secs);
+        let (kind, perm, size, nlink, mtime, crtime) = match &node.kind {
+            NodeKind::Dir { .. } => (FileType::Directory, 0o555, 0, 2, UNIX_EPOCH, UNIX_EPOCH),
+            NodeKind::File { metadata, .. } => (
+                FileType::RegularFile,
+                0o444,
+                metadata.size,
+                1,
+                secs(metadata.modified),
+                secs(metadata.created),
+            ),
+        };
+        Some(FileAttr {
+            ino,
+            size,
+            blocks: size.div_ceil(512),
+            atime: mtime,
+            mtime,
+            ctime: mtime,
+            crtime,
+            kind,
+            perm,
+            nlink,
+            uid: req.uid(),
+            gid: req.gid(),
+            rdev: 0,
+            blksize: BLOCK_SIZE as u32,
+            flags: 0,
+        })
+    }
+
+    async fn read_file(&mut self, ino: u64, offset: u64, size: u32) -> Result<Vec<u8>, String> {
+        let Some(Node {
+            kind: NodeKind::File { source, .. },
+            ..
+        }) = self.node(ino)
+        else {
+            return Err(format!("inode {ino} is not a file"));
+        };
+
+        if !self.handles.contains_key(&ino) {
+            let handle = match source.clone() {
+                FileSource::Public(addr) => self.client.file_open_public(addr).await,
+                FileSource::Private(data_map) => self.client.file_open(data_map).await,
+            }
+            .map_err(|err| format!("failed to open file: {err}"))?;
+            self.handles.insert(ino, handle);
+        }
+        let handle = &self.handles[&ino];
+
+        let end = u64::min(offset.saturating_add(size as u64), handle.size());
+        let mut data = Vec::with_capacity(end.saturating_sub(offset) as usize);
+        let mut pos = offset;
+        while pos < end {
+            let block_index = pos / BLOCK_SIZE;
+            let block = match self.cache.get(ino, block_index) {
+                Some(block) => block,
+                None => {
+                    let block = handle
+                        .read_at(block_index * BLOCK_SIZE, BLOCK_SIZE as usize)
+                        .await
+                        .map_err(|err| format!("failed to read block {block_index}: {err}"))?;
+                    self.cache.insert(ino, block_index, block.clone());
+                    block
+                }
+            };
+
+            let start_in_block = (pos - block_index * BLOCK_SIZE) as usize;
+            let end_in_block = usize::min((end - block_index * BLOCK_SIZE) as usize, block.len());
+            if start_in_block >= end_in_block {
+                break;
+            }
+            data.extend_from_slice(&block[start_in_block..end_in_block]);
+            pos += (end_in_block - start_in_block) as u64;
+        }
+        Ok(data)
+    }
+}
+
+impl Filesystem for ArchiveFs {
+    fn lookup(&mut self, req: &Request<'_>, parent: u64, name: &OsStr, reply: ReplyEntry) {
+        let child = match self.node(parent) {
+            Some(Node {
+                kind: NodeKind::Dir { children },
+                ..
+            }) => children.get(name).copied(),
+            _ => None,
+        };
+        match child.and_then(|ino| self.attr(ino, req)) {
+            Some(attr) => reply.entry(&TTL, &attr, 0),
+            None => reply.error(libc::ENOENT),
+        }
+    }
+
+    fn getattr(&mut self, req: &Request<'_>, ino: u64, _fh: Option<u64>, reply: ReplyAttr) {
+        match self.attr(ino, req) {
+            Some(attr) => reply.attr(&TTL, &attr),
+            None => reply.error(libc::ENOENT),
+        }
+    }
+
+    fn open(&mut self, _req: &Request<'_>, ino: u64, flags: i32, reply: ReplyOpen) {
+        if flags & libc::O_ACCMODE != libc::O_RDONLY {
+            reply.error(libc::EROFS);
+            return;
+        }
+        match self.node(ino) {
+            Some(Node {
+                kind: NodeKind::File { .. },
+                ..
+            }) => reply.opened(0, 0),
+            Some(_) => reply.error(libc::EISDIR),
+            None => reply.error(libc::ENOENT),
+        }
+    }
+
+    fn read(
+        &mut self,
+        _req: &Request<'_>,
+        ino: u64,
+        _fh: u64,
+        offset: i64,
+        size: u32,
+        _flags: i32,
+        _lock_owner: Option<u64>,
+        reply: ReplyData,
+    ) {
+        let Ok(offset) = u64::try_from(offset) else {
+            reply.error(libc::EINVAL);
+            return;
+        };
+        // FUSE callbacks run on the session thread, outside of the runtime, so blocking on it is fine here.
+        let runtime = self.runtime.clone();
+        match runtime.block_on(self.read_file(ino, offset, size)) {
+            Ok(data) => reply.data(&data),
+            Err(err) => {
+                error!("Failed to read {size} bytes at {offset} from inode {ino}: {err}");
+                reply.error(libc::EIO);
+            }
+        }
+    }
+
+    fn readdir(
+        &mut self,
+        _req: &Request<'_>,
+        ino: u64,
+        _fh: u64,
+        offset: i64,
+        mut reply: ReplyDirectory,
+    ) {
+        let Some(Node {
+            parent,
+            kind: NodeKind::Dir { children },
+        }) = self.node(ino)
+        else {
+            reply.error(libc::ENOTDIR);
+            return;
+        };
+
+        let mut entries = vec![
+            (ino, FileType::Directory, OsString::from(".")),
+            (*parent, FileType::Directory, OsString::from("..")),
+        ];
+        for (name, child) in children {
+            let kind = match self.node(*child).map(|node| &node.kind) {
+                Some(NodeKind::Dir { .. }) => FileType::Directory,
+                _ => FileType::RegularFile,
+            };
+            entries.push((*child, kind, name.clone()));
+        }
+
+        for (i, (ino, kind, name)) in entries.into_iter().enumerate().skip(offset as usize) {
+            // the offset of an entry is the one of the next entry to read
+            if reply.add(ino, (i + 1) as i64, kind, name) {
+                break;
+            }
+        }
+        reply.ok();
+    }
+}
+
+// Build the inode table from the (relative) paths of the archive, the root directory being inode 1.
+fn build_tree(files: impl IntoIterator<Item = (PathBuf, FileSource, Metadata)>) -> Vec<Node> {
+    let mut nodes = vec![Node {
+        parent: ROOT_INO,
+        kind: NodeKind::Dir {
+            children: BTreeMap::new(),
+        },
+    }];
+
+    'files: for (path, source, metadata) in files {
+        let names: Vec<&OsStr> = path
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(name) => Some(name),
+                _ => None,
+            })
+            .collect();
+        let Some((file_name, dir_names)) = names.split_last() else {
+            warn!("Skipping archive entry with an empty path: {path:?}");
+            continue;
+        };
+
+        let mut dir = ROOT_INO;
+        for name in dir_names {
+            let next_ino = nodes.len() as u64 + 1;
+            let NodeKind::Dir { children } = &mut nodes[dir as usize - 1].kind else {
+                warn!("Skipping archive entry {path:?}, one of its parents is a file");
+                continue 'files;
+            };
+            let child = *children.entry(name.to_os_string()).or_insert(next_ino);
+            if child == next_ino {
+                nodes.push(Node {
+                    parent: dir,
+                    kind: NodeKind::Dir {
+                        children: BTreeMap::new(),
+                    },
+                });
+            }
+            dir = child;
+        }
+
+        let next_ino = nodes.len() as u64 + 1;
+        let NodeKind::Dir { children } = &mut nodes[dir as usize - 1].kind else {
+            warn!("Skipping archive entry {path:?}, its parent is a file");
+            continue;
+        };
+        if children.contains_key(*file_name) {
+            warn!("Skipping duplicate archive entry {path:?}");
+            continue;
+        }
+        children.insert(file_name.to_os_string(), next_ino);
+        nodes.push(Node {
+            parent: dir,
+            kind: NodeKind::File { source, metadata },
+        });
+    }
+    nodes
+}
+
+/// In-memory LRU cache of the blocks read from the files of the archive.
+#[derive(Default)]
+struct BlockCache {
+    blocks: HashMap<(u64, u64), Bytes>,
+    order: VecDeque<(u64, u64)>,
+}
+
+impl BlockCache {
+    fn get(&mut self, ino: u64, block_index: u64) -> Option<Bytes> {
+        let block = self.blocks.get(&(ino, block_index))?.clone();
+        self.order.retain(|key| *key != (ino, block_index));
+        self.order.push_back((ino, block_index));
+        Some(block)
+    }
+
+    fn insert(&mut self, ino: u64, block_index: u64, block: Bytes) {
+        if self.blocks.insert((ino, block_index), block).is_none() {
+            self.order.push_back((ino, block_index));
+        }
+        while self.order.len() > MAX_CACHED_BLOCKS {
+            if let Some(evicted) = self.order.pop_front() {
+                self.blocks.remove(&evicted);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(nodes: &[Node], ino: u64) -> Vec<String> {
+        match &nodes[ino as usize - 1].kind {
+            NodeKind::Dir { children } => children
+                .keys()
+                .map(|name| name.to_string_lossy().to_string())
+                .collect(),
+            NodeKind::File { .. } => panic!("inode {ino} is not a directory"),
+        }
+    }
+
+    #[test]
+    fn tree_is_built_from_archive_paths() {
+        let source = FileSource::Public(DataAddr::default());
+        let files = [
+            "photos/a.jpg",
+            "photos/trip/b.jpg",
+            "notes.txt",
+            "photos/a.jpg",
+        ]
+        .into_iter()
+        .map(|path| {
+            (
+                PathBuf::from(path),
+                source.clone(),
+                Metadata::new_with_size(1),
+            )
+        });
+        let nodes = build_tree(files);
+
+        // root, photos, a.jpg, trip, b.jpg, notes.txt
+        assert_eq!(nodes.len(), 6);
+        assert_eq!(names(&nodes, ROOT_INO), vec!["notes.txt", "photos"]);
+        assert_eq!(names(&nodes, 2), vec!["a.jpg", "trip"]);
+        assert_eq!(names(&nodes, 4), vec!["b.jpg"]);
+        assert_eq!(nodes[4].parent, 4);
+    }
+
+    #[test]
+    fn block_cache_evicts_least_recently_used() {
+        let mut cache = BlockCache::default();
+        for i in 0..MAX_CACHED_BLOCKS as u64 {
+            cache.insert(1, i, Bytes::from_static(b"block"));
+        }
+        assert!(cache.get(1, 0).is_some());
+        cache.insert(2, 0, Bytes::from_static(b"block"));
+        assert!(cache.get(1, 0).is_some());
+        assert!(cache.get(1, 1).is_none());
+        assert_eq!(cache.blocks.len(), MAX_CACHED_BLOCKS);
+    }
+}
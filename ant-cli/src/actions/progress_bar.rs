@@ -11,6 +11,9 @@ use indicatif::{ProgressBar, ProgressStyle};
 use std::time::Duration;
 
 pub fn get_progress_bar(length: u64) -> Result<ProgressBar> {
+    if crate::output::is_json() {
+        return Ok(ProgressBar::hidden());
+    }
     let progress_bar = ProgressBar::new(length);
     progress_bar.set_style(
         ProgressStyle::default_bar()
@@ -20,3 +23,18 @@ pub fn get_progress_bar(length: u64) -> Result<ProgressBar> {
     progress_bar.enable_steady_tick(Duration::from_millis(100));
     Ok(progress_bar)
 }
+
+/// Progress bar of the chunks verified to be stored on the network, whose total grows as chunks are paid for.
+pub fn get_upload_progress_bar() -> Result<ProgressBar> {
+    if crate::output::is_json() {
+        return Ok(ProgressBar::hidden());
+    }
+    let progress_bar = ProgressBar::new(0);
+    progress_bar.set_style(
+        ProgressStyle::default_bar()
+            .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} chunks verified, {msg}")?
+            .progress_chars("#>-"),
+    );
+    progress_bar.enable_steady_tick(Duration::from_millis(100));
+    Ok(progress_bar)
+}
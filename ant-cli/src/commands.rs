@@ -7,12 +7,17 @@
 // permissions and limitations relating to use of the SAFE Network Software.
 
 mod file;
+mod graph;
+mod pointer;
 mod register;
+mod scratchpad;
 mod vault;
 mod wallet;
 
 use crate::opt::Opt;
-use clap::Subcommand;
+use autonomi::client::graph::{WalkLimits, DEFAULT_MAX_WALKED_ENTRIES};
+use autonomi::client::Amount;
+use clap::{Subcommand, ValueEnum};
 use color_eyre::Result;
 
 #[derive(Subcommand, Debug)]
@@ -29,6 +34,24 @@ pub enum SubCmd {
         command: RegisterCmd,
     },
 
+    /// Operations related to pointer management.
+    Pointer {
+        #[command(subcommand)]
+        command: PointerCmd,
+    },
+
+    /// Operations related to graph entry management.
+    Graph {
+        #[command(subcommand)]
+        command: GraphCmd,
+    },
+
+    /// Operations related to scratchpad management.
+    Scratchpad {
+        #[command(subcommand)]
+        command: ScratchpadCmd,
+    },
+
     /// Operations related to vault management.
     Vault {
         #[command(subcommand)]
@@ -57,6 +80,23 @@ pub enum FileCmd {
         /// Upload the file as public. Everyone can see public data on the Network.
         #[arg(short, long)]
         public: bool,
+        /// Resume a previously interrupted upload of the same file, reusing its payments and skipping already uploaded chunks.
+        #[arg(long)]
+        resume: bool,
+        /// Abort before paying anything if the quoted cost of the upload is above this amount, in AttoTokens.
+        ///
+        /// The whole upload is then quoted before paying, and cannot be resumed if interrupted.
+        #[arg(long, value_name = "ATTO", conflicts_with = "resume")]
+        max_cost: Option<Amount>,
+        /// Show the quoted cost of the upload and ask for confirmation before paying.
+        ///
+        /// The whole upload is then quoted before paying, and cannot be resumed if interrupted.
+        #[arg(long, conflicts_with = "resume")]
+        confirm: bool,
+        /// Reuse the unexpired payments of a stored receipt, e.g. the one of an earlier upload of the same file that
+        /// failed. See `wallet receipts list`.
+        #[arg(long, value_name = "ID", conflicts_with_all = ["max_cost", "confirm"])]
+        receipt: Option<String>,
     },
 
     /// Download a file from the given address.
@@ -67,6 +107,40 @@ pub enum FileCmd {
         dest_file: String,
     },
 
+    /// Sync a directory with a previously uploaded archive, only uploading new and modified files.
+    Sync {
+        /// The directory to sync.
+        dir: String,
+        /// The address of the previous archive of the directory.
+        addr: String,
+    },
+
+    /// Upload a directory as the new version of a named archive, keeping a stable address across versions.
+    ///
+    /// The address of the archive is derived from the name and the register key.
+    Publish {
+        /// The directory to publish.
+        dir: String,
+        /// The name of the archive.
+        #[arg(short, long)]
+        name: String,
+    },
+
+    /// List the versions of a named archive, latest first.
+    History {
+        /// The stable address of the named archive.
+        pointer: String,
+    },
+
+    /// Mount an archive as a read-only filesystem, fetching file content on demand.
+    #[cfg(feature = "fuse")]
+    Mount {
+        /// The address of the archive to mount.
+        addr: String,
+        /// The directory to mount the archive on.
+        mountpoint: String,
+    },
+
     /// List previous uploads
     List,
 }
@@ -126,6 +200,102 @@ pub enum RegisterCmd {
     List,
 }
 
+#[derive(Subcommand, Debug)]
+pub enum PointerCmd {
+    /// Create your pointer, owned by the pointer key in your data dir.
+    Create {
+        /// The address the pointer points to.
+        target: String,
+        /// The type of data the target address is of.
+        #[arg(long, value_enum, default_value_t = PointerTargetType::Chunk)]
+        target_type: PointerTargetType,
+    },
+
+    /// Get a pointer.
+    Get {
+        /// The address of the pointer.
+        address: String,
+    },
+
+    /// Update your pointer to point to a new target.
+    Update {
+        /// The new address the pointer points to.
+        target: String,
+        /// The type of data the target address is of.
+        #[arg(long, value_enum, default_value_t = PointerTargetType::Chunk)]
+        target_type: PointerTargetType,
+    },
+
+    /// Estimate cost to create your pointer.
+    Cost,
+}
+
+/// The type of data a pointer points to.
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum PointerTargetType {
+    Chunk,
+    Graph,
+    Pointer,
+    Scratchpad,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum GraphCmd {
+    /// Put your graph entry, owned by the graph key in your data dir.
+    Put {
+        /// The hex encoded content of the entry, 32 bytes.
+        content: String,
+        /// The hex encoded public key of a parent entry. Can be repeated.
+        #[arg(long = "parent")]
+        parents: Vec<String>,
+    },
+
+    /// Get the graph entries at an address.
+    Get {
+        /// The address of the graph entry.
+        address: String,
+    },
+
+    /// Walk a graph from an entry, following its outputs or its parents.
+    Walk {
+        /// The address of the graph entry to start from.
+        address: String,
+        /// Follow the parents of the entries instead of their outputs.
+        #[arg(long)]
+        backward: bool,
+        /// Write the walked graph in the Graphviz DOT format to this file.
+        #[arg(long)]
+        dot: Option<String>,
+        /// Stop following the graph this many steps away from the start entry.
+        #[arg(long)]
+        max_depth: Option<usize>,
+        /// Stop the walk after fetching this many entries.
+        #[arg(long, default_value_t = DEFAULT_MAX_WALKED_ENTRIES)]
+        max_entries: usize,
+    },
+
+    /// Estimate cost to put your graph entry.
+    Cost,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ScratchpadCmd {
+    /// Create your scratchpad, owned by the scratchpad key in your data dir, with the given data.
+    Create {
+        /// The data to store in the scratchpad.
+        data: String,
+    },
+
+    /// Get and decrypt the data of your scratchpad.
+    Get,
+
+    /// Replace the data of your scratchpad.
+    Update {
+        /// The new data to store in the scratchpad.
+        data: String,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 pub enum VaultCmd {
     /// Estimate cost to create a vault.
@@ -140,7 +310,7 @@ pub enum VaultCmd {
     /// You need to have your original `SECRET_KEY` to load the vault.
     Load,
 
-    /// Sync vault with the network, including registers and files.
+    /// Sync vault with the network, including files, registers, pointers, graphs, scratchpads and register keys.
     /// Loads existing user data from the network and merges it with your local user data.
     /// Pushes your local user data to the network.
     Sync {
@@ -180,6 +350,34 @@ pub enum WalletCmd {
 
     /// Check the balance of the wallet.
     Balance,
+
+    /// Manage the receipts of the payments made when uploading.
+    Receipts {
+        #[command(subcommand)]
+        command: ReceiptsCmd,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ReceiptsCmd {
+    /// List the saved payment receipts.
+    ///
+    /// Receipts are pruned once all their payments have expired.
+    List,
+
+    /// Show the payments of a receipt.
+    Show {
+        /// The id of the receipt, as given by `receipts list`.
+        id: String,
+    },
+
+    /// Export a receipt to a JSON file.
+    Export {
+        /// The id of the receipt, as given by `receipts list`.
+        id: String,
+        /// The file to write the receipt to.
+        output: String,
+    },
 }
 
 pub async fn handle_subcommand(opt: Opt) -> Result<()> {
@@ -189,10 +387,35 @@ pub async fn handle_subcommand(opt: Opt) -> Result<()> {
     match cmd {
         Some(SubCmd::File { command }) => match command {
             FileCmd::Cost { file } => file::cost(&file, peers.await?).await,
-            FileCmd::Upload { file, public } => file::upload(&file, public, peers.await?).await,
+            FileCmd::Upload {
+                file,
+                public,
+                resume,
+                max_cost,
+                confirm,
+                receipt,
+            } => {
+                file::upload(
+                    &file,
+                    public,
+                    resume,
+                    max_cost,
+                    confirm,
+                    receipt,
+                    peers.await?,
+                )
+                .await
+            }
             FileCmd::Download { addr, dest_file } => {
                 file::download(&addr, &dest_file, peers.await?).await
             }
+            FileCmd::Sync { dir, addr } => file::sync(&dir, &addr, peers.await?).await,
+            FileCmd::Publish { dir, name } => file::publish(&dir, &name, peers.await?).await,
+            FileCmd::History { pointer } => file::history(&pointer, peers.await?).await,
+            #[cfg(feature = "fuse")]
+            FileCmd::Mount { addr, mountpoint } => {
+                file::mount(&addr, &mountpoint, peers.await?).await
+            }
             FileCmd::List => file::list(),
         },
         Some(SubCmd::Register { command }) => match command {
@@ -211,6 +434,43 @@ pub async fn handle_subcommand(opt: Opt) -> Result<()> {
             RegisterCmd::Get { address, name } => register::get(address, name, peers.await?).await,
             RegisterCmd::List => register::list(),
         },
+        Some(SubCmd::Pointer { command }) => match command {
+            PointerCmd::Create {
+                target,
+                target_type,
+            } => pointer::create(&target, target_type, peers.await?).await,
+            PointerCmd::Get { address } => pointer::get(&address, peers.await?).await,
+            PointerCmd::Update {
+                target,
+                target_type,
+            } => pointer::update(&target, target_type, peers.await?).await,
+            PointerCmd::Cost => pointer::cost(peers.await?).await,
+        },
+        Some(SubCmd::Graph { command }) => match command {
+            GraphCmd::Put { content, parents } => {
+                graph::put(&content, &parents, peers.await?).await
+            }
+            GraphCmd::Get { address } => graph::get(&address, peers.await?).await,
+            GraphCmd::Walk {
+                address,
+                backward,
+                dot,
+                max_depth,
+                max_entries,
+            } => {
+                let limits = WalkLimits {
+                    max_depth: max_depth.unwrap_or(usize::MAX),
+                    max_entries,
+                };
+                graph::walk(&address, backward, dot, limits, peers.await?).await
+            }
+            GraphCmd::Cost => graph::cost(peers.await?).await,
+        },
+        Some(SubCmd::Scratchpad { command }) => match command {
+            ScratchpadCmd::Create { data } => scratchpad::create(&data, peers.await?).await,
+            ScratchpadCmd::Get => scratchpad::get(peers.await?).await,
+            ScratchpadCmd::Update { data } => scratchpad::update(&data, peers.await?).await,
+        },
         Some(SubCmd::Vault { command }) => match command {
             VaultCmd::Cost => vault::cost(peers.await?).await,
             VaultCmd::Create => vault::create(peers.await?).await,
@@ -229,6 +489,11 @@ pub async fn handle_subcommand(opt: Opt) -> Result<()> {
             } => wallet::import(private_key, no_password, password),
             WalletCmd::Export => wallet::export(),
             WalletCmd::Balance => wallet::balance().await,
+            WalletCmd::Receipts { command } => match command {
+                ReceiptsCmd::List => wallet::receipts_list(),
+                ReceiptsCmd::Show { id } => wallet::receipts_show(&id),
+                ReceiptsCmd::Export { id, output } => wallet::receipts_export(&id, &output),
+            },
         },
         None => Ok(()),
     }
@@ -6,67 +6,138 @@
 // KIND, either express or implied. Please review the Licences for the specific language governing
 // permissions and limitations relating to use of the SAFE Network Software.
 
-use crate::utils::collect_upload_summary;
+use crate::output::json_output;
+use crate::utils::{collect_upload_summary, print_upload_details};
 use crate::wallet::load_wallet;
-use autonomi::client::address::addr_to_str;
+use autonomi::client::address::{addr_to_str, str_to_addr};
+use autonomi::client::files::journal::{UploadJournal, UploadedArchive};
+use autonomi::client::files::versioned::VersionedArchive;
+use autonomi::client::pointer::PointerAddress;
+use autonomi::client::receipts::ReceiptStore;
+use autonomi::client::Amount;
 use autonomi::Multiaddr;
+use color_eyre::eyre::eyre;
 use color_eyre::eyre::Context;
 use color_eyre::eyre::Result;
 use color_eyre::Section;
+use serde_json::json;
 use std::path::PathBuf;
 
 pub async fn cost(file: &str, peers: Vec<Multiaddr>) -> Result<()> {
     let client = crate::actions::connect_to_network(peers).await?;
 
-    println!("Getting upload cost...");
+    human_println!("Getting upload cost...");
     info!("Calculating cost for file: {file}");
     let cost = client
         .file_cost(&PathBuf::from(file))
         .await
         .wrap_err("Failed to calculate cost for file")?;
 
-    println!("Estimate cost to upload file: {file}");
-    println!("Total cost: {cost}");
+    human_println!("Estimate cost to upload file: {file}");
+    human_println!("Total cost: {cost}");
     info!("Total cost: {cost} for file: {file}");
-    Ok(())
+    json_output(&json!({ "file": file, "cost": cost.as_atto().to_string() }))
 }
 
-pub async fn upload(file: &str, public: bool, peers: Vec<Multiaddr>) -> Result<()> {
+pub async fn upload(
+    file: &str,
+    public: bool,
+    resume: bool,
+    max_cost: Option<Amount>,
+    confirm: bool,
+    receipt: Option<String>,
+    peers: Vec<Multiaddr>,
+) -> Result<()> {
     let wallet = load_wallet()?;
+    let dir_path = PathBuf::from(file);
+    let journal_path = crate::data_dir::get_upload_journal_path(&dir_path, public)?;
+    let journal = if resume {
+        UploadJournal::load(journal_path.clone())
+            .wrap_err(format!(
+                "Failed to load the upload journal at {journal_path:?}"
+            ))
+            .with_suggestion(|| {
+                "No interrupted upload of this file was found, try again without --resume"
+            })?
+    } else {
+        UploadJournal::new(journal_path, dir_path.clone(), public)
+    };
+    if let Some(id) = receipt {
+        let stored = ReceiptStore::new(crate::data_dir::get_receipts_dir_path()?)
+            .load(&id)
+            .wrap_err(format!("Failed to load the payment receipt {id}"))
+            .with_suggestion(|| "List the stored receipts with `wallet receipts list`")?;
+        let reused = journal
+            .reuse_receipt(stored)
+            .wrap_err("Failed to record the payments of the receipt in the upload journal")?;
+        human_println!("Reusing {reused} payments of receipt {id}");
+    }
+
     let mut client = crate::actions::connect_to_network(peers).await?;
     let event_receiver = client.enable_client_events();
     let (upload_summary_thread, upload_completed_tx) = collect_upload_summary(event_receiver);
 
-    println!("Uploading data to network...");
-    info!(
-        "Uploading {} file: {file}",
-        if public { "public" } else { "private" }
-    );
-
-    let dir_path = PathBuf::from(file);
     let name = dir_path
         .file_name()
         .map(|n| n.to_string_lossy().to_string())
         .unwrap_or(file.to_string());
 
-    // upload dir
-    let local_addr;
-    let archive = if public {
-        let xor_name = client
-            .dir_and_archive_upload_public(dir_path, &wallet)
+    let uploaded = if max_cost.is_some() || confirm {
+        // get the quotes first, then pay exactly for what was approved
+        human_println!("Getting upload cost...");
+        let prepared = client
+            .dir_prepare_upload(dir_path.clone(), public)
             .await
-            .wrap_err("Failed to upload file")?;
-        local_addr = addr_to_str(xor_name);
-        local_addr.clone()
-    } else {
-        let private_data_access = client
-            .dir_and_archive_upload(dir_path, &wallet)
+            .wrap_err("Failed to calculate cost for file")?;
+        if let Err(err) = approve_upload_cost(prepared.total_cost().as_atto(), max_cost, confirm) {
+            prepared.abort();
+            return Err(err);
+        }
+
+        human_println!("Uploading data to network...");
+        info!(
+            "Uploading {} prepared files of {file}",
+            prepared.file_count()
+        );
+        prepared
+            .commit(&wallet)
             .await
-            .wrap_err("Failed to upload dir and archive")?;
+            .wrap_err("Failed to upload file")
+            .with_suggestion(|| {
+                "The quotes expire after an hour, run the same command again to get new ones"
+            })?
+    } else {
+        if resume {
+            human_println!("Resuming upload of data to network...");
+        } else {
+            human_println!("Uploading data to network...");
+        }
+        info!(
+            "Uploading {} file: {file}",
+            if public { "public" } else { "private" }
+        );
 
-        local_addr = private_data_access.address();
-        private_data_access.to_hex()
+        // upload dir
+        client
+            .resume_upload(&journal, &wallet)
+            .await
+            .wrap_err("Failed to upload file")
+            .with_suggestion(|| "The progress of the upload was saved, run the same command with --resume to continue where it stopped")?
+    };
+    let local_addr;
+    let archive = match uploaded {
+        UploadedArchive::Public(xor_name) => {
+            local_addr = addr_to_str(xor_name);
+            local_addr.clone()
+        }
+        UploadedArchive::Private(private_data_access) => {
+            local_addr = private_data_access.address();
+            private_data_access.to_hex()
+        }
     };
+    if let Err(e) = journal.remove() {
+        warn!("Failed to remove the upload journal: {e:?}");
+    }
 
     // wait for upload to complete
     if let Err(e) = upload_completed_tx.send(()) {
@@ -77,13 +148,14 @@ pub async fn upload(file: &str, public: bool, peers: Vec<Multiaddr>) -> Result<(
     // get summary
     let summary = upload_summary_thread.await?;
     if summary.record_count == 0 {
-        println!("All chunks already exist on the network.");
+        human_println!("All chunks already exist on the network.");
     } else {
-        println!("Successfully uploaded: {file}");
-        println!("At address: {local_addr}");
+        human_println!("Successfully uploaded: {file}");
+        human_println!("At address: {local_addr}");
         info!("Successfully uploaded: {file} at address: {local_addr}");
-        println!("Number of chunks uploaded: {}", summary.record_count);
-        println!("Total cost: {} AttoTokens", summary.tokens_spent);
+        human_println!("Number of chunks uploaded: {}", summary.record_count);
+        human_println!("Total cost: {} AttoTokens", summary.tokens_spent);
+        print_upload_details(&summary);
     }
     info!("Summary for upload of file {file} at {local_addr:?}: {summary:?}");
 
@@ -91,49 +163,352 @@ pub async fn upload(file: &str, public: bool, peers: Vec<Multiaddr>) -> Result<(
     let writer = if public {
         crate::user_data::write_local_public_file_archive(archive, &name)
     } else {
-        crate::user_data::write_local_private_file_archive(archive, local_addr, &name)
+        crate::user_data::write_local_private_file_archive(archive, local_addr.clone(), &name)
     };
     writer
         .wrap_err("Failed to save file to local user data")
         .with_suggestion(|| "Local user data saves the file address above to disk, without it you need to keep track of the address yourself")?;
     info!("Saved file to local user data");
 
+    json_output(&json!({
+        "file": file,
+        "address": local_addr,
+        "public": public,
+        "record_count": summary.record_count,
+        "tokens_spent": summary.tokens_spent.to_string(),
+        "gas_spent": summary.gas_spent.to_string(),
+        "tx_hashes": summary.tx_hashes.iter().map(|tx_hash| tx_hash.to_string()).collect::<Vec<_>>(),
+        "chunk_prices": summary
+            .chunk_prices
+            .iter()
+            .map(|(xor_name, price)| (hex::encode(xor_name), price.to_string()))
+            .collect::<std::collections::BTreeMap<_, _>>(),
+        "retries": summary.retries,
+        "bytes_uploaded": summary.bytes_uploaded,
+        "elapsed_secs": summary.elapsed.as_secs_f64(),
+    }))
+}
+
+/// Check the quoted cost of an upload against the maximum cost, and ask the user to confirm it.
+fn approve_upload_cost(cost: Amount, max_cost: Option<Amount>, confirm: bool) -> Result<()> {
+    human_println!("Cost of the upload: {cost} AttoTokens");
+    info!("Quoted upload cost: {cost}, max cost: {max_cost:?}");
+
+    if let Some(max_cost) = max_cost {
+        if cost > max_cost {
+            return Err(eyre!(
+                "The cost of {cost} AttoTokens is above the maximum of {max_cost} AttoTokens"
+            ))
+            .with_suggestion(|| "Raise --max-cost or upload fewer files at once");
+        }
+    }
+
+    if confirm {
+        prompt_println!("Proceed with the upload? [y/N]");
+        let mut answer = String::new();
+        std::io::stdin().read_line(&mut answer)?;
+        if !matches!(answer.trim(), "y" | "Y" | "yes") {
+            return Err(eyre!("The upload was cancelled, nothing was paid"));
+        }
+    }
     Ok(())
 }
 
 pub async fn download(addr: &str, dest_path: &str, peers: Vec<Multiaddr>) -> Result<()> {
     let mut client = crate::actions::connect_to_network(peers).await?;
-    crate::actions::download(addr, dest_path, &mut client).await
+    crate::actions::download(addr, dest_path, &mut client).await?;
+    json_output(&json!({ "address": addr, "destination": dest_path }))
+}
+
+pub async fn sync(dir: &str, addr: &str, peers: Vec<Multiaddr>) -> Result<()> {
+    let wallet = load_wallet()?;
+    let public_address = str_to_addr(addr).ok();
+    let private_address = crate::user_data::get_local_private_archive_access(addr)
+        .inspect_err(|e| error!("Failed to get private archive access: {e}"))
+        .ok();
+    let is_public = public_address.is_some();
+
+    let mut client = crate::actions::connect_to_network(peers).await?;
+    let event_receiver = client.enable_client_events();
+    let (upload_summary_thread, upload_completed_tx) = collect_upload_summary(event_receiver);
+
+    human_println!("Syncing {dir} with archive {addr}...");
+    info!("Syncing {dir} with archive {addr}");
+
+    let dir_path = PathBuf::from(dir);
+    let name = dir_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or(dir.to_string());
+
+    let (local_addr, archive, diff) = match (public_address, private_address) {
+        (Some(public_address), _) => {
+            let previous = client
+                .archive_get_public(public_address)
+                .await
+                .wrap_err("Failed to fetch the previous archive")?;
+            let (new_archive, diff) = client
+                .dir_sync_public(dir_path, &previous, &wallet)
+                .await
+                .wrap_err("Failed to sync directory")?;
+            let xor_name = client
+                .archive_put_public(&new_archive, &wallet)
+                .await
+                .wrap_err("Failed to upload the new archive")?;
+            (addr_to_str(xor_name), addr_to_str(xor_name), diff)
+        }
+        (_, Some(private_address)) => {
+            let previous = client
+                .archive_get(private_address)
+                .await
+                .wrap_err("Failed to fetch the previous archive")?;
+            let (new_archive, diff) = client
+                .dir_sync(dir_path, &previous, &wallet)
+                .await
+                .wrap_err("Failed to sync directory")?;
+            let private_data_access = client
+                .archive_put(&new_archive, (&wallet).into())
+                .await
+                .wrap_err("Failed to upload the new archive")?;
+            (
+                private_data_access.address(),
+                private_data_access.to_hex(),
+                diff,
+            )
+        }
+        _ => {
+            return Err(eyre!("Failed to parse archive address {addr}")).with_suggestion(|| {
+                "Try the `file list` command to get addresses you have access to"
+            })
+        }
+    };
+
+    // wait for upload to complete
+    if let Err(e) = upload_completed_tx.send(()) {
+        error!("Failed to send upload completed event: {e:?}");
+        eprintln!("Failed to send upload completed event: {e:?}");
+    }
+    let summary = upload_summary_thread.await?;
+
+    for path in &diff.added {
+        human_println!("+ {}", path.display());
+    }
+    for path in &diff.modified {
+        human_println!("~ {}", path.display());
+    }
+    for path in &diff.removed {
+        human_println!("- {}", path.display());
+    }
+    human_println!(
+        "{} added, {} modified, {} removed, {} unchanged",
+        diff.added.len(),
+        diff.modified.len(),
+        diff.removed.len(),
+        diff.unchanged.len()
+    );
+    human_println!("New archive address: {local_addr}");
+    human_println!("Number of chunks uploaded: {}", summary.record_count);
+    human_println!("Total cost: {} AttoTokens", summary.tokens_spent);
+    print_upload_details(&summary);
+    info!("Summary for sync of {dir} at {local_addr:?}: {diff:?} {summary:?}");
+
+    // save to local user data
+    let writer = if is_public {
+        crate::user_data::write_local_public_file_archive(archive, &name)
+    } else {
+        crate::user_data::write_local_private_file_archive(archive, local_addr.clone(), &name)
+    };
+    writer
+        .wrap_err("Failed to save file to local user data")
+        .with_suggestion(|| "Local user data saves the file address above to disk, without it you need to keep track of the address yourself")?;
+    info!("Saved new archive to local user data");
+
+    json_output(&json!({
+        "address": local_addr,
+        "added": diff.added,
+        "modified": diff.modified,
+        "removed": diff.removed,
+        "unchanged": diff.unchanged,
+        "record_count": summary.record_count,
+        "tokens_spent": summary.tokens_spent.to_string(),
+    }))
+}
+
+pub async fn publish(dir: &str, name: &str, peers: Vec<Multiaddr>) -> Result<()> {
+    let wallet = load_wallet()?;
+    let key = crate::keys::get_register_signing_key()
+        .wrap_err("The register key is required to perform this action")?;
+    let versioned = VersionedArchive::new(&key, name);
+
+    let mut client = crate::actions::connect_to_network(peers).await?;
+    let event_receiver = client.enable_client_events();
+    let (upload_summary_thread, upload_completed_tx) = collect_upload_summary(event_receiver);
+
+    human_println!("Publishing {dir} as {name}...");
+    info!("Publishing {dir} as {name}");
+    let archive = client
+        .dir_and_archive_upload_public(PathBuf::from(dir), &wallet)
+        .await
+        .wrap_err("Failed to upload directory")?;
+    let version = client
+        .versioned_archive_publish(&versioned, archive, &wallet)
+        .await
+        .wrap_err("Failed to publish the new version of the archive")?;
+
+    // wait for upload to complete
+    if let Err(e) = upload_completed_tx.send(()) {
+        error!("Failed to send upload completed event: {e:?}");
+        eprintln!("Failed to send upload completed event: {e:?}");
+    }
+    let summary = upload_summary_thread.await?;
+
+    let address = versioned.address().to_hex();
+    human_println!("Published version {} of {name}", version.version);
+    human_println!("Archive address: {}", addr_to_str(version.archive));
+    human_println!("Stable address: {address}");
+    human_println!("Number of chunks uploaded: {}", summary.record_count);
+    human_println!("Total cost: {} AttoTokens", summary.tokens_spent);
+    print_upload_details(&summary);
+    info!("Published {dir} as version {version:?} of {name} at {address}: {summary:?}");
+
+    // save the latest version to local user data
+    crate::user_data::write_local_public_file_archive(addr_to_str(version.archive), name)
+        .wrap_err("Failed to save file to local user data")
+        .with_suggestion(|| "Local user data saves the file address above to disk, without it you need to keep track of the address yourself")?;
+
+    json_output(&json!({
+        "name": name,
+        "version": version.version,
+        "archive": addr_to_str(version.archive),
+        "address": address,
+        "record_count": summary.record_count,
+        "tokens_spent": summary.tokens_spent.to_string(),
+    }))
+}
+
+pub async fn history(pointer: &str, peers: Vec<Multiaddr>) -> Result<()> {
+    let address = str_to_addr(pointer)
+        .map(PointerAddress::new)
+        .wrap_err("Failed to parse the address of the named archive")
+        .with_suggestion(|| {
+            "the address is the stable address printed by the `file publish` command"
+        })?;
+    let client = crate::actions::connect_to_network(peers).await?;
+
+    let versions = client
+        .versioned_archive_history(address)
+        .await
+        .wrap_err("Failed to get the history of the archive")?;
+    for version in &versions {
+        human_println!("{}: {}", version.version, addr_to_str(version.archive));
+    }
+    let versions: Vec<_> = versions
+        .iter()
+        .map(|version| json!({ "version": version.version, "archive": addr_to_str(version.archive) }))
+        .collect();
+    json_output(&json!({ "versions": versions }))
+}
+
+#[cfg(feature = "fuse")]
+pub async fn mount(addr: &str, mountpoint: &str, peers: Vec<Multiaddr>) -> Result<()> {
+    use crate::actions::{ArchiveFs, FileSource};
+
+    let public_address = str_to_addr(addr).ok();
+    let private_address = crate::user_data::get_local_private_archive_access(addr)
+        .inspect_err(|e| error!("Failed to get private archive access: {e}"))
+        .ok();
+
+    let client = crate::actions::connect_to_network(peers).await?;
+    let files: Vec<_> = match (public_address, private_address) {
+        (Some(public_address), _) => client
+            .archive_get_public(public_address)
+            .await
+            .wrap_err("Failed to fetch archive")?
+            .iter()
+            .map(|(path, addr, meta)| (path.clone(), FileSource::Public(*addr), meta.clone()))
+            .collect(),
+        (_, Some(private_address)) => client
+            .archive_get(private_address)
+            .await
+            .wrap_err("Failed to fetch archive")?
+            .iter()
+            .map(|(path, access, meta)| {
+                (
+                    path.clone(),
+                    FileSource::Private(access.clone()),
+                    meta.clone(),
+                )
+            })
+            .collect(),
+        _ => {
+            return Err(eyre!("Failed to parse archive address {addr}")).with_suggestion(|| {
+                "Try the `file list` command to get addresses you have access to"
+            })
+        }
+    };
+
+    let file_count = files.len();
+    let filesystem = ArchiveFs::new(client, files);
+    let options = [
+        fuser::MountOption::RO,
+        fuser::MountOption::FSName("autonomi".to_string()),
+        fuser::MountOption::Subtype("ant".to_string()),
+    ];
+    let session = fuser::spawn_mount2(filesystem, mountpoint, &options)
+        .wrap_err(format!("Failed to mount archive on {mountpoint}"))
+        .with_suggestion(|| "make sure the mountpoint is an existing empty directory and that FUSE is available (fusermount on Linux)")?;
+
+    human_println!("Mounted archive {addr} ({file_count} files) on {mountpoint}");
+    json_output(&json!({ "address": addr, "mountpoint": mountpoint, "files": file_count }))?;
+    human_println!("Press Ctrl+C to unmount");
+    info!("Mounted archive {addr} on {mountpoint}");
+
+    tokio::signal::ctrl_c()
+        .await
+        .wrap_err("Failed to listen for Ctrl+C")?;
+    drop(session);
+    human_println!("Unmounted {mountpoint}");
+    info!("Unmounted archive {addr} from {mountpoint}");
+    Ok(())
 }
 
 pub fn list() -> Result<()> {
     // get public file archives
-    println!("Retrieving local user data...");
+    human_println!("Retrieving local user data...");
     let file_archives = crate::user_data::get_local_public_file_archives()
         .wrap_err("Failed to get local public file archives")?;
 
-    println!(
+    human_println!(
         "✅ You have {} public file archive(s):",
         file_archives.len()
     );
-    for (addr, name) in file_archives {
-        println!("{}: {}", name, addr_to_str(addr));
+    for (addr, name) in &file_archives {
+        human_println!("{}: {}", name, addr_to_str(*addr));
     }
 
     // get private file archives
-    println!();
+    human_println!();
     let private_file_archives = crate::user_data::get_local_private_file_archives()
         .wrap_err("Failed to get local private file archives")?;
 
-    println!(
+    human_println!(
         "✅ You have {} private file archive(s):",
         private_file_archives.len()
     );
-    for (addr, name) in private_file_archives {
-        println!("{}: {}", name, addr.address());
+    for (addr, name) in &private_file_archives {
+        human_println!("{}: {}", name, addr.address());
     }
 
-    println!();
-    println!("> Note that private data addresses are not network addresses, they are only used for referring to private data client side.");
-    Ok(())
+    human_println!();
+    human_println!("> Note that private data addresses are not network addresses, they are only used for referring to private data client side.");
+
+    let public: Vec<_> = file_archives
+        .iter()
+        .map(|(addr, name)| json!({ "name": name, "address": addr_to_str(*addr) }))
+        .collect();
+    let private: Vec<_> = private_file_archives
+        .iter()
+        .map(|(addr, name)| json!({ "name": name, "address": addr.address() }))
+        .collect();
+    json_output(&json!({ "public": public, "private": private }))
 }
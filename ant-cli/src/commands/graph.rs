@@ -0,0 +1,234 @@
+// Copyright 2024 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use crate::output::{is_json, print_json};
+use crate::utils::collect_upload_summary;
+use crate::wallet::load_wallet;
+use autonomi::client::address::str_to_addr;
+use autonomi::client::graph::{
+    GraphEntry, GraphEntryAddress, PublicKey, WalkDirection, WalkLimits,
+};
+use autonomi::Multiaddr;
+use color_eyre::eyre::eyre;
+use color_eyre::eyre::Context;
+use color_eyre::eyre::Result;
+use color_eyre::Section;
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct GraphEntryOutput {
+    address: String,
+    owner: String,
+    parents: Vec<String>,
+    content: String,
+    outputs: Vec<(String, String)>,
+}
+
+impl From<&GraphEntry> for GraphEntryOutput {
+    fn from(entry: &GraphEntry) -> Self {
+        Self {
+            address: entry.address().to_hex(),
+            owner: entry.owner.to_hex(),
+            parents: entry.parents.iter().map(|parent| parent.to_hex()).collect(),
+            content: hex::encode(entry.content),
+            outputs: entry
+                .outputs
+                .iter()
+                .flatten()
+                .map(|(key, content)| (key.to_hex(), hex::encode(content)))
+                .collect(),
+        }
+    }
+}
+
+fn print_entry(entry: &GraphEntryOutput) {
+    human_println!("Address: {}", entry.address);
+    human_println!("Owner: {}", entry.owner);
+    human_println!("Content: {}", entry.content);
+    for parent in &entry.parents {
+        human_println!("Parent: {parent}");
+    }
+    for (key, content) in &entry.outputs {
+        human_println!("Output: {key} {content}");
+    }
+}
+
+pub async fn put(content: &str, parents: &[String], peers: Vec<Multiaddr>) -> Result<()> {
+    let content: [u8; 32] = hex::decode(content)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| eyre!("Failed to parse graph entry content"))
+        .with_suggestion(|| "the content should be 32 hex encoded bytes")?;
+    let parents = parents
+        .iter()
+        .map(|parent| PublicKey::from_hex(parent))
+        .collect::<Result<Vec<_>, _>>()
+        .wrap_err("Failed to parse parent")
+        .with_suggestion(|| {
+            "a parent is the hex encoded public key of the owner of the parent entry"
+        })?;
+    let wallet = load_wallet()?;
+    let key = crate::keys::get_graph_signing_key()
+        .wrap_err("The graph key is required to perform this action")?;
+    let mut client = crate::actions::connect_to_network(peers).await?;
+    let event_receiver = client.enable_client_events();
+    let (upload_summary_thread, upload_completed_tx) = collect_upload_summary(event_receiver);
+
+    let entry = GraphEntry::new(key.public_key(), parents, content, None, &key);
+    info!("Putting graph entry at {:?}", entry.address());
+    client
+        .transaction_put(entry.clone(), &wallet)
+        .await
+        .wrap_err("Failed to put graph entry")?;
+
+    if let Err(e) = upload_completed_tx.send(()) {
+        error!("Failed to send upload completed event: {e:?}");
+        eprintln!("Failed to send upload completed event: {e:?}");
+    }
+    let summary = upload_summary_thread.await?;
+
+    // entries without parents start a new graph
+    if entry.parents.is_empty() {
+        crate::user_data::write_local_graph_root(&entry.address(), "")
+            .wrap_err("Failed to save graph to local user data")?;
+        info!("Saved graph to local user data");
+    }
+
+    let output = GraphEntryOutput::from(&entry);
+    if is_json() {
+        return print_json(&output);
+    }
+    human_println!("✅ Graph entry put");
+    print_entry(&output);
+    human_println!("Total cost: {} AttoTokens", summary.tokens_spent);
+    Ok(())
+}
+
+pub async fn get(address: &str, peers: Vec<Multiaddr>) -> Result<()> {
+    let address = str_to_addr(address)
+        .map(GraphEntryAddress::new)
+        .wrap_err("Failed to parse graph entry address")?;
+    let client = crate::actions::connect_to_network(peers).await?;
+
+    let entries = client
+        .transaction_get(address)
+        .await
+        .wrap_err("Failed to get graph entry")?;
+    let outputs: Vec<GraphEntryOutput> = entries.iter().map(GraphEntryOutput::from).collect();
+    if is_json() {
+        return print_json(&outputs);
+    }
+    for output in &outputs {
+        print_entry(output);
+    }
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct GraphWalkOutput {
+    entries: Vec<GraphEntryOutput>,
+    forks: Vec<String>,
+    cycles: Vec<(String, String)>,
+    missing: Vec<String>,
+    unexplored: Vec<String>,
+}
+
+pub async fn walk(
+    address: &str,
+    backward: bool,
+    dot: Option<String>,
+    limits: WalkLimits,
+    peers: Vec<Multiaddr>,
+) -> Result<()> {
+    let address = str_to_addr(address)
+        .map(GraphEntryAddress::new)
+        .wrap_err("Failed to parse graph entry address")?;
+    let client = crate::actions::connect_to_network(peers).await?;
+
+    let direction = if backward {
+        WalkDirection::Backward
+    } else {
+        WalkDirection::Forward
+    };
+    let graph = client
+        .graph_walk(address, direction, limits)
+        .await
+        .wrap_err("Failed to walk the graph")?;
+
+    if let Some(path) = dot {
+        std::fs::write(&path, graph.to_dot())
+            .wrap_err_with(|| format!("Failed to write the graph to {path}"))?;
+        info!("Wrote the graph walked from {address:?} to {path}");
+    }
+
+    let output = GraphWalkOutput {
+        entries: graph
+            .iter()
+            .flat_map(|node| node.entries.iter().map(GraphEntryOutput::from))
+            .collect(),
+        forks: graph.forks().iter().map(|fork| fork.to_hex()).collect(),
+        cycles: graph
+            .cycles()
+            .iter()
+            .map(|(from, to)| (from.to_hex(), to.to_hex()))
+            .collect(),
+        missing: graph
+            .missing()
+            .iter()
+            .map(|missing| missing.to_hex())
+            .collect(),
+        unexplored: graph
+            .unexplored()
+            .iter()
+            .map(|unexplored| unexplored.to_hex())
+            .collect(),
+    };
+    if is_json() {
+        return print_json(&output);
+    }
+    for node in graph.iter() {
+        human_println!("Depth {}: {}", node.depth, node.address.to_hex());
+        for entry in &node.entries {
+            human_println!("  Content: {}", hex::encode(entry.content));
+        }
+    }
+    human_println!("{} entries walked", output.entries.len());
+    for fork in &output.forks {
+        human_println!("Fork at {fork}");
+    }
+    for (from, to) in &output.cycles {
+        human_println!("Cycle closed by {from} -> {to}");
+    }
+    for missing in &output.missing {
+        human_println!("Not stored yet: {missing}");
+    }
+    if !output.unexplored.is_empty() {
+        human_println!(
+            "The walk stopped at its limits, {} entries left unexplored",
+            output.unexplored.len()
+        );
+    }
+    Ok(())
+}
+
+pub async fn cost(peers: Vec<Multiaddr>) -> Result<()> {
+    let key = crate::keys::get_graph_signing_key()
+        .wrap_err("The graph key is required to perform this action")?;
+    let client = crate::actions::connect_to_network(peers).await?;
+
+    let cost = client
+        .transaction_cost(key)
+        .await
+        .wrap_err("Failed to get cost for graph entry")?;
+    info!("Estimated cost to put a graph entry: {cost}");
+    if is_json() {
+        return print_json(&serde_json::json!({ "cost": cost.as_atto().to_string() }));
+    }
+    human_println!("✅ The estimated cost to put a graph entry is: {cost}");
+    Ok(())
+}
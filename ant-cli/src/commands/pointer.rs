@@ -0,0 +1,168 @@
+// Copyright 2024 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use crate::commands::PointerTargetType;
+use crate::output::{is_json, print_json};
+use crate::wallet::load_wallet;
+use ant_protocol::storage::ScratchpadAddress;
+use autonomi::client::address::str_to_addr;
+use autonomi::client::graph::GraphEntryAddress;
+use autonomi::client::pointer::{Pointer, PointerAddress, PointerTarget};
+use autonomi::{ChunkAddress, Multiaddr};
+use color_eyre::eyre::Context;
+use color_eyre::eyre::Result;
+use color_eyre::Section;
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct PointerOutput {
+    address: String,
+    owner: String,
+    counter: u32,
+    target_type: &'static str,
+    target: String,
+}
+
+impl From<&Pointer> for PointerOutput {
+    fn from(pointer: &Pointer) -> Self {
+        let (target_type, target) = match pointer.target() {
+            PointerTarget::ChunkAddress(addr) => ("chunk", addr.to_hex()),
+            PointerTarget::GraphEntryAddress(addr) => ("graph", addr.to_hex()),
+            PointerTarget::PointerAddress(addr) => ("pointer", addr.to_hex()),
+            PointerTarget::ScratchpadAddress(addr) => ("scratchpad", addr.to_hex()),
+        };
+        Self {
+            address: pointer.network_address().to_hex(),
+            owner: pointer.owner().to_hex(),
+            counter: pointer.count(),
+            target_type,
+            target,
+        }
+    }
+}
+
+fn print_pointer(pointer: &Pointer) -> Result<()> {
+    let output = PointerOutput::from(pointer);
+    if is_json() {
+        return print_json(&output);
+    }
+    human_println!("Address: {}", output.address);
+    human_println!("Owner: {}", output.owner);
+    human_println!("Counter: {}", output.counter);
+    human_println!("Target ({}): {}", output.target_type, output.target);
+    Ok(())
+}
+
+fn parse_target(target: &str, target_type: PointerTargetType) -> Result<PointerTarget> {
+    let target = match target_type {
+        PointerTargetType::Scratchpad => PointerTarget::ScratchpadAddress(
+            ScratchpadAddress::from_hex(target)
+                .wrap_err("Failed to parse scratchpad address")
+                .with_suggestion(|| {
+                    "a scratchpad address is the hex encoded public key of its owner"
+                })?,
+        ),
+        _ => {
+            let xor_name = str_to_addr(target)
+                .wrap_err("Failed to parse target address")
+                .with_suggestion(|| "the target should be a hex encoded address")?;
+            match target_type {
+                PointerTargetType::Chunk => {
+                    PointerTarget::ChunkAddress(ChunkAddress::new(xor_name))
+                }
+                PointerTargetType::Graph => {
+                    PointerTarget::GraphEntryAddress(GraphEntryAddress::new(xor_name))
+                }
+                _ => PointerTarget::PointerAddress(PointerAddress::new(xor_name)),
+            }
+        }
+    };
+    Ok(target)
+}
+
+pub async fn create(
+    target: &str,
+    target_type: PointerTargetType,
+    peers: Vec<Multiaddr>,
+) -> Result<()> {
+    let target = parse_target(target, target_type)?;
+    let wallet = load_wallet()?;
+    let key = crate::keys::get_pointer_signing_key()
+        .wrap_err("The pointer key is required to perform this action")?;
+    let client = crate::actions::connect_to_network(peers).await?;
+
+    let pointer = Pointer::new(key.public_key(), 0, target, &key);
+    info!("Creating pointer at {:?}", pointer.network_address());
+    client
+        .pointer_put(pointer.clone(), &wallet)
+        .await
+        .wrap_err("Failed to create pointer")
+        .with_suggestion(|| {
+            "a pointer can only be created once, use `pointer update` to change its target"
+        })?;
+    crate::user_data::write_local_pointer(&pointer.network_address(), "")
+        .wrap_err("Failed to save pointer to local user data")?;
+    info!("Saved pointer to local user data");
+
+    human_println!("✅ Pointer created");
+    print_pointer(&pointer)
+}
+
+pub async fn get(address: &str, peers: Vec<Multiaddr>) -> Result<()> {
+    let address = str_to_addr(address)
+        .map(PointerAddress::new)
+        .wrap_err("Failed to parse pointer address")?;
+    let client = crate::actions::connect_to_network(peers).await?;
+
+    let pointer = client
+        .pointer_get(address)
+        .await
+        .wrap_err("Failed to get pointer")?;
+    print_pointer(&pointer)
+}
+
+pub async fn update(
+    target: &str,
+    target_type: PointerTargetType,
+    peers: Vec<Multiaddr>,
+) -> Result<()> {
+    let target = parse_target(target, target_type)?;
+    let key = crate::keys::get_pointer_signing_key()
+        .wrap_err("The pointer key is required to perform this action")?;
+    let client = crate::actions::connect_to_network(peers).await?;
+
+    info!(
+        "Updating pointer at {:?}",
+        PointerAddress::from_owner(key.public_key())
+    );
+    let pointer = client
+        .pointer_update(&key, target)
+        .await
+        .wrap_err("Failed to update pointer")
+        .with_suggestion(|| "use `pointer create` to create the pointer first")?;
+
+    human_println!("✅ Pointer updated");
+    print_pointer(&pointer)
+}
+
+pub async fn cost(peers: Vec<Multiaddr>) -> Result<()> {
+    let key = crate::keys::get_pointer_signing_key()
+        .wrap_err("The pointer key is required to perform this action")?;
+    let client = crate::actions::connect_to_network(peers).await?;
+
+    let cost = client
+        .pointer_cost(key)
+        .await
+        .wrap_err("Failed to get cost for pointer")?;
+    info!("Estimated cost to create a pointer: {cost}");
+    if is_json() {
+        return print_json(&serde_json::json!({ "cost": cost.as_atto().to_string() }));
+    }
+    human_println!("✅ The estimated cost to create a pointer is: {cost}");
+    Ok(())
+}
@@ -8,6 +8,7 @@
 
 #![allow(deprecated)]
 
+use crate::output::json_output;
 use crate::utils::collect_upload_summary;
 use crate::wallet::load_wallet;
 use autonomi::client::registers::RegisterAddress;
@@ -19,6 +20,7 @@ use color_eyre::eyre::eyre;
 use color_eyre::eyre::Context;
 use color_eyre::eyre::Result;
 use color_eyre::Section;
+use serde_json::json;
 
 pub fn generate_key(overwrite: bool) -> Result<()> {
     // check if the key already exists
@@ -35,8 +37,8 @@ pub fn generate_key(overwrite: bool) -> Result<()> {
     let path = crate::keys::create_register_signing_key_file(key)
         .wrap_err("Failed to create new register key")?;
     info!("Created new register key at: {path:?}");
-    println!("✅ Created new register key at: {}", path.display());
-    Ok(())
+    human_println!("✅ Created new register key at: {}", path.display());
+    json_output(&json!({ "path": path }))
 }
 
 pub async fn cost(name: &str, peers: Vec<Multiaddr>) -> Result<()> {
@@ -49,8 +51,8 @@ pub async fn cost(name: &str, peers: Vec<Multiaddr>) -> Result<()> {
         .await
         .wrap_err("Failed to get cost for register")?;
     info!("Estimated cost to create a register with name {name}: {cost}");
-    println!("✅ The estimated cost to create a register with name {name} is: {cost}");
-    Ok(())
+    human_println!("✅ The estimated cost to create a register with name {name} is: {cost}");
+    json_output(&json!({ "name": name, "cost": cost.as_atto().to_string() }))
 }
 
 pub async fn create(name: &str, value: &str, public: bool, peers: Vec<Multiaddr>) -> Result<()> {
@@ -61,10 +63,10 @@ pub async fn create(name: &str, value: &str, public: bool, peers: Vec<Multiaddr>
     let event_receiver = client.enable_client_events();
     let (upload_summary_thread, upload_completed_tx) = collect_upload_summary(event_receiver);
 
-    println!("Creating register with name: {name}");
+    human_println!("Creating register with name: {name}");
     info!("Creating register with name: {name}");
     let register = if public {
-        println!("With public write access");
+        human_println!("With public write access");
         info!("With public write access");
         let permissions = RegisterPermissions::new_anyone_can_write();
         client
@@ -78,7 +80,7 @@ pub async fn create(name: &str, value: &str, public: bool, peers: Vec<Multiaddr>
             .await
             .wrap_err("Failed to create register")?
     } else {
-        println!("With private write access");
+        human_println!("With private write access");
         info!("With private write access");
         client
             .register_create(
@@ -100,14 +102,14 @@ pub async fn create(name: &str, value: &str, public: bool, peers: Vec<Multiaddr>
 
     let summary = upload_summary_thread.await?;
     if summary.record_count == 0 {
-        println!("✅ The register already exists on the network at address: {address}.");
-        println!("No tokens were spent.");
+        human_println!("✅ The register already exists on the network at address: {address}.");
+        human_println!("No tokens were spent.");
     } else {
-        println!("✅ Register created at address: {address}");
-        println!("With name: {name}");
-        println!("And initial value: [{value}]");
+        human_println!("✅ Register created at address: {address}");
+        human_println!("With name: {name}");
+        human_println!("And initial value: [{value}]");
         info!("Register created at address: {address} with name: {name}");
-        println!("Total cost: {} AttoTokens", summary.tokens_spent);
+        human_println!("Total cost: {} AttoTokens", summary.tokens_spent);
     }
     info!("Summary of register creation: {summary:?}");
 
@@ -116,7 +118,13 @@ pub async fn create(name: &str, value: &str, public: bool, peers: Vec<Multiaddr>
         .with_suggestion(|| "Local user data saves the register address above to disk, without it you need to keep track of the address yourself")?;
     info!("Saved register to local user data");
 
-    Ok(())
+    json_output(&json!({
+        "address": address.to_hex(),
+        "name": name,
+        "value": value,
+        "record_count": summary.record_count,
+        "tokens_spent": summary.tokens_spent.to_string(),
+    }))
 }
 
 pub async fn edit(address: String, name: bool, value: &str, peers: Vec<Multiaddr>) -> Result<()> {
@@ -134,15 +142,15 @@ pub async fn edit(address: String, name: bool, value: &str, peers: Vec<Multiaddr
             })?
     };
 
-    println!("Getting register at address: {address}");
+    human_println!("Getting register at address: {address}");
     info!("Getting register at address: {address}");
     let register = client
         .register_get(address)
         .await
         .wrap_err(format!("Failed to get register at address: {address}"))?;
 
-    println!("Found register at address: {address}");
-    println!("Updating register with new value: {value}");
+    human_println!("Found register at address: {address}");
+    human_println!("Updating register with new value: {value}");
     info!("Updating register at address: {address} with new value: {value}");
 
     client
@@ -150,11 +158,11 @@ pub async fn edit(address: String, name: bool, value: &str, peers: Vec<Multiaddr
         .await
         .wrap_err(format!("Failed to update register at address: {address}"))?;
 
-    println!("✅ Successfully updated register");
-    println!("With value: [{value}]");
+    human_println!("✅ Successfully updated register");
+    human_println!("With value: [{value}]");
     info!("Successfully updated register at address: {address}");
 
-    Ok(())
+    json_output(&json!({ "address": address.to_hex(), "value": value }))
 }
 
 pub async fn get(address: String, name: bool, peers: Vec<Multiaddr>) -> Result<()> {
@@ -172,7 +180,7 @@ pub async fn get(address: String, name: bool, peers: Vec<Multiaddr>) -> Result<(
             })?
     };
 
-    println!("Getting register at address: {address}");
+    human_println!("Getting register at address: {address}");
     info!("Getting register at address: {address}");
     let register = client
         .register_get(address)
@@ -180,26 +188,34 @@ pub async fn get(address: String, name: bool, peers: Vec<Multiaddr>) -> Result<(
         .wrap_err(format!("Failed to get register at address: {address}"))?;
     let values = register.values();
 
-    println!("✅ Register found at address: {address}");
+    human_println!("✅ Register found at address: {address}");
     info!("Register found at address: {address}");
     match values.as_slice() {
-        [one] => println!("With value: [{:?}]", String::from_utf8_lossy(one)),
+        [one] => human_println!("With value: [{:?}]", String::from_utf8_lossy(one)),
         _ => {
-            println!("With multiple concurrent values:");
+            human_println!("With multiple concurrent values:");
             for value in values.iter() {
-                println!("[{:?}]", String::from_utf8_lossy(value));
+                human_println!("[{:?}]", String::from_utf8_lossy(value));
             }
         }
     }
-    Ok(())
+    let values: Vec<_> = values
+        .iter()
+        .map(|value| String::from_utf8_lossy(value).to_string())
+        .collect();
+    json_output(&json!({ "address": address.to_hex(), "values": values }))
 }
 
 pub fn list() -> Result<()> {
-    println!("Retrieving local user data...");
+    human_println!("Retrieving local user data...");
     let registers = crate::user_data::get_local_registers()?;
-    println!("✅ You have {} register(s):", registers.len());
-    for (addr, name) in registers {
-        println!("{}: {}", name, addr.to_hex());
+    human_println!("✅ You have {} register(s):", registers.len());
+    for (addr, name) in &registers {
+        human_println!("{}: {}", name, addr.to_hex());
     }
-    Ok(())
+    let registers: Vec<_> = registers
+        .iter()
+        .map(|(addr, name)| json!({ "name": name, "address": addr.to_hex() }))
+        .collect();
+    json_output(&json!({ "registers": registers }))
 }
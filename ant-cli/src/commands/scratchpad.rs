@@ -0,0 +1,149 @@
+// Copyright 2024 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use crate::output::{is_json, print_json};
+use crate::wallet::load_wallet;
+use autonomi::client::scratchpad::{
+    data_encoding_from_name, DataEncoding, DataEncodingRegistry, ScratchpadAddress,
+};
+use autonomi::Multiaddr;
+use color_eyre::eyre::Context;
+use color_eyre::eyre::Result;
+use color_eyre::Section;
+use serde::Serialize;
+use std::sync::LazyLock;
+
+/// Data encoding of the scratchpads written by the CLI.
+const SCRATCHPAD_CONTENT_TYPE: &str = "ant-cli scratchpad";
+
+/// The data encodings the CLI can name.
+static DATA_ENCODINGS: LazyLock<DataEncodingRegistry> = LazyLock::new(|| {
+    let mut registry = DataEncodingRegistry::new();
+    let _ = registry.register(SCRATCHPAD_CONTENT_TYPE);
+    registry
+});
+
+#[derive(Serialize)]
+struct ScratchpadOutput {
+    address: String,
+    data_encoding: DataEncoding,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data_encoding_name: Option<String>,
+    counter: u64,
+    data: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cost: Option<String>,
+}
+
+impl ScratchpadOutput {
+    fn new(
+        address: &ScratchpadAddress,
+        data_encoding: DataEncoding,
+        counter: u64,
+        data: &[u8],
+    ) -> Self {
+        Self {
+            address: address.to_hex(),
+            data_encoding,
+            data_encoding_name: DATA_ENCODINGS.name(data_encoding).map(str::to_string),
+            counter,
+            data: String::from_utf8_lossy(data).to_string(),
+            cost: None,
+        }
+    }
+}
+
+fn print_scratchpad(output: &ScratchpadOutput) -> Result<()> {
+    if is_json() {
+        return print_json(output);
+    }
+    human_println!("Address: {}", output.address);
+    match &output.data_encoding_name {
+        Some(name) => human_println!("Data encoding: {} ({name})", output.data_encoding),
+        None => human_println!("Data encoding: {}", output.data_encoding),
+    }
+    human_println!("Counter: {}", output.counter);
+    human_println!("Data: {}", output.data);
+    if let Some(cost) = &output.cost {
+        human_println!("Total cost: {cost} AttoTokens");
+    }
+    Ok(())
+}
+
+pub async fn create(data: &str, peers: Vec<Multiaddr>) -> Result<()> {
+    let wallet = load_wallet()?;
+    let key = crate::keys::get_scratchpad_signing_key()
+        .wrap_err("The scratchpad key is required to perform this action")?;
+    let client = crate::actions::connect_to_network(peers).await?;
+
+    info!("Creating scratchpad");
+    let data_encoding = data_encoding_from_name(SCRATCHPAD_CONTENT_TYPE);
+    let (pad, cost) = client
+        .scratchpad_create(
+            &key,
+            data_encoding,
+            data.as_bytes().to_vec().into(),
+            (&wallet).into(),
+        )
+        .await
+        .wrap_err("Failed to create scratchpad")
+        .with_suggestion(|| {
+            "a scratchpad can only be created once, use `scratchpad update` to change its data"
+        })?;
+    crate::user_data::write_local_scratchpad(pad.address(), "")
+        .wrap_err("Failed to save scratchpad to local user data")?;
+    info!("Saved scratchpad to local user data");
+
+    human_println!("✅ Scratchpad created");
+    let mut output =
+        ScratchpadOutput::new(pad.address(), data_encoding, pad.count(), data.as_bytes());
+    output.cost = Some(cost.as_atto().to_string());
+    print_scratchpad(&output)
+}
+
+pub async fn get(peers: Vec<Multiaddr>) -> Result<()> {
+    let key = crate::keys::get_scratchpad_signing_key()
+        .wrap_err("The scratchpad key is required to perform this action")?;
+    let client = crate::actions::connect_to_network(peers).await?;
+
+    let pad = client
+        .scratchpad_get(&ScratchpadAddress::new(key.public_key()))
+        .await
+        .wrap_err("Failed to get scratchpad")
+        .with_suggestion(|| "use `scratchpad create` to create the scratchpad first")?;
+    let data = pad
+        .decrypt_data(&key)
+        .wrap_err("Failed to decrypt scratchpad")?;
+    print_scratchpad(&ScratchpadOutput::new(
+        pad.address(),
+        pad.data_encoding(),
+        pad.count(),
+        &data,
+    ))
+}
+
+pub async fn update(data: &str, peers: Vec<Multiaddr>) -> Result<()> {
+    let key = crate::keys::get_scratchpad_signing_key()
+        .wrap_err("The scratchpad key is required to perform this action")?;
+    let client = crate::actions::connect_to_network(peers).await?;
+
+    info!("Updating scratchpad");
+    let pad = client
+        .scratchpad_update(&key, |_| data.as_bytes().to_vec().into())
+        .await
+        .wrap_err("Failed to update scratchpad")
+        .with_suggestion(|| "use `scratchpad create` to create the scratchpad first")?;
+
+    human_println!("✅ Scratchpad updated");
+    print_scratchpad(&ScratchpadOutput::new(
+        pad.address(),
+        pad.data_encoding(),
+        pad.count(),
+        data.as_bytes(),
+    ))
+}
@@ -6,25 +6,53 @@
 // KIND, either express or implied. Please review the Licences for the specific language governing
 // permissions and limitations relating to use of the SAFE Network Software.
 
+use crate::output::json_output;
 use crate::wallet::load_wallet;
+use autonomi::client::vault::UserData;
 use autonomi::Multiaddr;
 use color_eyre::eyre::Context;
 use color_eyre::eyre::Result;
 use color_eyre::Section;
+use serde_json::json;
+
+fn print_user_data_counts(user_data: &UserData) {
+    human_println!("{} public file archive(s)", user_data.file_archives.len());
+    human_println!(
+        "{} private file archive(s)",
+        user_data.private_file_archives.len()
+    );
+    human_println!("{} register(s)", user_data.registers.len());
+    human_println!("{} pointer(s)", user_data.pointers.len());
+    human_println!("{} graph(s)", user_data.graph_roots.len());
+    human_println!("{} scratchpad(s)", user_data.scratchpads.len());
+    human_println!("{} register key(s)", user_data.register_keys.len());
+}
+
+fn user_data_counts(user_data: &UserData) -> serde_json::Value {
+    json!({
+        "file_archives": user_data.file_archives.len(),
+        "private_file_archives": user_data.private_file_archives.len(),
+        "registers": user_data.registers.len(),
+        "pointers": user_data.pointers.len(),
+        "graph_roots": user_data.graph_roots.len(),
+        "scratchpads": user_data.scratchpads.len(),
+        "register_keys": user_data.register_keys.len(),
+    })
+}
 
 pub async fn cost(peers: Vec<Multiaddr>) -> Result<()> {
     let client = crate::actions::connect_to_network(peers).await?;
     let vault_sk = crate::keys::get_vault_secret_key()?;
 
-    println!("Getting cost to create a new vault...");
+    human_println!("Getting cost to create a new vault...");
     let total_cost = client.vault_cost(&vault_sk).await?;
 
     if total_cost.is_zero() {
-        println!("Vault already exists, modifying an existing vault is free");
+        human_println!("Vault already exists, modifying an existing vault is free");
     } else {
-        println!("Cost to create a new vault: {total_cost} AttoTokens");
+        human_println!("Cost to create a new vault: {total_cost} AttoTokens");
     }
-    Ok(())
+    json_output(&json!({ "cost": total_cost.as_atto().to_string() }))
 }
 
 pub async fn create(peers: Vec<Multiaddr>) -> Result<()> {
@@ -32,27 +60,26 @@ pub async fn create(peers: Vec<Multiaddr>) -> Result<()> {
     let wallet = load_wallet()?;
     let vault_sk = crate::keys::get_vault_secret_key()?;
 
-    println!("Retrieving local user data...");
+    human_println!("Retrieving local user data...");
     let local_user_data = crate::user_data::get_local_user_data()?;
-    let file_archives_len = local_user_data.file_archives.len();
-    let private_file_archives_len = local_user_data.private_file_archives.len();
+    let mut output = user_data_counts(&local_user_data);
 
-    println!("Pushing to network vault...");
+    human_println!("Pushing to network vault...");
     let total_cost = client
-        .put_user_data_to_vault(&vault_sk, wallet.into(), local_user_data)
+        .put_user_data_to_vault(&vault_sk, wallet.into(), local_user_data.clone())
         .await?;
 
     if total_cost.is_zero() {
-        println!("✅ Successfully pushed user data to existing vault");
+        human_println!("✅ Successfully pushed user data to existing vault");
     } else {
-        println!("✅ Successfully created new vault containing local user data");
+        human_println!("✅ Successfully created new vault containing local user data");
     }
 
-    println!("Total cost: {total_cost} AttoTokens");
-    println!("Vault contains:");
-    println!("{file_archives_len} public file archive(s)");
-    println!("{private_file_archives_len} private file archive(s)");
-    Ok(())
+    human_println!("Total cost: {total_cost} AttoTokens");
+    human_println!("Vault contains:");
+    print_user_data_counts(&local_user_data);
+    output["tokens_spent"] = json!(total_cost.as_atto().to_string());
+    json_output(&output)
 }
 
 pub async fn sync(peers: Vec<Multiaddr>, force: bool) -> Result<()> {
@@ -60,49 +87,43 @@ pub async fn sync(peers: Vec<Multiaddr>, force: bool) -> Result<()> {
     let vault_sk = crate::keys::get_vault_secret_key()?;
     let wallet = load_wallet()?;
 
-    println!("Fetching vault from network...");
+    human_println!("Fetching vault from network...");
     let net_user_data = client
         .get_user_data_from_vault(&vault_sk)
         .await
         .wrap_err("Failed to fetch vault from network")
         .with_suggestion(|| "Make sure you have already created a vault on the network")?;
 
+    let mut local_user_data = crate::user_data::get_local_user_data()?;
     if force {
-        println!("The force flag was provided, overwriting user data in the vault with local user data...");
+        human_println!("The force flag was provided, overwriting user data in the vault with local user data...");
     } else {
-        println!("Syncing vault with local user data...");
-        crate::user_data::write_local_user_data(&net_user_data)?;
+        human_println!("Syncing vault with local user data...");
+        local_user_data.merge(net_user_data);
+        crate::user_data::write_local_user_data(&local_user_data)?;
     }
 
-    println!("Pushing local user data to network vault...");
-    let local_user_data = crate::user_data::get_local_user_data()?;
-    let file_archives_len = local_user_data.file_archives.len();
-    let private_file_archives_len = local_user_data.private_file_archives.len();
+    human_println!("Pushing local user data to network vault...");
     client
-        .put_user_data_to_vault(&vault_sk, wallet.into(), local_user_data)
+        .put_user_data_to_vault(&vault_sk, wallet.into(), local_user_data.clone())
         .await?;
 
-    println!("✅ Successfully synced vault");
-    println!("Vault contains:");
-    println!("{file_archives_len} public file archive(s)");
-    println!("{private_file_archives_len} private file archive(s)");
-    Ok(())
+    human_println!("✅ Successfully synced vault");
+    human_println!("Vault contains:");
+    print_user_data_counts(&local_user_data);
+    json_output(&user_data_counts(&local_user_data))
 }
 
 pub async fn load(peers: Vec<Multiaddr>) -> Result<()> {
     let client = crate::actions::connect_to_network(peers).await?;
     let vault_sk = crate::keys::get_vault_secret_key()?;
 
-    println!("Retrieving vault from network...");
+    human_println!("Retrieving vault from network...");
     let user_data = client.get_user_data_from_vault(&vault_sk).await?;
-    println!("Writing user data to disk...");
+    human_println!("Writing user data to disk...");
     crate::user_data::write_local_user_data(&user_data)?;
 
-    println!("✅ Successfully loaded vault with:");
-    println!("{} public file archive(s)", user_data.file_archives.len());
-    println!(
-        "{} private file archive(s)",
-        user_data.private_file_archives.len()
-    );
-    Ok(())
+    human_println!("✅ Successfully loaded vault with:");
+    print_user_data_counts(&user_data);
+    json_output(&user_data_counts(&user_data))
 }
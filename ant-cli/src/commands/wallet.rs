@@ -6,13 +6,17 @@
 // KIND, either express or implied. Please review the Licences for the specific language governing
 // permissions and limitations relating to use of the SAFE Network Software.
 
+use crate::output::{is_json, json_output, print_json};
 use crate::wallet::fs::{select_wallet, select_wallet_private_key, store_private_key};
 use crate::wallet::input::request_password;
 use crate::wallet::DUMMY_NETWORK;
+use autonomi::client::receipts::ReceiptStore;
 use autonomi::Wallet;
-use color_eyre::eyre::eyre;
+use color_eyre::eyre::{eyre, Context};
 use color_eyre::Result;
 use prettytable::{Cell, Row, Table};
+use serde_json::json;
+use std::time::UNIX_EPOCH;
 
 const WALLET_PASSWORD_REQUIRED: bool = false;
 
@@ -29,11 +33,15 @@ pub fn create(no_password: bool, password: Option<String>) -> Result<()> {
     // Save the private key file
     let file_path = store_private_key(&wallet_private_key, maybe_encryption_password)?;
 
-    println!("Wallet address: {wallet_address}");
-    println!("Wallet private key: {wallet_private_key}");
-    println!("Stored wallet in: {file_path:?}");
+    human_println!("Wallet address: {wallet_address}");
+    human_println!("Wallet private key: {wallet_private_key}");
+    human_println!("Stored wallet in: {file_path:?}");
 
-    Ok(())
+    json_output(&json!({
+        "address": wallet_address,
+        "private_key": wallet_private_key,
+        "path": file_path,
+    }))
 }
 
 pub fn import(
@@ -60,10 +68,10 @@ pub fn import(
     // Save the private key file
     let file_path = store_private_key(&wallet_private_key, maybe_encryption_password)?;
 
-    println!("Wallet address: {wallet_address}");
-    println!("Stored wallet in: {file_path:?}");
+    human_println!("Wallet address: {wallet_address}");
+    human_println!("Stored wallet in: {file_path:?}");
 
-    Ok(())
+    json_output(&json!({ "address": wallet_address, "path": file_path }))
 }
 
 pub fn export() -> Result<()> {
@@ -74,10 +82,10 @@ pub fn export() -> Result<()> {
         .address()
         .to_string();
 
-    println!("Wallet address: {wallet_address}");
-    println!("Wallet private key: {wallet_private_key}");
+    human_println!("Wallet address: {wallet_address}");
+    human_println!("Wallet private key: {wallet_private_key}");
 
-    Ok(())
+    json_output(&json!({ "address": wallet_address, "private_key": wallet_private_key }))
 }
 
 pub async fn balance() -> Result<()> {
@@ -86,7 +94,15 @@ pub async fn balance() -> Result<()> {
     let token_balance = wallet.balance_of_tokens().await?;
     let gas_balance = wallet.balance_of_gas_tokens().await?;
 
-    println!("Wallet balances: {}", wallet.address());
+    if is_json() {
+        return print_json(&json!({
+            "address": wallet.address().to_string(),
+            "token_balance": token_balance.to_string(),
+            "gas_balance": gas_balance.to_string(),
+        }));
+    }
+
+    human_println!("Wallet balances: {}", wallet.address());
 
     let mut table = Table::new();
 
@@ -105,6 +121,131 @@ pub async fn balance() -> Result<()> {
     Ok(())
 }
 
+pub fn receipts_list() -> Result<()> {
+    let store = receipt_store()?;
+    // Expired receipts can no longer be used, there is no point listing them
+    let _ = store.prune_expired()?;
+    let receipts = store.list()?;
+
+    if is_json() {
+        let receipts: Vec<_> = receipts
+            .iter()
+            .map(|receipt| {
+                json!({
+                    "id": receipt.id,
+                    "paid_at": unix_secs(receipt.paid_at),
+                    "chunks": receipt.receipt.len(),
+                    "total_paid": receipt.total_paid().to_string(),
+                })
+            })
+            .collect();
+        return print_json(&json!({ "receipts": receipts }));
+    }
+
+    if receipts.is_empty() {
+        human_println!("No payment receipts found in {:?}", store.dir());
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.add_row(Row::new(vec![
+        Cell::new("Id"),
+        Cell::new("Chunks"),
+        Cell::new("Total Paid"),
+    ]));
+    for receipt in &receipts {
+        table.add_row(Row::new(vec![
+            Cell::new(&receipt.id),
+            Cell::new(&receipt.receipt.len().to_string()),
+            Cell::new(&receipt.total_paid().to_string()),
+        ]));
+    }
+    table.printstd();
+
+    Ok(())
+}
+
+pub fn receipts_show(id: &str) -> Result<()> {
+    let receipt = receipt_store()?.load(id)?;
+
+    let payments: Vec<_> = receipt
+        .receipt
+        .iter()
+        .map(|(xor_name, (proof, price))| {
+            (
+                hex::encode(xor_name),
+                price.to_string(),
+                proof.peer_quotes.len(),
+                proof.has_expired(),
+            )
+        })
+        .collect();
+
+    if is_json() {
+        let payments: Vec<_> = payments
+            .iter()
+            .map(|(chunk, price, payees, expired)| {
+                json!({ "chunk": chunk, "price": price, "payees": payees, "expired": expired })
+            })
+            .collect();
+        return print_json(&json!({
+            "id": receipt.id,
+            "paid_at": unix_secs(receipt.paid_at),
+            "total_paid": receipt.total_paid().to_string(),
+            "payments": payments,
+        }));
+    }
+
+    human_println!(
+        "Receipt {} paid at {} (unix time): {} for {} chunks",
+        receipt.id,
+        unix_secs(receipt.paid_at),
+        receipt.total_paid(),
+        payments.len()
+    );
+
+    let mut table = Table::new();
+    table.add_row(Row::new(vec![
+        Cell::new("Chunk"),
+        Cell::new("Price"),
+        Cell::new("Payees"),
+        Cell::new("Expired"),
+    ]));
+    for (chunk, price, payees, expired) in &payments {
+        table.add_row(Row::new(vec![
+            Cell::new(chunk),
+            Cell::new(price),
+            Cell::new(&payees.to_string()),
+            Cell::new(&expired.to_string()),
+        ]));
+    }
+    table.printstd();
+
+    Ok(())
+}
+
+pub fn receipts_export(id: &str, output: &str) -> Result<()> {
+    let receipt = receipt_store()?.load(id)?;
+
+    let json = serde_json::to_string_pretty(&receipt)?;
+    std::fs::write(output, json)
+        .wrap_err_with(|| format!("Failed to write the receipt to {output}"))?;
+
+    human_println!("Receipt {id} exported to {output}");
+
+    json_output(&json!({ "id": id, "path": output }))
+}
+
+fn receipt_store() -> Result<ReceiptStore> {
+    Ok(ReceiptStore::new(crate::data_dir::get_receipts_dir_path()?))
+}
+
+fn unix_secs(time: std::time::SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
 fn maybe_request_password(no_password: bool, password: Option<String>) -> Result<Option<String>> {
     if no_password && password.is_some() {
         return Err(eyre!(
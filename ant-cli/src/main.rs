@@ -9,6 +9,9 @@
 #[macro_use]
 extern crate tracing;
 
+#[macro_use]
+mod output;
+
 mod access;
 mod actions;
 mod commands;
@@ -35,6 +38,8 @@ use tracing::Level;
 async fn main() -> Result<()> {
     color_eyre::install().expect("Failed to initialise error handler");
     let opt = Opt::parse();
+    output::set_json_output(opt.json);
+    actions::set_chunk_cache_size(opt.chunk_cache_size.saturating_mul(1024 * 1024));
     if let Some(network_id) = opt.network_id {
         ant_protocol::version::set_network_id(network_id);
     }
@@ -80,7 +85,15 @@ async fn main() -> Result<()> {
     let version = ant_build_info::git_info();
     info!("autonomi client built with git version: {version}");
 
-    commands::handle_subcommand(opt).await?;
+    let json = opt.json;
+    if let Err(err) = commands::handle_subcommand(opt).await {
+        if json {
+            error!("Command failed: {err:?}");
+            output::print_json_error(&err);
+            std::process::exit(1);
+        }
+        return Err(err);
+    }
 
     Ok(())
 }
@@ -101,6 +114,8 @@ fn init_logging_and_metrics(opt: &Opt) -> Result<(ReloadHandle, Option<WorkerGua
     let mut log_builder = LogBuilder::new(logging_targets);
     log_builder.output_dest(opt.log_output_dest.clone());
     log_builder.format(opt.log_format.unwrap_or(LogFormat::Default));
+    // keep stdout for the JSON document
+    log_builder.print_updates_to_stdout(!opt.json);
     let guards = log_builder.initialize()?;
     Ok(guards)
 }
@@ -27,10 +27,22 @@ pub(crate) struct Opt {
     #[clap(long = "timeout", global = true, value_parser = |t: &str| -> Result<Duration> { Ok(t.parse().map(Duration::from_secs)?) })]
     pub connection_timeout: Option<Duration>,
 
+    /// Maximum size in MiB of the on-disk cache of downloaded chunks.
+    ///
+    /// Set it to 0 to disable the cache.
+    #[clap(long, global = true, default_value_t = 1024, verbatim_doc_comment)]
+    pub chunk_cache_size: u64,
+
     /// Print the crate version.
     #[clap(long)]
     pub crate_version: bool,
 
+    /// Print the result of the command as a single JSON document instead of human readable text.
+    ///
+    /// Progress bars are disabled and errors are printed as JSON with a stable error code.
+    #[clap(long, global = true, verbatim_doc_comment)]
+    pub json: bool,
+
     /// Specify the logging format.
     ///
     /// Valid values are "default" or "json".
@@ -0,0 +1,197 @@
+// Copyright 2024 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Output of the commands, either human readable text or a single JSON document when `--json` is used.
+
+use autonomi::client::data::{CostError, GetError, PutError};
+use autonomi::client::graph::GraphError;
+use autonomi::client::pointer::PointerError;
+use autonomi::client::registers::RegisterError;
+use autonomi::client::vault::VaultError;
+use autonomi::client::ConnectError;
+use color_eyre::eyre::{Context, Report, Result};
+use serde::Serialize;
+use std::sync::OnceLock;
+
+static JSON_OUTPUT: OnceLock<bool> = OnceLock::new();
+
+/// Switch the output of the commands to JSON. Must be called before any output is printed.
+pub fn set_json_output(json: bool) {
+    let _ = JSON_OUTPUT.set(json);
+}
+
+/// Whether the commands output JSON instead of human readable text.
+pub fn is_json() -> bool {
+    JSON_OUTPUT.get().copied().unwrap_or(false)
+}
+
+/// Prints a line of human readable output on stdout.
+/// Nothing is printed in JSON mode, where the command prints a single JSON document instead.
+macro_rules! human_println {
+    ($($arg:tt)*) => {
+        if !$crate::output::is_json() {
+            println!($($arg)*);
+        }
+    };
+}
+
+/// Prints a line of an interactive prompt, on stderr in JSON mode to keep stdout parseable.
+macro_rules! prompt_println {
+    ($($arg:tt)*) => {
+        if $crate::output::is_json() {
+            eprintln!($($arg)*);
+        } else {
+            println!($($arg)*);
+        }
+    };
+}
+
+/// Prints the value as pretty JSON on stdout.
+pub fn print_json<T: Serialize>(value: &T) -> Result<()> {
+    let json =
+        serde_json::to_string_pretty(value).wrap_err("Failed to serialize output to JSON")?;
+    println!("{json}");
+    Ok(())
+}
+
+/// Prints the result of the command in JSON mode, does nothing otherwise.
+pub fn json_output<T: Serialize>(value: &T) -> Result<()> {
+    if is_json() {
+        print_json(value)?;
+    }
+    Ok(())
+}
+
+/// Stable code identifying the kind of error in the JSON output.
+/// The codes are part of the output format, existing ones must not be renamed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// Could not connect to the network.
+    Connection,
+    /// A network operation failed.
+    Network,
+    /// Getting quotes or paying for the data failed.
+    Payment,
+    /// Uploading data failed.
+    Upload,
+    /// Downloading data failed.
+    Download,
+    /// A register operation failed.
+    Register,
+    /// A vault operation failed.
+    Vault,
+    /// A pointer operation failed.
+    Pointer,
+    /// A graph entry operation failed.
+    Graph,
+    /// Reading or writing local files failed.
+    Io,
+    /// Any other error.
+    Unknown,
+}
+
+impl ErrorCode {
+    /// The code of the first error of the chain that has a known type.
+    pub fn of(err: &Report) -> Self {
+        for cause in err.chain() {
+            if cause.downcast_ref::<ConnectError>().is_some()
+                || cause.downcast_ref::<ant_bootstrap::Error>().is_some()
+            {
+                return Self::Connection;
+            }
+            if let Some(err) = cause.downcast_ref::<PutError>() {
+                return match err {
+                    PutError::Network(_) => Self::Network,
+                    PutError::CostError(_) | PutError::PayError(_) | PutError::Wallet(_) => {
+                        Self::Payment
+                    }
+                    _ => Self::Upload,
+                };
+            }
+            if let Some(err) = cause.downcast_ref::<GetError>() {
+                return match err {
+                    GetError::Network(_) => Self::Network,
+                    _ => Self::Download,
+                };
+            }
+            if cause.downcast_ref::<CostError>().is_some() {
+                return Self::Payment;
+            }
+            if cause.downcast_ref::<RegisterError>().is_some() {
+                return Self::Register;
+            }
+            if cause.downcast_ref::<VaultError>().is_some() {
+                return Self::Vault;
+            }
+            if cause.downcast_ref::<PointerError>().is_some() {
+                return Self::Pointer;
+            }
+            if cause.downcast_ref::<GraphError>().is_some() {
+                return Self::Graph;
+            }
+            if cause.downcast_ref::<std::io::Error>().is_some() {
+                return Self::Io;
+            }
+        }
+        Self::Unknown
+    }
+}
+
+#[derive(Serialize)]
+struct ErrorOutput {
+    error: ErrorDetails,
+}
+
+#[derive(Serialize)]
+struct ErrorDetails {
+    code: ErrorCode,
+    message: String,
+    causes: Vec<String>,
+}
+
+/// Prints the error as a JSON document on stdout.
+pub fn print_json_error(err: &Report) {
+    let output = ErrorOutput {
+        error: ErrorDetails {
+            code: ErrorCode::of(err),
+            message: err.to_string(),
+            causes: err.chain().skip(1).map(|cause| cause.to_string()).collect(),
+        },
+    };
+    if let Err(err) = print_json(&output) {
+        eprintln!("{err:?}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use color_eyre::eyre::eyre;
+
+    #[test]
+    fn error_code_is_taken_from_the_error_chain() {
+        let err = Err::<(), _>(ConnectError::TimedOut)
+            .wrap_err("Failed to connect to the network")
+            .unwrap_err();
+        assert_eq!(ErrorCode::of(&err), ErrorCode::Connection);
+
+        let err = Err::<(), _>(std::io::Error::other("disk full"))
+            .wrap_err("Failed to write file")
+            .unwrap_err();
+        assert_eq!(ErrorCode::of(&err), ErrorCode::Io);
+
+        assert_eq!(ErrorCode::of(&eyre!("something else")), ErrorCode::Unknown);
+        assert_eq!(
+            serde_json::to_string(&ErrorCode::Connection)
+                .ok()
+                .as_deref(),
+            Some("\"connection\"")
+        );
+    }
+}
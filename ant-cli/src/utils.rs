@@ -6,9 +6,11 @@
 // KIND, either express or implied. Please review the Licences for the specific language governing
 // permissions and limitations relating to use of the SAFE Network Software.
 
-use autonomi::client::{Amount, ClientEvent, UploadSummary};
+use crate::actions::get_upload_progress_bar;
+use autonomi::client::{ClientEvent, UploadSummary};
+use indicatif::ProgressBar;
 
-/// Collects upload summary from the event receiver.
+/// Collects upload summary from the event receiver, showing the progress of the chunks being uploaded.
 /// Send a signal to the returned sender to stop collecting and to return the result via the join handle.
 pub fn collect_upload_summary(
     mut event_receiver: tokio::sync::mpsc::Receiver<ClientEvent>,
@@ -18,17 +20,13 @@ pub fn collect_upload_summary(
 ) {
     let (upload_completed_tx, mut upload_completed_rx) = tokio::sync::oneshot::channel::<()>();
     let stats_thread = tokio::spawn(async move {
-        let mut tokens_spent: Amount = Amount::from(0);
-        let mut record_count = 0;
+        let mut progress = UploadProgress::default();
 
         loop {
             tokio::select! {
                 event = event_receiver.recv() => {
                     match event {
-                        Some(ClientEvent::UploadComplete(upload_summary)) => {
-                            tokens_spent += upload_summary.tokens_spent;
-                            record_count += upload_summary.record_count;
-                        }
+                        Some(event) => progress.handle(event),
                         None => break,
                     }
                 }
@@ -38,19 +36,66 @@ pub fn collect_upload_summary(
 
         // try to drain the event receiver in case there are any more events
         while let Ok(event) = event_receiver.try_recv() {
-            match event {
-                ClientEvent::UploadComplete(upload_summary) => {
-                    tokens_spent += upload_summary.tokens_spent;
-                    record_count += upload_summary.record_count;
-                }
-            }
+            progress.handle(event);
         }
 
-        UploadSummary {
-            tokens_spent,
-            record_count,
-        }
+        progress.finish()
     });
 
     (stats_thread, upload_completed_tx)
 }
+
+/// Progress of the chunks of the uploads, along with the summary of the completed uploads.
+#[derive(Default)]
+struct UploadProgress {
+    // Only shown once chunks are being paid for, not while the cost is estimated and confirmed.
+    progress_bar: Option<ProgressBar>,
+    quoted: u64,
+    paid: u64,
+    verified: u64,
+    summary: UploadSummary,
+}
+
+impl UploadProgress {
+    fn handle(&mut self, event: ClientEvent) {
+        match event {
+            ClientEvent::ChunkQuoted { .. } => self.quoted += 1,
+            ClientEvent::ChunkPaid { .. } => self.paid += 1,
+            ClientEvent::ChunkVerified { .. } => self.verified += 1,
+            ClientEvent::UploadComplete(summary) => self.summary.merge(summary),
+        }
+        if self.paid == 0 && self.verified == 0 {
+            return;
+        }
+
+        let progress_bar = self.progress_bar.get_or_insert_with(|| {
+            get_upload_progress_bar().unwrap_or_else(|_| ProgressBar::hidden())
+        });
+        // chunks paid for beforehand are stored without having been paid in this run
+        progress_bar.set_length(self.paid.max(self.verified));
+        progress_bar.set_position(self.verified);
+        progress_bar.set_message(format!(
+            "chunks quoted: {}, paid: {}",
+            self.quoted, self.paid
+        ));
+    }
+
+    fn finish(self) -> UploadSummary {
+        if let Some(progress_bar) = self.progress_bar {
+            progress_bar.finish_and_clear();
+        }
+        self.summary
+    }
+}
+
+/// Print the details of an upload beyond its cost.
+pub fn print_upload_details(summary: &UploadSummary) {
+    human_println!("Gas spent: {} wei", summary.gas_spent);
+    human_println!("Payment transactions: {}", summary.tx_hashes.len());
+    for tx_hash in &summary.tx_hashes {
+        human_println!("  {tx_hash}");
+    }
+    human_println!("Bytes uploaded: {}", summary.bytes_uploaded);
+    human_println!("Chunk upload retries: {}", summary.retries);
+    human_println!("Upload time: {:.2?}", summary.elapsed);
+}
@@ -169,7 +169,7 @@ fn get_wallet_selection(wallet_files: Vec<String>) -> Result<String, Error> {
 }
 
 fn list_wallets(wallet_files: &[String]) {
-    println!("Wallets:");
+    prompt_println!("Wallets:");
 
     let mut table = Table::new();
 
@@ -189,7 +189,11 @@ fn list_wallets(wallet_files: &[String]) {
         ]));
     }
 
-    table.printstd();
+    if crate::output::is_json() {
+        let _ = table.print(&mut std::io::stderr());
+    } else {
+        table.printstd();
+    }
 }
 
 fn get_wallet_files(wallets_folder: &PathBuf) -> Result<Vec<String>, Error> {
@@ -17,13 +17,15 @@ use crate::{
     log_markers::Marker,
     multiaddr_pop_p2p,
     network_discovery::NetworkDiscovery,
-    record_store::{ClientRecordStore, NodeRecordStore, NodeRecordStoreConfig},
+    record_store::{encryption_seed, ClientRecordStore, NodeRecordStore, NodeRecordStoreConfig},
     record_store_api::UnifiedRecordStore,
+    record_store_backend::StorageBackend,
     relay_manager::RelayManager,
     replication_fetcher::ReplicationFetcher,
     time::Interval,
     time::{interval, spawn, Instant},
-    transport, GetRecordError, Network, NodeIssue, CLOSE_GROUP_SIZE,
+    transport::{self, TransportProtocol},
+    GetRecordError, Network, NodeIssue, CLOSE_GROUP_SIZE,
 };
 #[cfg(feature = "open-metrics")]
 use crate::{
@@ -50,7 +52,6 @@ use libp2p::{core::muxing::StreamMuxerBox, relay};
 use libp2p::{
     identity::Keypair,
     kad::{self, QueryId, Quorum, Record, RecordKey, K_VALUE},
-    multiaddr::Protocol,
     request_response::{self, Config as RequestResponseConfig, OutboundRequestId, ProtocolSupport},
     swarm::{
         dial_opts::{DialOpts, PeerCondition},
@@ -63,8 +64,7 @@ use libp2p::{swarm::SwarmEvent, Transport as _};
 use prometheus_client::metrics::info::Info;
 use rand::Rng;
 use std::{
-    collections::{btree_map::Entry, BTreeMap, HashMap, HashSet},
-    convert::TryInto,
+    collections::{btree_map::Entry, BTreeMap, BTreeSet, HashMap, HashSet},
     fmt::Debug,
     fs,
     io::{Read, Write},
@@ -272,13 +272,17 @@ pub struct NetworkBuilder {
     keypair: Keypair,
     listen_addr: Option<SocketAddr>,
     local: bool,
+    max_storage_bytes: Option<u64>,
     #[cfg(feature = "open-metrics")]
     metrics_registries: Option<MetricsRegistries>,
     #[cfg(feature = "open-metrics")]
     metrics_server_port: Option<u16>,
     request_timeout: Option<Duration>,
+    storage_backend: StorageBackend,
+    transports: BTreeSet<TransportProtocol>,
     #[cfg(feature = "upnp")]
     upnp: bool,
+    websocket_port: Option<u16>,
 }
 
 impl NetworkBuilder {
@@ -290,13 +294,17 @@ impl NetworkBuilder {
             keypair,
             listen_addr: None,
             local,
+            max_storage_bytes: None,
             #[cfg(feature = "open-metrics")]
             metrics_registries: None,
             #[cfg(feature = "open-metrics")]
             metrics_server_port: None,
             request_timeout: None,
+            storage_backend: StorageBackend::default(),
+            transports: transport::default_transports(),
             #[cfg(feature = "upnp")]
             upnp: false,
+            websocket_port: None,
         }
     }
 
@@ -338,6 +346,35 @@ impl NetworkBuilder {
         self.upnp = upnp;
     }
 
+    /// Set the disk budget of the records stored by a node, in bytes.
+    /// The number of records the node accepts and its quotes follow the remaining capacity.
+    pub fn max_storage(&mut self, max_storage_bytes: u64) {
+        self.max_storage_bytes = Some(max_storage_bytes);
+    }
+
+    /// Set the backend persisting the records of a node.
+    pub fn storage_backend(&mut self, storage_backend: StorageBackend) {
+        self.storage_backend = storage_backend;
+    }
+
+    /// Set the transports used to dial peers. A node listens on each of them.
+    /// Only QUIC is used by default, an empty set is ignored.
+    pub fn transports(&mut self, transports: impl IntoIterator<Item = TransportProtocol>) {
+        let transports: BTreeSet<_> = transports.into_iter().collect();
+        if transports.is_empty() {
+            warn!("No transport provided, keeping {:?}", self.transports);
+            return;
+        }
+        self.transports = transports;
+    }
+
+    /// The TCP port the node listens on for WebSocket connections.
+    /// By default the port of the listen address is used, unless plain TCP is also enabled, in which case a
+    /// random port is picked.
+    pub fn websocket_port(&mut self, port: u16) {
+        self.websocket_port = Some(port);
+    }
+
     /// Creates a new `SwarmDriver` instance, along with a `Network` handle
     /// for sending commands and an `mpsc::Receiver<NetworkEvent>` for receiving
     /// network events. It initializes the swarm, sets up the transport, and
@@ -407,22 +444,24 @@ impl NetworkBuilder {
                 });
             }
             let peer_id = PeerId::from(self.keypair.public());
-            let encryption_seed: [u8; 16] = peer_id
-                .to_bytes()
-                .get(..16)
-                .expect("Cann't get encryption_seed from keypair")
-                .try_into()
-                .expect("Cann't get 16 bytes from serialised key_pair");
-            NodeRecordStoreConfig {
+            let encryption_seed = encryption_seed(&peer_id);
+            let store_cfg = NodeRecordStoreConfig {
                 max_value_bytes: MAX_PACKET_SIZE, // TODO, does this need to be _less_ than MAX_PACKET_SIZE
                 storage_dir: storage_dir_path,
                 historic_quote_dir: root_dir.clone(),
                 encryption_seed,
+                backend: self.storage_backend,
                 ..Default::default()
+            };
+            match self.max_storage_bytes {
+                Some(max_storage_bytes) => store_cfg.with_max_storage_bytes(max_storage_bytes),
+                None => store_cfg,
             }
         };
 
         let listen_addr = self.listen_addr;
+        let transports = self.transports.clone();
+        let websocket_port = self.websocket_port;
         #[cfg(feature = "upnp")]
         let upnp = self.upnp;
 
@@ -438,13 +477,23 @@ impl NetworkBuilder {
         // Listen on the provided address
         let listen_socket_addr = listen_addr.ok_or(NetworkError::ListenAddressNotProvided)?;
 
-        // Listen on QUIC
-        let addr_quic = Multiaddr::from(listen_socket_addr.ip())
-            .with(Protocol::Udp(listen_socket_addr.port()))
-            .with(Protocol::QuicV1);
-        swarm_driver
-            .listen_on(addr_quic)
-            .expect("Multiaddr should be supported by our configured transports");
+        for protocol in &transports {
+            let listen_socket_addr = match protocol {
+                TransportProtocol::WebSocket => {
+                    let port =
+                        websocket_port.unwrap_or(if transports.contains(&TransportProtocol::Tcp) {
+                            0
+                        } else {
+                            listen_socket_addr.port()
+                        });
+                    SocketAddr::new(listen_socket_addr.ip(), port)
+                }
+                TransportProtocol::Quic | TransportProtocol::Tcp => listen_socket_addr,
+            };
+            swarm_driver
+                .listen_on(protocol.listen_addr(listen_socket_addr))
+                .expect("Multiaddr should be supported by our configured transports");
+        }
 
         Ok((network, events_receiver, swarm_driver))
     }
@@ -507,9 +556,10 @@ impl NetworkBuilder {
 
         // ==== Transport ====
         #[cfg(feature = "open-metrics")]
-        let main_transport = transport::build_transport(&self.keypair, &mut metrics_registries);
+        let main_transport =
+            transport::build_transport(&self.keypair, &self.transports, &mut metrics_registries);
         #[cfg(not(feature = "open-metrics"))]
-        let main_transport = transport::build_transport(&self.keypair);
+        let main_transport = transport::build_transport(&self.keypair, &self.transports);
         let transport = if !self.local {
             debug!("Preventing non-global dials");
             // Wrap upper in a transport that prevents dialing local addresses.
@@ -598,7 +648,7 @@ impl NetworkBuilder {
                         store_cfg,
                         network_event_sender.clone(),
                         local_swarm_cmd_sender.clone(),
-                    );
+                    )?;
                     #[cfg(feature = "open-metrics")]
                     let mut node_record_store = node_record_store;
                     #[cfg(feature = "open-metrics")]
@@ -148,6 +148,9 @@ pub enum NetworkError {
     #[error("Node Listen Address was not provided during construction")]
     ListenAddressNotProvided,
 
+    #[error("Failed to open the record store: {0}")]
+    RecordStoreBackend(#[from] crate::record_store_backend::StorageBackendError),
+
     #[cfg(feature = "open-metrics")]
     #[error("Network Metric error")]
     NetworkMetricError,
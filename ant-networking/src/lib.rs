@@ -24,6 +24,7 @@ mod metrics;
 mod network_discovery;
 mod record_store;
 mod record_store_api;
+mod record_store_backend;
 mod relay_manager;
 mod replication_fetcher;
 pub mod time;
@@ -41,7 +42,9 @@ pub use self::{
     error::{GetRecordError, NetworkError},
     event::{MsgResponder, NetworkEvent},
     graph::get_graph_entry_from_record,
-    record_store::NodeRecordStore,
+    record_store::{record_type_of, NodeRecordStore, OfflineRecordStore},
+    record_store_backend::{StorageBackend, StorageBackendError},
+    transport::TransportProtocol,
 };
 #[cfg(feature = "open-metrics")]
 pub use metrics::service::MetricsRegistries;
@@ -1274,7 +1277,7 @@ pub(crate) fn multiaddr_get_ip(addr: &Multiaddr) -> Option<IpAddr> {
 
 pub(crate) fn multiaddr_get_port(addr: &Multiaddr) -> Option<u16> {
     addr.iter().find_map(|p| match p {
-        Protocol::Udp(port) => Some(port),
+        Protocol::Udp(port) | Protocol::Tcp(port) => Some(port),
         _ => None,
     })
 }
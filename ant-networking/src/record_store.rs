@@ -9,6 +9,9 @@
 
 use crate::cmd::LocalSwarmCmd;
 use crate::driver::MAX_PACKET_SIZE;
+use crate::record_store_backend::{
+    key_to_hex, FlatFileStorage, IndexEntry, RecordStorage, StorageBackend, StorageBackendError,
+};
 use crate::send_local_swarm_cmd;
 use crate::time::{spawn, Instant};
 use crate::{event::NetworkEvent, log_markers::Marker};
@@ -23,7 +26,6 @@ use ant_protocol::{
     NetworkAddress, PrettyPrintRecordKey,
 };
 use hkdf::Hkdf;
-use itertools::Itertools;
 use libp2p::{
     identity::PeerId,
     kad::{
@@ -33,7 +35,7 @@ use libp2p::{
 };
 #[cfg(feature = "open-metrics")]
 use prometheus_client::metrics::gauge::Gauge;
-use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use rayon::iter::{IntoParallelIterator, IntoParallelRefIterator, ParallelIterator};
 use serde::{Deserialize, Serialize};
 use sha2::Sha256;
 use std::{
@@ -41,11 +43,11 @@ use std::{
     collections::{BTreeMap, HashMap},
     fs,
     path::{Path, PathBuf},
+    sync::Arc,
     time::SystemTime,
     vec,
 };
 use tokio::sync::mpsc;
-use walkdir::{DirEntry, WalkDir};
 use xor_name::XorName;
 
 // A transaction record is at the size of 4KB roughly.
@@ -56,12 +58,31 @@ use xor_name::XorName;
 // this shall allow around 16K records.
 const MAX_RECORDS_COUNT: usize = 16 * 1024;
 
+/// The average size of a record, used to turn a storage budget into a number of records.
+const AVERAGE_RECORD_SIZE: u64 = 2 * 1024 * 1024;
+
+/// The default storage budget of a node, enough for `MAX_RECORDS_COUNT` records of average size.
+pub const DEFAULT_MAX_STORAGE_BYTES: u64 = MAX_RECORDS_COUNT as u64 * AVERAGE_RECORD_SIZE;
+
+/// Size of the authentication tag added to the records by the encryption.
+const ENCRYPTION_TAG_SIZE: u64 = 16;
+
 /// The maximum number of records to cache in memory.
 const MAX_RECORDS_CACHE_SIZE: usize = 25;
 
 /// File name of the recorded historical quoting metrics.
 const HISTORICAL_QUOTING_METRICS_FILENAME: &str = "historic_quoting_metrics";
 
+/// The seed of the key encrypting the records of a node, derived from its peer id.
+pub(crate) fn encryption_seed(peer_id: &PeerId) -> [u8; 16] {
+    peer_id
+        .to_bytes()
+        .get(..16)
+        .expect("Cann't get encryption_seed from keypair")
+        .try_into()
+        .expect("Cann't get 16 bytes from serialised key_pair")
+}
+
 fn derive_aes256gcm_siv_from_seed(seed: &[u8; 16]) -> (Aes256GcmSiv, [u8; 4]) {
     // shall be unique for purpose.
     let salt = b"autonomi_record_store";
@@ -83,6 +104,16 @@ fn derive_aes256gcm_siv_from_seed(seed: &[u8; 16]) -> (Aes256GcmSiv, [u8; 4]) {
     (Aes256GcmSiv::new(seeded_key), nonce_starter)
 }
 
+/// A fingerprint of the key derived from `seed`, kept along an index of records to tell whether they were
+/// encrypted with our key without decrypting them.
+fn key_fingerprint_from_seed(seed: &[u8; 16]) -> [u8; 32] {
+    let hk = Hkdf::<Sha256>::new(Some(b"autonomi_record_store"), seed);
+    let mut fingerprint = [0u8; 32];
+    hk.expand(b"key fingerprint", &mut fingerprint)
+        .expect("32 bytes is a valid length for HKDF output");
+    fingerprint
+}
+
 /// FIFO simple cache of records to reduce read times
 struct RecordCache {
     records_cache: HashMap<Key, (Record, SystemTime)>,
@@ -137,10 +168,16 @@ pub struct NodeRecordStore {
     local_address: NetworkAddress,
     /// The configuration of the store.
     config: NodeRecordStoreConfig,
+    /// Where the encrypted records are persisted.
+    storage: Arc<dyn RecordStorage>,
     /// Main records store remains unchanged for compatibility
     records: HashMap<Key, (NetworkAddress, RecordType)>,
     /// Additional index organizing records by distance
     records_by_distance: BTreeMap<U256, Key>,
+    /// The size on disk of the records, including the ones still being written.
+    record_sizes: HashMap<Key, u64>,
+    /// The total size of `record_sizes`, counted against `max_storage_bytes`.
+    stored_bytes: u64,
     /// FIFO simple cache of records to reduce read times
     records_cache: RecordCache,
     /// Send network events to the node layer.
@@ -173,14 +210,30 @@ pub struct NodeRecordStoreConfig {
     /// The directory where the historic quote to be stored
     /// (normally to be the parent dir of the storage_dir)
     pub historic_quote_dir: PathBuf,
-    /// The maximum number of records.
+    /// The maximum number of records, whatever their size.
     pub max_records: usize,
+    /// The disk budget of the records, in bytes.
+    pub max_storage_bytes: u64,
     /// The maximum size of record values, in bytes.
     pub max_value_bytes: usize,
     /// The maximum number of records to cache in memory.
     pub records_cache_size: usize,
     /// The seed to generate record_store encryption_details
     pub encryption_seed: [u8; 16],
+    /// The backend persisting the records in the storage_dir.
+    pub backend: StorageBackend,
+}
+
+impl NodeRecordStoreConfig {
+    /// Set the disk budget of the records, raising `max_records` so that the budget can be used by records of
+    /// average size.
+    pub fn with_max_storage_bytes(mut self, max_storage_bytes: u64) -> Self {
+        let records_of_average_size =
+            usize::try_from(max_storage_bytes / AVERAGE_RECORD_SIZE).unwrap_or(usize::MAX);
+        self.max_storage_bytes = max_storage_bytes;
+        self.max_records = MAX_RECORDS_COUNT.max(records_of_average_size);
+        self
+    }
 }
 
 impl Default for NodeRecordStoreConfig {
@@ -190,9 +243,26 @@ impl Default for NodeRecordStoreConfig {
             storage_dir: historic_quote_dir.clone(),
             historic_quote_dir,
             max_records: MAX_RECORDS_COUNT,
+            max_storage_bytes: DEFAULT_MAX_STORAGE_BYTES,
             max_value_bytes: MAX_PACKET_SIZE,
             records_cache_size: MAX_RECORDS_CACHE_SIZE,
             encryption_seed: [0u8; 16],
+            backend: StorageBackend::default(),
+        }
+    }
+}
+
+/// The type of a decrypted record, as tracked by the store. `None` if the record has no valid header.
+pub fn record_type_of(record: &Record) -> Option<RecordType> {
+    match RecordHeader::is_record_of_type_chunk(record) {
+        Ok(true) => Some(RecordType::Chunk),
+        Ok(false) => Some(RecordType::NonChunk(XorName::from_content(&record.value))),
+        Err(error) => {
+            warn!(
+                "Failed to parse record type of record {:?}: {error:?}",
+                PrettyPrintRecordKey::from(&record.key)
+            );
+            None
         }
     }
 }
@@ -214,100 +284,157 @@ struct HistoricQuotingMetrics {
 }
 
 impl NodeRecordStore {
-    /// If a directory for our node already exists, repopulate the records from the files in the dir
+    /// If the storage of our node already holds records, repopulate the index of records from it.
+    ///
+    /// Records that can't be decrypted were written with a different key, hence are cleaned up.
+    /// The index of a storage is trusted when it was written with our key, its records being checked
+    /// when they are read instead.
     fn update_records_from_an_existing_store(
-        config: &NodeRecordStoreConfig,
+        storage: &dyn RecordStorage,
         encryption_details: &(Aes256GcmSiv, [u8; 4]),
-    ) -> HashMap<Key, (NetworkAddress, RecordType)> {
-        let process_entry = |entry: &DirEntry| -> _ {
-            let path = entry.path();
-            if path.is_file() {
-                debug!("Existing record found: {path:?}");
-                // if we've got a file, lets try and read it
-                let filename = match path.file_name().and_then(|n| n.to_str()) {
-                    Some(file_name) => file_name,
-                    None => {
-                        // warn and remove this file as it's not a valid record
-                        warn!(
-                            "Found a file in the storage dir that is not a valid record: {:?}",
-                            path
-                        );
-                        if let Err(e) = fs::remove_file(path) {
-                            warn!(
-                                "Failed to remove invalid record file from storage dir: {:?}",
-                                e
-                            );
-                        }
-                        return None;
-                    }
-                };
-                // get the record key from the filename
-                let key = Self::get_data_from_filename(filename)?;
-                let record = match fs::read(path) {
-                    Ok(bytes) => {
-                        // and the stored record
-                        if let Some(record) =
-                            Self::get_record_from_bytes(bytes, &key, encryption_details)
-                        {
-                            record
-                        } else {
-                            // This will be due to node restart, result in different encrypt_detail.
-                            // Hence need to clean up the old copy.
-                            info!("Failed to decrypt record from file {filename:?}, clean it up.");
-                            if let Err(e) = fs::remove_file(path) {
-                                warn!(
-                                    "Failed to remove outdated record file {filename:?} from storage dir: {:?}",
-                                    e
-                                );
-                            }
-                            return None;
-                        }
-                    }
-                    Err(err) => {
-                        error!("Error while reading file. filename: {filename}, error: {err:?}");
-                        return None;
-                    }
-                };
-
-                let record_type = match RecordHeader::is_record_of_type_chunk(&record) {
-                    Ok(true) => RecordType::Chunk,
-                    Ok(false) => {
-                        let xorname_hash = XorName::from_content(&record.value);
-                        RecordType::NonChunk(xorname_hash)
-                    }
-                    Err(error) => {
-                        warn!(
-                            "Failed to parse record type of record {filename:?}: {:?}",
-                            error
-                        );
-                        // In correct decryption using different key could result in this.
-                        // In that case, a cleanup shall be carried out.
-                        if let Err(e) = fs::remove_file(path) {
-                            warn!(
-                                "Failed to remove invalid record file {filename:?} from storage dir: {:?}",
-                                e
-                            );
-                        }
-                        return None;
+        key_fingerprint: &[u8],
+    ) -> Vec<IndexEntry> {
+        info!("Attempting to repopulate records from existing store...");
+        match storage.index() {
+            Ok(Some(index)) => match storage.key_fingerprint() {
+                Ok(Some(fingerprint)) if fingerprint == key_fingerprint => {
+                    info!("{} existing records loaded from the index", index.len());
+                    index
+                }
+                _ => {
+                    let index = Self::check_index(storage, index, encryption_details);
+                    if let Err(err) = storage.set_key_fingerprint(key_fingerprint) {
+                        warn!("Failed to store the fingerprint of the record key: {err:?}");
                     }
-                };
+                    index
+                }
+            },
+            Ok(None) => match storage.keys() {
+                Ok(keys) => keys
+                    .par_iter()
+                    .filter_map(|key| {
+                        let (record_type, bytes) =
+                            Self::load_record(storage, key, encryption_details)?;
+                        Some(IndexEntry {
+                            key: key.clone(),
+                            record_type,
+                            size: bytes.len() as u64,
+                        })
+                    })
+                    .collect(),
+                Err(err) => {
+                    error!("Failed to list the records of the existing store: {err:?}");
+                    vec![]
+                }
+            },
+            Err(err) => {
+                error!("Failed to read the index of the existing store: {err:?}");
+                vec![]
+            }
+        }
+    }
 
-                let address = NetworkAddress::from_record_key(&key);
-                info!("Existing record loaded: {path:?}");
-                return Some((key, (address, record_type)));
+    /// Read and decrypt a stored record to find out its type.
+    /// The record is removed from the storage if it is not valid.
+    ///
+    /// Returns the type and the encrypted bytes of the record.
+    fn load_record(
+        storage: &dyn RecordStorage,
+        key: &Key,
+        encryption_details: &(Aes256GcmSiv, [u8; 4]),
+    ) -> Option<(RecordType, Vec<u8>)> {
+        let filename = key_to_hex(key);
+        let bytes = match storage.read(key) {
+            Ok(Some(bytes)) => bytes,
+            Ok(None) => return None,
+            Err(err) => {
+                error!("Error while reading file. filename: {filename}, error: {err:?}");
+                return None;
+            }
+        };
+        let remove_record = || {
+            if let Err(e) = storage.remove(key) {
+                warn!("Failed to remove invalid record {filename:?} from storage: {e:?}");
             }
-            None
         };
 
-        info!("Attempting to repopulate records from existing store...");
-        let records = WalkDir::new(&config.storage_dir)
-            .into_iter()
-            .filter_map(|e| e.ok())
-            .collect_vec()
-            .par_iter()
-            .filter_map(process_entry)
+        let Some(record) = Self::get_record_from_bytes(bytes.clone(), key, encryption_details)
+        else {
+            // This will be due to node restart, result in different encrypt_detail.
+            // Hence need to clean up the old copy.
+            info!("Failed to decrypt record {filename:?}, clean it up.");
+            remove_record();
+            return None;
+        };
+
+        let Some(record_type) = record_type_of(&record) else {
+            // In correct decryption using different key could result in this.
+            // In that case, a cleanup shall be carried out.
+            remove_record();
+            return None;
+        };
+
+        info!("Existing record loaded: {filename:?}");
+        Some((record_type, bytes))
+    }
+
+    /// Check that the records of an index, written with another key or before the key was recorded, can be
+    /// decrypted with our key.
+    /// The records that can't, written by a different node or corrupted, are cleaned up and left out of the index.
+    fn check_index(
+        storage: &dyn RecordStorage,
+        index: Vec<IndexEntry>,
+        encryption_details: &(Aes256GcmSiv, [u8; 4]),
+    ) -> Vec<IndexEntry> {
+        let indexed = index.len();
+        let index: Vec<IndexEntry> = index
+            .into_par_iter()
+            .filter(|entry| Self::load_record(storage, &entry.key, encryption_details).is_some())
             .collect();
-        records
+        if index.len() < indexed {
+            info!(
+                "Cleaned up {} records of the index that failed to decrypt",
+                indexed - index.len()
+            );
+        }
+        info!("{} existing records loaded from the index", index.len());
+        index
+    }
+
+    /// Move the records left in flat files into `storage`, in case the node switched to another backend.
+    fn import_flat_files(
+        storage: &dyn RecordStorage,
+        storage_dir: &Path,
+        encryption_details: &(Aes256GcmSiv, [u8; 4]),
+    ) {
+        let flat_files = FlatFileStorage::new(storage_dir);
+        let keys = match flat_files.keys() {
+            Ok(keys) => keys,
+            Err(err) => {
+                warn!("Failed to list the flat files to import: {err:?}");
+                return;
+            }
+        };
+        if keys.is_empty() {
+            return;
+        }
+
+        info!("Importing {} records from flat files", keys.len());
+        for key in keys {
+            let Some((record_type, bytes)) =
+                Self::load_record(&flat_files, &key, encryption_details)
+            else {
+                continue;
+            };
+            match storage.write(&key, &record_type, &bytes) {
+                Ok(()) => {
+                    if let Err(err) = flat_files.remove(&key) {
+                        warn!("Failed to remove imported record file {key:?}: {err:?}");
+                    }
+                }
+                Err(err) => error!("Failed to import record {key:?}: {err:?}"),
+            }
+        }
     }
 
     /// If quote_metrics file already exists, using the existing parameters.
@@ -348,7 +475,7 @@ impl NodeRecordStore {
         config: NodeRecordStoreConfig,
         network_event_sender: mpsc::Sender<NetworkEvent>,
         swarm_cmd_sender: mpsc::Sender<LocalSwarmCmd>,
-    ) -> Self {
+    ) -> std::result::Result<Self, StorageBackendError> {
         info!("Using encryption_seed of {:?}", config.encryption_seed);
         let encryption_details = derive_aes256gcm_siv_from_seed(&config.encryption_seed);
 
@@ -365,22 +492,44 @@ impl NodeRecordStore {
             (0, SystemTime::now())
         };
 
-        let records = Self::update_records_from_an_existing_store(&config, &encryption_details);
+        info!("Using the {} storage backend", config.backend);
+        let storage = config.backend.open(&config.storage_dir)?;
+        if config.backend != StorageBackend::FlatFile {
+            Self::import_flat_files(storage.as_ref(), &config.storage_dir, &encryption_details);
+        }
+        let index = Self::update_records_from_an_existing_store(
+            storage.as_ref(),
+            &encryption_details,
+            &key_fingerprint_from_seed(&config.encryption_seed),
+        );
         let local_address = NetworkAddress::from_peer(local_id);
 
-        // Initialize records_by_distance
+        // Initialize records, records_by_distance and record_sizes
+        let mut records = HashMap::new();
         let mut records_by_distance: BTreeMap<U256, Key> = BTreeMap::new();
-        for (key, (addr, _record_type)) in records.iter() {
-            let distance = convert_distance_to_u256(&local_address.distance(addr));
-            let _ = records_by_distance.insert(distance, key.clone());
+        let mut record_sizes = HashMap::new();
+        for entry in index {
+            let addr = NetworkAddress::from_record_key(&entry.key);
+            let distance = convert_distance_to_u256(&local_address.distance(&addr));
+            let _ = records_by_distance.insert(distance, entry.key.clone());
+            let _ = record_sizes.insert(entry.key.clone(), entry.size);
+            let _ = records.insert(entry.key, (addr, entry.record_type));
         }
+        let stored_bytes = record_sizes.values().sum();
+        info!(
+            "Records use {stored_bytes} bytes of the {} bytes budget",
+            config.max_storage_bytes
+        );
 
         let cache_size = config.records_cache_size;
         let mut record_store = NodeRecordStore {
             local_address,
             config,
+            storage,
             records,
             records_by_distance,
+            record_sizes,
+            stored_bytes,
             records_cache: RecordCache::new(cache_size),
             network_event_sender,
             local_swarm_cmd_sender: swarm_cmd_sender,
@@ -397,7 +546,7 @@ impl NodeRecordStore {
 
         record_store.flush_historic_quoting_metrics();
 
-        record_store
+        Ok(record_store)
     }
 
     /// Set the record_count_metric to report the number of records stored to the metrics server
@@ -412,22 +561,6 @@ impl NodeRecordStore {
         self.responsible_distance_range
     }
 
-    // Converts a Key into a Hex string.
-    fn generate_filename(key: &Key) -> String {
-        hex::encode(key.as_ref())
-    }
-
-    // Converts a Hex string back into a Key.
-    fn get_data_from_filename(hex_str: &str) -> Option<Key> {
-        match hex::decode(hex_str) {
-            Ok(bytes) => Some(Key::from(bytes)),
-            Err(error) => {
-                error!("Error decoding hex string: {:?}", error);
-                None
-            }
-        }
-    }
-
     /// Upon read perform any data transformations required to return a `Record`.
     fn get_record_from_bytes<'a>(
         bytes: Vec<u8>,
@@ -454,26 +587,38 @@ impl NodeRecordStore {
         }
     }
 
+    /// Read and decrypt a record. A record that fails to decrypt is corrupted, it is dropped from the store.
     fn read_from_disk<'a>(
         encryption_details: &(Aes256GcmSiv, [u8; 4]),
         key: &Key,
-        storage_dir: &Path,
+        storage: &dyn RecordStorage,
+        swarm_cmd_sender: &mpsc::Sender<LocalSwarmCmd>,
     ) -> Option<Cow<'a, Record>> {
         let start = Instant::now();
-        let filename = Self::generate_filename(key);
-
-        let file_path = storage_dir.join(&filename);
+        let filename = key_to_hex(key);
 
         // we should only be reading if we know the record is written to disk properly
-        match fs::read(file_path) {
-            Ok(bytes) => {
+        match storage.read(key) {
+            Ok(Some(bytes)) => {
                 // vdash metric (if modified please notify at https://github.com/happybeing/vdash/issues):
                 info!(
                     "Retrieved record from disk! filename: {filename} after {:?}",
                     start.elapsed()
                 );
 
-                Self::get_record_from_bytes(bytes, key, encryption_details)
+                let record = Self::get_record_from_bytes(bytes, key, encryption_details);
+                if record.is_none() {
+                    warn!("Dropping record that failed to decrypt. filename: {filename}");
+                    send_local_swarm_cmd(
+                        swarm_cmd_sender.clone(),
+                        LocalSwarmCmd::RemoveFailedLocalRecord { key: key.clone() },
+                    );
+                }
+                record
+            }
+            Ok(None) => {
+                error!("Record missing from the storage. filename: {filename}");
+                None
             }
             Err(err) => {
                 error!("Error while reading file. filename: {filename}, error: {err:?}");
@@ -508,24 +653,58 @@ impl NodeRecordStore {
         }
     }
 
+    /// Whether storing the incoming record would exceed the maximum number of records or the storage budget.
+    fn is_full(&self, incoming_record_key: &Key, incoming_record_size: u64) -> bool {
+        let existing_size = self.record_sizes.get(incoming_record_key).copied();
+        let too_many_records =
+            existing_size.is_none() && self.records.len() >= self.config.max_records;
+        let stored_bytes = self
+            .stored_bytes
+            .saturating_sub(existing_size.unwrap_or(0))
+            .saturating_add(incoming_record_size);
+        too_many_records || stored_bytes > self.config.max_storage_bytes
+    }
+
+    /// The number of records the store can hold, given its storage budget and the average size of the records
+    /// stored so far.
+    fn records_capacity(&self) -> usize {
+        let average_record_size = match self.record_sizes.len() as u64 {
+            0 => AVERAGE_RECORD_SIZE,
+            count => (self.stored_bytes / count).max(1),
+        };
+        usize::try_from(self.config.max_storage_bytes / average_record_size)
+            .unwrap_or(usize::MAX)
+            .min(self.config.max_records)
+    }
+
     /// Prune the records in the store to ensure that we free up space
     /// for the incoming record.
     /// Returns Ok if the record can be stored because it is closer to the local peer
     /// or we are not full.
     ///
-    /// Err MaxRecords if we cannot store as it's farther than the farthest data we have
-    fn prune_records_if_needed(&mut self, incoming_record_key: &Key) -> Result<()> {
-        // we're not full, so we don't need to prune
-        if self.records.len() < self.config.max_records {
-            return Ok(());
+    /// Err MaxRecords if we cannot store as it's farther than the farthest data we have,
+    /// or if it does not fit in the storage budget.
+    fn prune_records_if_needed(
+        &mut self,
+        incoming_record_key: &Key,
+        incoming_record_size: u64,
+    ) -> Result<()> {
+        if incoming_record_size > self.config.max_storage_bytes {
+            return Err(Error::MaxRecords);
         }
-
-        if let Some((farthest_record, farthest_record_distance)) = self.farthest_record.clone() {
+        let incoming_record_distance = self
+            .local_address
+            .distance(&NetworkAddress::from_record_key(incoming_record_key));
+
+        while self.is_full(incoming_record_key, incoming_record_size) {
+            // we're full of records still being written, nothing can be pruned yet
+            let Some((farthest_record, farthest_record_distance)) = self.farthest_record.clone()
+            else {
+                return Err(Error::MaxRecords);
+            };
             // if the incoming record is farther than the farthest record, we can't store it
-            if farthest_record_distance
-                < self
-                    .local_address
-                    .distance(&NetworkAddress::from_record_key(incoming_record_key))
+            if farthest_record == *incoming_record_key
+                || farthest_record_distance < incoming_record_distance
             {
                 return Err(Error::MaxRecords);
             }
@@ -548,7 +727,7 @@ impl NodeRecordStore {
     //     result in mis-calculation of relevant records.
     pub fn cleanup_irrelevant_records(&mut self) {
         let accumulated_records = self.records.len();
-        if accumulated_records < MAX_RECORDS_COUNT / 10 {
+        if accumulated_records < self.records_capacity() / 10 {
             return;
         }
 
@@ -670,10 +849,15 @@ impl NodeRecordStore {
         // Store the new record to the cache
         self.records_cache.push_back(key.clone(), r.clone());
 
-        self.prune_records_if_needed(key)?;
+        let record_size = r.value.len() as u64 + ENCRYPTION_TAG_SIZE;
+        self.prune_records_if_needed(key, record_size)?;
+        if let Some(previous_size) = self.record_sizes.insert(key.clone(), record_size) {
+            self.stored_bytes = self.stored_bytes.saturating_sub(previous_size);
+        }
+        self.stored_bytes = self.stored_bytes.saturating_add(record_size);
 
-        let filename = Self::generate_filename(key);
-        let file_path = self.config.storage_dir.join(&filename);
+        let filename = key_to_hex(key);
+        let storage = Arc::clone(&self.storage);
 
         #[cfg(feature = "open-metrics")]
         if let Some(metric) = &self.record_count_metric {
@@ -687,7 +871,7 @@ impl NodeRecordStore {
         spawn(async move {
             let key = r.key.clone();
             if let Some(bytes) = Self::prepare_record_bytes(r, encryption_details) {
-                let cmd = match fs::write(&file_path, bytes) {
+                let cmd = match storage.write(&key, &record_type, &bytes) {
                     Ok(_) => {
                         // vdash metric (if modified please notify at https://github.com/happybeing/vdash/issues):
                         info!("Wrote record {record_key2:?} to disk! filename: {filename}");
@@ -726,7 +910,7 @@ impl NodeRecordStore {
 
         let mut quoting_metrics = QuotingMetrics {
             close_records_stored: records_stored,
-            max_records: self.config.max_records,
+            max_records: self.records_capacity(),
             received_payment_count: self.received_payment_count,
             live_time,
             network_density: None,
@@ -801,7 +985,12 @@ impl RecordStore for NodeRecordStore {
 
         debug!("GET request for Record key: {key}");
 
-        Self::read_from_disk(&self.encryption_details, k, &self.config.storage_dir)
+        Self::read_from_disk(
+            &self.encryption_details,
+            k,
+            self.storage.as_ref(),
+            &self.local_swarm_cmd_sender,
+        )
     }
 
     fn put(&mut self, record: Record) -> Result<()> {
@@ -874,6 +1063,9 @@ impl RecordStore for NodeRecordStore {
         }
 
         self.records_cache.remove(k);
+        if let Some(size) = self.record_sizes.remove(k) {
+            self.stored_bytes = self.stored_bytes.saturating_sub(size);
+        }
 
         #[cfg(feature = "open-metrics")]
         if let Some(metric) = &self.record_count_metric {
@@ -886,11 +1078,12 @@ impl RecordStore for NodeRecordStore {
             }
         }
 
-        let filename = Self::generate_filename(k);
-        let file_path = self.config.storage_dir.join(&filename);
+        let filename = key_to_hex(k);
+        let storage = Arc::clone(&self.storage);
+        let key = k.clone();
 
         let _handle = spawn(async move {
-            match fs::remove_file(file_path) {
+            match storage.remove(&key) {
                 Ok(_) => {
                     info!("Removed record from disk! filename: {filename}");
                 }
@@ -926,6 +1119,94 @@ impl RecordStore for NodeRecordStore {
     }
 }
 
+/// Offline access to the records persisted by a node, to inspect and repair the store of a stopped node.
+pub struct OfflineRecordStore {
+    storage: Arc<dyn RecordStorage>,
+    encryption_details: (Aes256GcmSiv, [u8; 4]),
+}
+
+impl OfflineRecordStore {
+    /// Open the records of the node `peer_id`, persisted in `storage_dir` by the `backend`.
+    pub fn open(
+        storage_dir: &Path,
+        peer_id: &PeerId,
+        backend: StorageBackend,
+    ) -> std::result::Result<Self, StorageBackendError> {
+        Ok(Self {
+            storage: backend.open(storage_dir)?,
+            encryption_details: derive_aes256gcm_siv_from_seed(&encryption_seed(peer_id)),
+        })
+    }
+
+    /// The keys of all the stored records.
+    pub fn keys(&self) -> std::result::Result<Vec<Key>, StorageBackendError> {
+        self.storage.keys()
+    }
+
+    /// The encrypted value of a record, `None` if it is not stored.
+    pub fn read_encrypted(
+        &self,
+        key: &Key,
+    ) -> std::result::Result<Option<Vec<u8>>, StorageBackendError> {
+        self.storage.read(key)
+    }
+
+    /// Decrypt a value read with `read_encrypted`, `None` if it was not encrypted by this node.
+    pub fn decrypt(&self, key: &Key, bytes: Vec<u8>) -> Option<Record> {
+        NodeRecordStore::get_record_from_bytes(bytes, key, &self.encryption_details)
+            .map(Cow::into_owned)
+    }
+
+    /// Encrypt and store a record, replacing any previous value.
+    pub fn put(
+        &self,
+        record: Record,
+        record_type: &RecordType,
+    ) -> std::result::Result<(), StorageBackendError> {
+        let key = record.key.clone();
+        let bytes = NodeRecordStore::prepare_record_bytes(record, self.encryption_details.clone())
+            .ok_or_else(|| std::io::Error::other("Failed to encrypt the record"))?;
+        self.storage.write(&key, record_type, &bytes)
+    }
+
+    /// Remove a record.
+    pub fn remove(&self, key: &Key) -> std::result::Result<(), StorageBackendError> {
+        self.storage.remove(key)
+    }
+
+    /// Move all the records into `target`, re-encrypting them with the key of its node.
+    ///
+    /// Each record is removed from this store once written to `target`, so an interrupted
+    /// migration can be resumed. Records that cannot be decrypted are left behind.
+    /// Returns the keys of the migrated records.
+    pub fn migrate_into(
+        &self,
+        target: &OfflineRecordStore,
+    ) -> std::result::Result<Vec<Key>, StorageBackendError> {
+        let mut migrated = vec![];
+        for key in self.keys()? {
+            let Some(bytes) = self.read_encrypted(&key)? else {
+                continue;
+            };
+            let Some(record) = self.decrypt(&key, bytes) else {
+                warn!(
+                    "Record {:?} cannot be decrypted, not migrating it",
+                    PrettyPrintRecordKey::from(&key)
+                );
+                continue;
+            };
+            let Some(record_type) = record_type_of(&record) else {
+                continue;
+            };
+            target.put(record, &record_type)?;
+            self.remove(&key)?;
+            migrated.push(key);
+        }
+        info!("Migrated {} records", migrated.len());
+        Ok(migrated)
+    }
+}
+
 /// A place holder RecordStore impl for the client that does nothing
 #[derive(Default, Debug)]
 pub struct ClientRecordStore {
@@ -1002,6 +1283,7 @@ mod tests {
     };
     use bytes::Bytes;
     use eyre::ContextCompat;
+    use itertools::Itertools;
     use libp2p::{core::multihash::Multihash, kad::RecordKey};
     use quickcheck::*;
     use tokio::runtime::Runtime;
@@ -1065,7 +1347,8 @@ mod tests {
             Default::default(),
             network_event_sender,
             swarm_cmd_sender,
-        );
+        )
+        .expect("Failed to open the record store");
 
         // An initial unverified put should not write to disk
         assert!(store.put(r.clone()).is_ok());
@@ -1143,7 +1426,8 @@ mod tests {
             store_config.clone(),
             network_event_sender.clone(),
             swarm_cmd_sender.clone(),
-        );
+        )
+        .expect("Failed to open the record store");
 
         // Create a chunk
         let chunk_data = Bytes::from_static(b"Test chunk data");
@@ -1191,7 +1475,8 @@ mod tests {
             store_config,
             new_network_event_sender,
             new_swarm_cmd_sender,
-        );
+        )
+        .expect("Failed to open the record store");
 
         // Verify the record still exists
         let stored_record = store.get(&record.key);
@@ -1216,7 +1501,8 @@ mod tests {
             store_config_diff,
             diff_network_event_sender,
             diff_swarm_cmd_sender,
-        );
+        )
+        .expect("Failed to open the record store");
 
         // When encryption is enabled, the record should be gone because it can't be decrypted
         // with the different encryption seed
@@ -1228,6 +1514,145 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn kv_backend_imports_flat_files() -> eyre::Result<()> {
+        let tmp_dir = TempDir::new()?;
+        let flat_file_config = NodeRecordStoreConfig {
+            storage_dir: tmp_dir.to_path_buf(),
+            encryption_seed: [1u8; 16],
+            ..Default::default()
+        };
+        let self_id = PeerId::random();
+        let (network_event_sender, _network_event_receiver) = mpsc::channel(1);
+        let (swarm_cmd_sender, mut swarm_cmd_receiver) = mpsc::channel(1);
+
+        let mut store = NodeRecordStore::with_config(
+            self_id,
+            flat_file_config.clone(),
+            network_event_sender.clone(),
+            swarm_cmd_sender.clone(),
+        )?;
+        let chunk = Chunk::new(Bytes::from_static(b"Test chunk data"));
+        let record = Record {
+            key: NetworkAddress::ChunkAddress(*chunk.address()).to_record_key(),
+            value: try_serialize_record(&chunk, RecordKind::Chunk)?.to_vec(),
+            expires: None,
+            publisher: None,
+        };
+        store.put_verified(record.clone(), RecordType::Chunk)?;
+        match swarm_cmd_receiver.recv().await {
+            Some(LocalSwarmCmd::AddLocalRecordAsStored { key, record_type }) => {
+                store.mark_as_stored(key, record_type);
+            }
+            _ => panic!("Unexpected command received"),
+        }
+        drop(store);
+
+        // Restart the node with the kv backend, the flat file is moved into the database
+        let kv_config = NodeRecordStoreConfig {
+            backend: StorageBackend::Kv,
+            ..flat_file_config
+        };
+        let store = NodeRecordStore::with_config(
+            self_id,
+            kv_config.clone(),
+            network_event_sender.clone(),
+            swarm_cmd_sender.clone(),
+        )?;
+        assert!(store.contains(&record.key));
+        assert_eq!(
+            store.get(&record.key).map(|stored| stored.value.clone()),
+            Some(record.value.clone())
+        );
+        assert!(!tmp_dir.path().join(key_to_hex(&record.key)).exists());
+        drop(store);
+
+        // The records can't be decrypted with a different encryption seed
+        let store = NodeRecordStore::with_config(
+            PeerId::random(),
+            NodeRecordStoreConfig {
+                encryption_seed: [2u8; 16],
+                ..kv_config
+            },
+            network_event_sender,
+            swarm_cmd_sender,
+        )?;
+        assert!(!store.contains(&record.key));
+        assert!(store.get(&record.key).is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn kv_index_is_trusted_and_corrupted_records_are_dropped_on_read() -> eyre::Result<()> {
+        let tmp_dir = TempDir::new()?;
+        let config = NodeRecordStoreConfig {
+            storage_dir: tmp_dir.to_path_buf(),
+            encryption_seed: [1u8; 16],
+            backend: StorageBackend::Kv,
+            ..Default::default()
+        };
+        let self_id = PeerId::random();
+        let (network_event_sender, _network_event_receiver) = mpsc::channel(1);
+        let (swarm_cmd_sender, mut swarm_cmd_receiver) = mpsc::channel(1);
+
+        let mut store = NodeRecordStore::with_config(
+            self_id,
+            config.clone(),
+            network_event_sender.clone(),
+            swarm_cmd_sender.clone(),
+        )?;
+        let mut records = vec![];
+        for content in [b"first chunk".as_slice(), b"second chunk".as_slice()] {
+            let chunk = Chunk::new(Bytes::copy_from_slice(content));
+            let record = Record {
+                key: NetworkAddress::ChunkAddress(*chunk.address()).to_record_key(),
+                value: try_serialize_record(&chunk, RecordKind::Chunk)?.to_vec(),
+                expires: None,
+                publisher: None,
+            };
+            store.put_verified(record.clone(), RecordType::Chunk)?;
+            match swarm_cmd_receiver.recv().await {
+                Some(LocalSwarmCmd::AddLocalRecordAsStored { key, record_type }) => {
+                    store.mark_as_stored(key, record_type);
+                }
+                _ => panic!("Unexpected command received"),
+            }
+            records.push(record);
+        }
+        drop(store);
+
+        // Corrupt the first record only
+        let storage = StorageBackend::Kv.open(tmp_dir.path())?;
+        storage.write(&records[0].key, &RecordType::Chunk, b"corrupted")?;
+        drop(storage);
+
+        // The index was written with our key, so the records are not decrypted at startup
+        let mut store =
+            NodeRecordStore::with_config(self_id, config, network_event_sender, swarm_cmd_sender)?;
+        assert!(store.contains(&records[0].key));
+        assert_eq!(
+            store
+                .get(&records[1].key)
+                .map(|stored| stored.value.clone()),
+            Some(records[1].value.clone())
+        );
+
+        // The corrupted record is dropped when it is read
+        assert!(store.get(&records[0].key).is_none());
+        match swarm_cmd_receiver.recv().await {
+            Some(LocalSwarmCmd::RemoveFailedLocalRecord { key }) => {
+                assert_eq!(key, records[0].key);
+                store.remove(&key);
+            }
+            _ => panic!("Unexpected command received"),
+        }
+        assert!(!store.contains(&records[0].key));
+        assert!(store.contains(&records[1].key));
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn can_store_and_retrieve_chunk() {
         let temp_dir = std::env::temp_dir();
@@ -1244,7 +1669,8 @@ mod tests {
             store_config,
             network_event_sender,
             swarm_cmd_sender,
-        );
+        )
+        .expect("Failed to open the record store");
 
         // Create a chunk
         let chunk_data = Bytes::from_static(b"Test chunk data");
@@ -1308,7 +1734,8 @@ mod tests {
             store_config,
             network_event_sender,
             swarm_cmd_sender,
-        );
+        )
+        .expect("Failed to open the record store");
 
         // Create a scratchpad
         let unencrypted_scratchpad_data = Bytes::from_static(b"Test scratchpad data");
@@ -1401,7 +1828,8 @@ mod tests {
             store_config.clone(),
             network_event_sender,
             swarm_cmd_sender,
-        );
+        )
+        .expect("Failed to open the record store");
         // keep track of everything ever stored, to check missing at the end are further away
         let mut stored_records_at_some_point: Vec<RecordKey> = vec![];
         let self_address = NetworkAddress::from_peer(self_id);
@@ -1503,6 +1931,144 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn pruning_on_storage_budget() -> eyre::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let value = try_serialize_record(
+            &(0..1000).map(|_| rand::random::<u8>()).collect::<Bytes>(),
+            RecordKind::Chunk,
+        )?
+        .to_vec();
+        // the budget holds 10 records, far below the default `max_records`
+        let record_size = value.len() as u64 + ENCRYPTION_TAG_SIZE;
+        let store_config = NodeRecordStoreConfig {
+            storage_dir: temp_dir.to_path_buf(),
+            max_storage_bytes: 10 * record_size,
+            ..Default::default()
+        };
+        let self_id = PeerId::random();
+        let (network_event_sender, _) = mpsc::channel(1);
+        let (swarm_cmd_sender, _) = mpsc::channel(1);
+        let mut store = NodeRecordStore::with_config(
+            self_id,
+            store_config,
+            network_event_sender,
+            swarm_cmd_sender,
+        )
+        .expect("Failed to open the record store");
+        let self_address = NetworkAddress::from_peer(self_id);
+
+        let mut failed_records = vec![];
+        for _ in 0..20 {
+            let record_key = NetworkAddress::from_peer(PeerId::random()).to_record_key();
+            let record = Record {
+                key: record_key.clone(),
+                value: value.clone(),
+                publisher: None,
+                expires: None,
+            };
+            if store.put_verified(record, RecordType::Chunk).is_ok() {
+                store.mark_as_stored(record_key, RecordType::Chunk);
+            } else {
+                failed_records.push(record_key);
+            }
+        }
+
+        let stored = store.record_addresses();
+        assert_eq!(stored.len(), 10);
+        assert_eq!(store.stored_bytes, 10 * record_size);
+
+        // records are refused only when they are farther than all the stored ones
+        let farthest_stored = stored
+            .keys()
+            .map(|addr| self_address.distance(addr))
+            .max()
+            .expect("records are stored");
+        for key in failed_records {
+            assert!(
+                self_address.distance(&NetworkAddress::from_record_key(&key)) > farthest_stored
+            );
+        }
+
+        // quotes are based on the records the budget can hold, not on `max_records`
+        let key = NetworkAddress::from_peer(PeerId::random()).to_record_key();
+        let (metrics, _) = store.quoting_metrics(&key, None);
+        assert_eq!(metrics.max_records, 10);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn migration_reencrypts_records_for_new_key() -> eyre::Result<()> {
+        let old_dir = TempDir::new()?;
+        let new_dir = TempDir::new()?;
+        let old_id = PeerId::random();
+        let new_id = PeerId::random();
+        let old_store = OfflineRecordStore::open(&old_dir, &old_id, StorageBackend::FlatFile)?;
+
+        let mut records = vec![];
+        for content in [b"first chunk", b"other chunk"] {
+            let chunk = Chunk::new(Bytes::from_static(content));
+            let record = Record {
+                key: chunk.network_address().to_record_key(),
+                value: try_serialize_record(&chunk, RecordKind::Chunk)?.to_vec(),
+                publisher: None,
+                expires: None,
+            };
+            old_store.put(record.clone(), &RecordType::Chunk)?;
+            records.push(record);
+        }
+        // a record encrypted by another node cannot be migrated
+        let foreign_chunk = Chunk::new(Bytes::from_static(b"foreign chunk"));
+        let foreign_key = foreign_chunk.network_address().to_record_key();
+        OfflineRecordStore::open(&old_dir, &PeerId::random(), StorageBackend::FlatFile)?.put(
+            Record {
+                key: foreign_key.clone(),
+                value: try_serialize_record(&foreign_chunk, RecordKind::Chunk)?.to_vec(),
+                publisher: None,
+                expires: None,
+            },
+            &RecordType::Chunk,
+        )?;
+
+        let new_store = OfflineRecordStore::open(&new_dir, &new_id, StorageBackend::FlatFile)?;
+        let migrated = old_store.migrate_into(&new_store)?;
+        assert_eq!(
+            migrated
+                .into_iter()
+                .collect::<std::collections::HashSet<_>>(),
+            records.iter().map(|record| record.key.clone()).collect()
+        );
+        assert_eq!(old_store.keys()?, vec![foreign_key]);
+
+        // the store of the new node indexes and decrypts the migrated records
+        let store_config = NodeRecordStoreConfig {
+            storage_dir: new_dir.to_path_buf(),
+            historic_quote_dir: new_dir.to_path_buf(),
+            encryption_seed: encryption_seed(&new_id),
+            backend: StorageBackend::FlatFile,
+            ..Default::default()
+        };
+        let (network_event_sender, _) = mpsc::channel(1);
+        let (swarm_cmd_sender, _) = mpsc::channel(1);
+        let store = NodeRecordStore::with_config(
+            new_id,
+            store_config,
+            network_event_sender,
+            swarm_cmd_sender,
+        )
+        .expect("Failed to open the record store");
+        for record in records {
+            assert!(store.contains(&record.key));
+            let stored = store
+                .get(&record.key)
+                .context("migrated record is readable")?;
+            assert_eq!(stored.value, record.value);
+        }
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn get_records_within_range() -> eyre::Result<()> {
         let max_records = 50;
@@ -1525,7 +2091,8 @@ mod tests {
             store_config,
             network_event_sender,
             swarm_cmd_sender,
-        );
+        )
+        .expect("Failed to open the record store");
 
         let mut stored_records: Vec<RecordKey> = vec![];
         let self_address = NetworkAddress::from_peer(self_id);
@@ -1609,7 +2176,8 @@ mod tests {
             store_config.clone(),
             network_event_sender.clone(),
             swarm_cmd_sender.clone(),
-        );
+        )
+        .expect("Failed to open the record store");
 
         store.payment_received();
 
@@ -1621,7 +2189,8 @@ mod tests {
             store_config,
             network_event_sender,
             swarm_cmd_sender,
-        );
+        )
+        .expect("Failed to open the record store");
 
         assert_eq!(1, new_store.received_payment_count);
         assert_eq!(store.timestamp, new_store.timestamp);
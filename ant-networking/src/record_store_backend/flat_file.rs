@@ -0,0 +1,109 @@
+// Copyright 2024 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use super::{key_to_hex, IndexEntry, RecordStorage, Result};
+use ant_protocol::storage::RecordType;
+use libp2p::kad::RecordKey as Key;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+use walkdir::WalkDir;
+
+/// Extension of the files being written, renamed to the record file once complete.
+const PARTIAL_FILE_EXTENSION: &str = "partial";
+
+/// Stores every record in its own file, named after the hex encoded record key.
+///
+/// There is no index: the directory is walked and every record read at startup.
+#[derive(Debug, Clone)]
+pub struct FlatFileStorage {
+    storage_dir: PathBuf,
+}
+
+impl FlatFileStorage {
+    pub fn new(storage_dir: &Path) -> Self {
+        Self {
+            storage_dir: storage_dir.to_path_buf(),
+        }
+    }
+
+    fn record_path(&self, key: &Key) -> PathBuf {
+        self.storage_dir.join(key_to_hex(key))
+    }
+
+    /// The key of a record file, `None` if the file is not a record.
+    fn key_from_path(path: &Path) -> Option<Key> {
+        if path.extension().is_some() {
+            return None;
+        }
+        let filename = path.file_name()?.to_str()?;
+        match hex::decode(filename) {
+            Ok(bytes) => Some(Key::from(bytes)),
+            Err(error) => {
+                error!("Error decoding hex string: {:?}", error);
+                None
+            }
+        }
+    }
+}
+
+impl RecordStorage for FlatFileStorage {
+    fn index(&self) -> Result<Option<Vec<IndexEntry>>> {
+        Ok(None)
+    }
+
+    fn keys(&self) -> Result<Vec<Key>> {
+        let mut keys = vec![];
+        for entry in WalkDir::new(&self.storage_dir)
+            .into_iter()
+            .filter_map(|e| e.ok())
+        {
+            let path = entry.path();
+            if !path.is_file() {
+                continue;
+            }
+            if path.extension().and_then(|ext| ext.to_str()) == Some(PARTIAL_FILE_EXTENSION) {
+                // the node stopped while writing this record
+                info!("Removing partially written record file {path:?}");
+                if let Err(err) = fs::remove_file(path) {
+                    warn!("Failed to remove partially written record file {path:?}: {err:?}");
+                }
+                continue;
+            }
+            if let Some(key) = Self::key_from_path(path) {
+                debug!("Existing record found: {path:?}");
+                keys.push(key);
+            }
+        }
+        Ok(keys)
+    }
+
+    fn read(&self, key: &Key) -> Result<Option<Vec<u8>>> {
+        match fs::read(self.record_path(key)) {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn write(&self, key: &Key, _record_type: &RecordType, value: &[u8]) -> Result<()> {
+        let file_path = self.record_path(key);
+        let partial_path = file_path.with_extension(PARTIAL_FILE_EXTENSION);
+        fs::write(&partial_path, value)?;
+        fs::rename(&partial_path, &file_path)?;
+        Ok(())
+    }
+
+    fn remove(&self, key: &Key) -> Result<()> {
+        match fs::remove_file(self.record_path(key)) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,142 @@
+// Copyright 2024 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use super::{IndexEntry, RecordStorage, Result};
+use ant_protocol::storage::RecordType;
+use libp2p::kad::RecordKey as Key;
+use redb::{Database, ReadableTable, TableDefinition};
+use std::path::Path;
+
+/// File name of the database in the storage directory.
+const DATABASE_FILENAME: &str = "records.redb";
+
+/// Record key to encrypted record value.
+const RECORDS_TABLE: TableDefinition<&[u8], &[u8]> = TableDefinition::new("records");
+/// Record key to the serialised `RecordType` and size of the value, read at startup instead of the values.
+const INDEX_TABLE: TableDefinition<&[u8], &[u8]> = TableDefinition::new("index");
+/// Metadata of the database, such as the fingerprint of the key the records are encrypted with.
+const METADATA_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("metadata");
+
+const KEY_FINGERPRINT: &str = "key_fingerprint";
+
+/// Stores the records in an embedded key-value database.
+///
+/// The value and the index entry of a record are written in the same transaction, which keeps the index
+/// consistent with the stored values and lets the node start without reading every record.
+pub struct KvStorage {
+    db: Database,
+}
+
+impl KvStorage {
+    /// Whether `storage_dir` holds a database.
+    pub fn exists(storage_dir: &Path) -> bool {
+        storage_dir.join(DATABASE_FILENAME).is_file()
+    }
+
+    /// Open the database in `storage_dir`, creating it if needed.
+    pub fn open(storage_dir: &Path) -> Result<Self> {
+        std::fs::create_dir_all(storage_dir)?;
+        let db =
+            Database::create(storage_dir.join(DATABASE_FILENAME)).map_err(redb::Error::from)?;
+
+        // create the tables up front, so that reads never hit a missing table
+        let txn = db.begin_write().map_err(redb::Error::from)?;
+        {
+            let _ = txn.open_table(RECORDS_TABLE).map_err(redb::Error::from)?;
+            let _ = txn.open_table(INDEX_TABLE).map_err(redb::Error::from)?;
+            let _ = txn.open_table(METADATA_TABLE).map_err(redb::Error::from)?;
+        }
+        txn.commit().map_err(redb::Error::from)?;
+
+        Ok(Self { db })
+    }
+}
+
+impl RecordStorage for KvStorage {
+    fn index(&self) -> Result<Option<Vec<IndexEntry>>> {
+        let txn = self.db.begin_read().map_err(redb::Error::from)?;
+        let table = txn.open_table(INDEX_TABLE).map_err(redb::Error::from)?;
+        let mut index = vec![];
+        for entry in table.iter().map_err(redb::Error::from)? {
+            let (key, entry) = entry.map_err(redb::Error::from)?;
+            let (record_type, size) = rmp_serde::from_slice(entry.value())?;
+            index.push(IndexEntry {
+                key: Key::from(key.value().to_vec()),
+                record_type,
+                size,
+            });
+        }
+        Ok(Some(index))
+    }
+
+    fn keys(&self) -> Result<Vec<Key>> {
+        let txn = self.db.begin_read().map_err(redb::Error::from)?;
+        let table = txn.open_table(RECORDS_TABLE).map_err(redb::Error::from)?;
+        let mut keys = vec![];
+        for entry in table.iter().map_err(redb::Error::from)? {
+            let (key, _value) = entry.map_err(redb::Error::from)?;
+            keys.push(Key::from(key.value().to_vec()));
+        }
+        Ok(keys)
+    }
+
+    fn read(&self, key: &Key) -> Result<Option<Vec<u8>>> {
+        let txn = self.db.begin_read().map_err(redb::Error::from)?;
+        let table = txn.open_table(RECORDS_TABLE).map_err(redb::Error::from)?;
+        let value = table.get(key.as_ref()).map_err(redb::Error::from)?;
+        Ok(value.map(|value| value.value().to_vec()))
+    }
+
+    fn write(&self, key: &Key, record_type: &RecordType, value: &[u8]) -> Result<()> {
+        let entry = rmp_serde::to_vec(&(record_type, value.len() as u64))?;
+        let txn = self.db.begin_write().map_err(redb::Error::from)?;
+        {
+            let mut records = txn.open_table(RECORDS_TABLE).map_err(redb::Error::from)?;
+            let _ = records
+                .insert(key.as_ref(), value)
+                .map_err(redb::Error::from)?;
+            let mut index = txn.open_table(INDEX_TABLE).map_err(redb::Error::from)?;
+            let _ = index
+                .insert(key.as_ref(), entry.as_slice())
+                .map_err(redb::Error::from)?;
+        }
+        txn.commit().map_err(redb::Error::from)?;
+        Ok(())
+    }
+
+    fn remove(&self, key: &Key) -> Result<()> {
+        let txn = self.db.begin_write().map_err(redb::Error::from)?;
+        {
+            let mut records = txn.open_table(RECORDS_TABLE).map_err(redb::Error::from)?;
+            let _ = records.remove(key.as_ref()).map_err(redb::Error::from)?;
+            let mut index = txn.open_table(INDEX_TABLE).map_err(redb::Error::from)?;
+            let _ = index.remove(key.as_ref()).map_err(redb::Error::from)?;
+        }
+        txn.commit().map_err(redb::Error::from)?;
+        Ok(())
+    }
+
+    fn key_fingerprint(&self) -> Result<Option<Vec<u8>>> {
+        let txn = self.db.begin_read().map_err(redb::Error::from)?;
+        let table = txn.open_table(METADATA_TABLE).map_err(redb::Error::from)?;
+        let value = table.get(KEY_FINGERPRINT).map_err(redb::Error::from)?;
+        Ok(value.map(|value| value.value().to_vec()))
+    }
+
+    fn set_key_fingerprint(&self, fingerprint: &[u8]) -> Result<()> {
+        let txn = self.db.begin_write().map_err(redb::Error::from)?;
+        {
+            let mut table = txn.open_table(METADATA_TABLE).map_err(redb::Error::from)?;
+            let _ = table
+                .insert(KEY_FINGERPRINT, fingerprint)
+                .map_err(redb::Error::from)?;
+        }
+        txn.commit().map_err(redb::Error::from)?;
+        Ok(())
+    }
+}
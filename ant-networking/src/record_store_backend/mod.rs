@@ -0,0 +1,214 @@
+// Copyright 2024 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Storage backends holding the encrypted record values of a `NodeRecordStore`.
+
+mod flat_file;
+mod kv;
+
+pub use flat_file::FlatFileStorage;
+pub use kv::KvStorage;
+
+use ant_protocol::storage::RecordType;
+use libp2p::kad::RecordKey as Key;
+use std::{path::Path, sync::Arc};
+
+/// Errors returned by a [`RecordStorage`].
+#[derive(Debug, thiserror::Error)]
+pub enum StorageBackendError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Embedded database error: {0}")]
+    Database(Box<redb::Error>),
+    #[error("Failed to serialise the record index: {0}")]
+    IndexSerialisation(#[from] rmp_serde::encode::Error),
+    #[error("Failed to deserialise the record index: {0}")]
+    IndexDeserialisation(#[from] rmp_serde::decode::Error),
+}
+
+impl From<redb::Error> for StorageBackendError {
+    fn from(error: redb::Error) -> Self {
+        Self::Database(Box::new(error))
+    }
+}
+
+pub type Result<T> = std::result::Result<T, StorageBackendError>;
+
+/// A record listed in the index of a [`RecordStorage`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexEntry {
+    pub key: Key,
+    pub record_type: RecordType,
+    /// The size of the stored value, in bytes.
+    pub size: u64,
+}
+
+/// Where the records of a node are persisted.
+///
+/// The values handed to the storage are already encrypted, the storage only has to keep them safe.
+/// Writes must be atomic: a record is either fully stored or not at all.
+pub trait RecordStorage: Send + Sync {
+    /// The keys of the stored records along with their type and size, if the storage keeps an index of them.
+    ///
+    /// `None` means the index has to be rebuilt by reading every record listed by [`RecordStorage::keys`].
+    fn index(&self) -> Result<Option<Vec<IndexEntry>>>;
+
+    /// The keys of all the stored records.
+    fn keys(&self) -> Result<Vec<Key>>;
+
+    /// Read the value of a record, `None` if it is not stored.
+    fn read(&self, key: &Key) -> Result<Option<Vec<u8>>>;
+
+    /// Store the value of a record, replacing any previous value.
+    fn write(&self, key: &Key, record_type: &RecordType, value: &[u8]) -> Result<()>;
+
+    /// Remove a record. Removing a record that is not stored is not an error.
+    fn remove(&self, key: &Key) -> Result<()>;
+
+    /// The fingerprint of the key the indexed records are encrypted with, as set by
+    /// [`RecordStorage::set_key_fingerprint`]. Storages without an index don't keep one.
+    fn key_fingerprint(&self) -> Result<Option<Vec<u8>>> {
+        Ok(None)
+    }
+
+    /// Remember the fingerprint of the key the indexed records are encrypted with.
+    fn set_key_fingerprint(&self, _fingerprint: &[u8]) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// The kind of storage backend used by a node.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "kebab-case")]
+pub enum StorageBackend {
+    /// One file per record in the storage directory.
+    #[default]
+    FlatFile,
+    /// An embedded key-value database, indexing the records for a fast startup.
+    Kv,
+}
+
+impl StorageBackend {
+    /// The backend of the existing storage in `storage_dir`.
+    ///
+    /// A node using the key-value store moves any record left in flat files into it, so the storage is the
+    /// key-value store as soon as its database exists.
+    pub fn detect(storage_dir: &Path) -> Self {
+        if KvStorage::exists(storage_dir) {
+            Self::Kv
+        } else {
+            Self::FlatFile
+        }
+    }
+
+    /// Open the storage of this kind in `storage_dir`.
+    pub fn open(&self, storage_dir: &Path) -> Result<Arc<dyn RecordStorage>> {
+        let storage: Arc<dyn RecordStorage> = match self {
+            Self::FlatFile => Arc::new(FlatFileStorage::new(storage_dir)),
+            Self::Kv => Arc::new(KvStorage::open(storage_dir)?),
+        };
+        Ok(storage)
+    }
+}
+
+/// Converts a Key into a Hex string.
+pub(crate) fn key_to_hex(key: &Key) -> String {
+    hex::encode(key.as_ref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::TempDir;
+    use xor_name::XorName;
+
+    fn check_storage(storage: &dyn RecordStorage) -> Result<()> {
+        let chunk_key = Key::new(&XorName::random(&mut rand::thread_rng()));
+        let other_key = Key::new(&XorName::random(&mut rand::thread_rng()));
+        let other_type = RecordType::NonChunk(XorName::random(&mut rand::thread_rng()));
+
+        storage.write(&chunk_key, &RecordType::Chunk, b"chunk")?;
+        storage.write(&other_key, &other_type, b"first")?;
+        storage.write(&other_key, &other_type, b"second")?;
+
+        assert_eq!(storage.read(&chunk_key)?, Some(b"chunk".to_vec()));
+        assert_eq!(storage.read(&other_key)?, Some(b"second".to_vec()));
+        let keys = storage.keys()?;
+        assert_eq!(keys.len(), 2);
+        assert!(keys.contains(&chunk_key) && keys.contains(&other_key));
+
+        storage.remove(&chunk_key)?;
+        storage.remove(&chunk_key)?;
+        assert_eq!(storage.read(&chunk_key)?, None);
+        assert_eq!(storage.keys()?, vec![other_key.clone()]);
+        if let Some(index) = storage.index()? {
+            assert_eq!(
+                index,
+                vec![IndexEntry {
+                    key: other_key,
+                    record_type: other_type,
+                    size: 6,
+                }]
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn backends_store_read_and_remove_records() -> eyre::Result<()> {
+        for backend in [StorageBackend::FlatFile, StorageBackend::Kv] {
+            let temp_dir = TempDir::new()?;
+            let storage = backend.open(temp_dir.path())?;
+            check_storage(storage.as_ref())?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn backend_is_detected_from_the_storage() -> eyre::Result<()> {
+        let temp_dir = TempDir::new()?;
+        assert_eq!(
+            StorageBackend::detect(temp_dir.path()),
+            StorageBackend::FlatFile
+        );
+        let _ = StorageBackend::FlatFile.open(temp_dir.path())?;
+        assert_eq!(
+            StorageBackend::detect(temp_dir.path()),
+            StorageBackend::FlatFile
+        );
+        let _ = StorageBackend::Kv.open(temp_dir.path())?;
+        assert_eq!(StorageBackend::detect(temp_dir.path()), StorageBackend::Kv);
+        Ok(())
+    }
+
+    #[test]
+    fn kv_index_survives_reopening() -> eyre::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let key = Key::new(&XorName::random(&mut rand::thread_rng()));
+        {
+            let storage = StorageBackend::Kv.open(temp_dir.path())?;
+            storage.write(&key, &RecordType::Chunk, b"chunk")?;
+        }
+        let storage = StorageBackend::Kv.open(temp_dir.path())?;
+        assert_eq!(
+            storage.index()?,
+            Some(vec![IndexEntry {
+                key,
+                record_type: RecordType::Chunk,
+                size: 5,
+            }])
+        );
+        assert_eq!(storage.key_fingerprint()?, None);
+        storage.set_key_fingerprint(b"fingerprint")?;
+        drop(storage);
+
+        let storage = StorageBackend::Kv.open(temp_dir.path())?;
+        assert_eq!(storage.key_fingerprint()?, Some(b"fingerprint".to_vec()));
+        Ok(())
+    }
+}
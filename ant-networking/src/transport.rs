@@ -9,22 +9,84 @@
 #[cfg(feature = "open-metrics")]
 use crate::MetricsRegistries;
 use libp2p::{
-    core::{muxing::StreamMuxerBox, transport},
+    core::{muxing::StreamMuxerBox, transport, upgrade},
     identity::Keypair,
-    PeerId, Transport as _,
+    multiaddr::Protocol,
+    Multiaddr, PeerId, Transport as _,
 };
+use std::{collections::BTreeSet, net::SocketAddr};
+
+/// A transport the network can dial and listen on.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, strum::Display, strum::EnumString,
+)]
+#[strum(ascii_case_insensitive)]
+pub enum TransportProtocol {
+    /// QUIC over UDP, the default transport.
+    #[strum(serialize = "quic")]
+    Quic,
+    /// TCP secured with noise and multiplexed with yamux.
+    #[strum(serialize = "tcp")]
+    Tcp,
+    /// WebSocket over TCP, secured with noise and multiplexed with yamux.
+    #[strum(serialize = "ws")]
+    WebSocket,
+}
+
+impl TransportProtocol {
+    /// All the supported transports.
+    pub const ALL: [TransportProtocol; 3] = [Self::Quic, Self::Tcp, Self::WebSocket];
+
+    /// The address to listen on with this transport.
+    pub(crate) fn listen_addr(&self, ip_port: SocketAddr) -> Multiaddr {
+        let addr = Multiaddr::from(ip_port.ip());
+        match self {
+            Self::Quic => addr
+                .with(Protocol::Udp(ip_port.port()))
+                .with(Protocol::QuicV1),
+            Self::Tcp => addr.with(Protocol::Tcp(ip_port.port())),
+            Self::WebSocket => addr
+                .with(Protocol::Tcp(ip_port.port()))
+                .with(Protocol::Ws("/".into())),
+        }
+    }
+}
+
+/// The transports used when none are configured.
+pub(crate) fn default_transports() -> BTreeSet<TransportProtocol> {
+    BTreeSet::from([TransportProtocol::Quic])
+}
 
 pub(crate) fn build_transport(
     keypair: &Keypair,
+    transports: &BTreeSet<TransportProtocol>,
     #[cfg(feature = "open-metrics")] registries: &mut MetricsRegistries,
 ) -> transport::Boxed<(PeerId, StreamMuxerBox)> {
-    let trans = generate_quic_transport(keypair);
-    #[cfg(feature = "open-metrics")]
-    let trans = libp2p::metrics::BandwidthTransport::new(trans, &mut registries.standard_metrics);
+    let mut trans = transport::dummy::DummyTransport::<(PeerId, StreamMuxerBox)>::new().boxed();
+    for protocol in transports {
+        trans = match protocol {
+            TransportProtocol::Quic => generate_quic_transport(keypair)
+                .map(|(peer_id, muxer), _| (peer_id, StreamMuxerBox::new(muxer)))
+                .or_transport(trans)
+                .map(|either, _| either.into_inner())
+                .boxed(),
+            TransportProtocol::Tcp => generate_tcp_transport(keypair)
+                .or_transport(trans)
+                .map(|either, _| either.into_inner())
+                .boxed(),
+            TransportProtocol::WebSocket => generate_websocket_transport(keypair)
+                .or_transport(trans)
+                .map(|either, _| either.into_inner())
+                .boxed(),
+        };
+    }
 
-    let trans = trans.map(|(peer_id, muxer), _| (peer_id, StreamMuxerBox::new(muxer)));
+    #[cfg(feature = "open-metrics")]
+    let trans = libp2p::metrics::BandwidthTransport::new(trans, &mut registries.standard_metrics)
+        .map(|(peer_id, muxer), _| (peer_id, StreamMuxerBox::new(muxer)))
+        .boxed();
 
-    trans.boxed()
+    trans
 }
 
 fn generate_quic_transport(
@@ -32,3 +94,51 @@ fn generate_quic_transport(
 ) -> libp2p::quic::GenTransport<libp2p::quic::tokio::Provider> {
     libp2p::quic::tokio::Transport::new(libp2p::quic::Config::new(keypair))
 }
+
+fn generate_tcp_transport(keypair: &Keypair) -> transport::Boxed<(PeerId, StreamMuxerBox)> {
+    libp2p::tcp::tokio::Transport::new(libp2p::tcp::Config::default().nodelay(true))
+        .upgrade(upgrade::Version::V1Lazy)
+        .authenticate(
+            libp2p::noise::Config::new(keypair)
+                .expect("Signing libp2p-noise static DH keypair failed."),
+        )
+        .multiplex(libp2p::yamux::Config::default())
+        .boxed()
+}
+
+fn generate_websocket_transport(keypair: &Keypair) -> transport::Boxed<(PeerId, StreamMuxerBox)> {
+    libp2p::websocket::WsConfig::new(libp2p::tcp::tokio::Transport::new(
+        libp2p::tcp::Config::default().nodelay(true),
+    ))
+    .upgrade(upgrade::Version::V1Lazy)
+    .authenticate(
+        libp2p::noise::Config::new(keypair)
+            .expect("Signing libp2p-noise static DH keypair failed."),
+    )
+    .multiplex(libp2p::yamux::Config::default())
+    .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transports_listen_on_their_own_protocol() {
+        let ip_port: SocketAddr = "127.0.0.1:12000".parse().expect("valid socket addr");
+        let listen_addrs = TransportProtocol::ALL.map(|protocol| protocol.listen_addr(ip_port));
+        assert_eq!(
+            listen_addrs.map(|addr| addr.to_string()),
+            [
+                "/ip4/127.0.0.1/udp/12000/quic-v1",
+                "/ip4/127.0.0.1/tcp/12000",
+                "/ip4/127.0.0.1/tcp/12000/ws",
+            ]
+        );
+
+        for protocol in TransportProtocol::ALL {
+            assert_eq!(protocol.to_string().parse(), Ok(protocol));
+        }
+        assert_eq!("WS".parse(), Ok(TransportProtocol::WebSocket));
+    }
+}
@@ -82,6 +82,7 @@ pub struct InstallNodeServiceCtxBuilder {
     pub network_id: Option<u8>,
     pub max_archived_log_files: Option<usize>,
     pub max_log_files: Option<usize>,
+    pub max_storage: Option<u64>,
     pub metrics_port: Option<u16>,
     pub node_ip: Option<Ipv4Addr>,
     pub node_port: Option<u16>,
@@ -144,6 +145,10 @@ impl InstallNodeServiceCtxBuilder {
             args.push(OsString::from("--max-log-files"));
             args.push(OsString::from(log_files.to_string()));
         }
+        if let Some(max_storage) = self.max_storage {
+            args.push(OsString::from("--max-storage"));
+            args.push(OsString::from(max_storage.to_string()));
+        }
 
         args.push(OsString::from("--rewards-address"));
         args.push(OsString::from(self.rewards_address.to_string()));
@@ -189,6 +194,7 @@ pub struct AddNodeServiceOptions {
     pub log_format: Option<LogFormat>,
     pub max_archived_log_files: Option<usize>,
     pub max_log_files: Option<usize>,
+    pub max_storage: Option<u64>,
     pub metrics_port: Option<PortRange>,
     pub network_id: Option<u8>,
     pub node_ip: Option<Ipv4Addr>,
@@ -322,6 +328,7 @@ mod tests {
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             name: "test-node".to_string(),
             network_id: None,
@@ -358,6 +365,7 @@ mod tests {
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             name: "test-node".to_string(),
             network_id: None,
@@ -395,6 +403,7 @@ mod tests {
             log_format: None,
             max_archived_log_files: Some(10),
             max_log_files: Some(10),
+            max_storage: None,
             metrics_port: None,
             name: "test-node".to_string(),
             network_id: Some(5),
@@ -208,6 +208,7 @@ pub async fn add_node(
             log_format: options.log_format,
             max_archived_log_files: options.max_archived_log_files,
             max_log_files: options.max_log_files,
+            max_storage: options.max_storage,
             metrics_port: metrics_free_port,
             name: service_name.clone(),
             network_id: options.network_id,
@@ -246,6 +247,7 @@ pub async fn add_node(
                     log_format: options.log_format,
                     max_archived_log_files: options.max_archived_log_files,
                     max_log_files: options.max_log_files,
+                    max_storage: options.max_storage,
                     metrics_port: metrics_free_port,
                     network_id: options.network_id,
                     node_ip: options.node_ip,
@@ -137,6 +137,7 @@ async fn add_genesis_node_should_use_latest_version_and_add_one_service() -> Res
         log_format: None,
         max_archived_log_files: None,
         max_log_files: None,
+        max_storage: None,
         metrics_port: None,
         name: "antnode1".to_string(),
         network_id: None,
@@ -173,6 +174,7 @@ async fn add_genesis_node_should_use_latest_version_and_add_one_service() -> Res
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -295,6 +297,7 @@ async fn add_genesis_node_should_return_an_error_if_there_is_already_a_genesis_n
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -343,6 +346,7 @@ async fn add_genesis_node_should_return_an_error_if_there_is_already_a_genesis_n
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -433,6 +437,7 @@ async fn add_genesis_node_should_return_an_error_if_count_is_greater_than_1() ->
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -529,6 +534,7 @@ async fn add_node_should_use_latest_version_and_add_three_services() -> Result<(
         log_format: None,
         max_archived_log_files: None,
         max_log_files: None,
+        max_storage: None,
         metrics_port: None,
         network_id: None,
         name: "antnode1".to_string(),
@@ -578,6 +584,7 @@ async fn add_node_should_use_latest_version_and_add_three_services() -> Result<(
         log_format: None,
         max_archived_log_files: None,
         max_log_files: None,
+        max_storage: None,
         metrics_port: None,
         network_id: None,
         name: "antnode2".to_string(),
@@ -627,6 +634,7 @@ async fn add_node_should_use_latest_version_and_add_three_services() -> Result<(
         log_dir_path: node_logs_dir.to_path_buf().join("antnode3"),
         max_archived_log_files: None,
         max_log_files: None,
+        max_storage: None,
         metrics_port: None,
         network_id: None,
         name: "antnode3".to_string(),
@@ -664,6 +672,7 @@ async fn add_node_should_use_latest_version_and_add_three_services() -> Result<(
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -810,6 +819,7 @@ async fn add_node_should_update_the_environment_variables_inside_node_registry()
         log_format: None,
         max_archived_log_files: None,
         max_log_files: None,
+        max_storage: None,
         metrics_port: None,
         network_id: None,
         name: "antnode1".to_string(),
@@ -846,6 +856,7 @@ async fn add_node_should_update_the_environment_variables_inside_node_registry()
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -941,6 +952,7 @@ async fn add_new_node_should_add_another_service() -> Result<()> {
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -998,6 +1010,7 @@ async fn add_new_node_should_add_another_service() -> Result<()> {
         log_format: None,
         max_archived_log_files: None,
         max_log_files: None,
+        max_storage: None,
         metrics_port: None,
         network_id: None,
         name: "antnode2".to_string(),
@@ -1035,6 +1048,7 @@ async fn add_new_node_should_add_another_service() -> Result<()> {
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -1199,6 +1213,7 @@ async fn add_node_should_create_service_file_with_first_arg() -> Result<()> {
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -1356,6 +1371,7 @@ async fn add_node_should_create_service_file_with_peers_args() -> Result<()> {
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -1508,6 +1524,7 @@ async fn add_node_should_create_service_file_with_local_arg() -> Result<()> {
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -1664,6 +1681,7 @@ async fn add_node_should_create_service_file_with_network_contacts_url_arg() ->
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -1819,6 +1837,7 @@ async fn add_node_should_create_service_file_with_testnet_arg() -> Result<()> {
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -1971,6 +1990,7 @@ async fn add_node_should_create_service_file_with_ignore_cache_arg() -> Result<(
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -2124,6 +2144,7 @@ async fn add_node_should_create_service_file_with_custom_bootstrap_cache_path()
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -2270,6 +2291,7 @@ async fn add_node_should_create_service_file_with_network_id() -> Result<()> {
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: Some(5),
             node_ip: None,
@@ -2414,6 +2436,7 @@ async fn add_node_should_use_custom_ip() -> Result<()> {
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: Some(custom_ip),
@@ -2511,6 +2534,7 @@ async fn add_node_should_use_custom_ports_for_one_service() -> Result<()> {
         log_format: None,
         max_archived_log_files: None,
         max_log_files: None,
+        max_storage: None,
         metrics_port: None,
         network_id: None,
         name: "antnode1".to_string(),
@@ -2548,6 +2572,7 @@ async fn add_node_should_use_custom_ports_for_one_service() -> Result<()> {
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -2806,6 +2831,7 @@ async fn add_node_should_use_a_custom_port_range() -> Result<()> {
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -2881,6 +2907,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_port_is_used() -> R
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -2927,6 +2954,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_port_is_used() -> R
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -3000,6 +3028,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_port_in_range_is_us
             log_dir_path: PathBuf::from("/var/log/antnode/antnode1"),
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -3046,6 +3075,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_port_in_range_is_us
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -3125,6 +3155,7 @@ async fn add_node_should_return_an_error_if_port_and_node_count_do_not_match() -
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -3209,6 +3240,7 @@ async fn add_node_should_return_an_error_if_multiple_services_are_specified_with
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -3355,6 +3387,7 @@ async fn add_node_should_set_random_ports_if_enable_metrics_server_is_true() ->
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -3493,6 +3526,7 @@ async fn add_node_should_set_max_archived_log_files() -> Result<()> {
             log_format: None,
             max_archived_log_files: Some(20),
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -3632,6 +3666,7 @@ async fn add_node_should_set_max_log_files() -> Result<()> {
             log_format: None,
             max_archived_log_files: None,
             max_log_files: Some(20),
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -3672,6 +3707,146 @@ async fn add_node_should_set_max_log_files() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn add_node_should_set_max_storage() -> Result<()> {
+    let tmp_data_dir = assert_fs::TempDir::new()?;
+    let node_reg_path = tmp_data_dir.child("node_reg.json");
+
+    let mut mock_service_control = MockServiceControl::new();
+
+    let mut node_registry = NodeRegistry {
+        auditor: None,
+        faucet: None,
+        save_path: node_reg_path.to_path_buf(),
+        nat_status: None,
+        nodes: vec![],
+        environment_variables: None,
+        daemon: None,
+    };
+
+    let latest_version = "0.96.4";
+    let temp_dir = assert_fs::TempDir::new()?;
+    let node_data_dir = temp_dir.child("data");
+    node_data_dir.create_dir_all()?;
+    let node_logs_dir = temp_dir.child("logs");
+    node_logs_dir.create_dir_all()?;
+    let antnode_download_path = temp_dir.child(ANTNODE_FILE_NAME);
+    antnode_download_path.write_binary(b"fake antnode bin")?;
+
+    let mut seq = Sequence::new();
+
+    // Expected calls for first installation
+    mock_service_control
+        .expect_get_available_port()
+        .times(1)
+        .returning(|| Ok(8081))
+        .in_sequence(&mut seq);
+
+    mock_service_control
+        .expect_install()
+        .times(1)
+        .with(
+            eq(ServiceInstallCtx {
+                args: vec![
+                    OsString::from("--rpc"),
+                    OsString::from("127.0.0.1:8081"),
+                    OsString::from("--root-dir"),
+                    OsString::from(
+                        node_data_dir
+                            .to_path_buf()
+                            .join("antnode1")
+                            .to_string_lossy()
+                            .to_string(),
+                    ),
+                    OsString::from("--log-output-dest"),
+                    OsString::from(
+                        node_logs_dir
+                            .to_path_buf()
+                            .join("antnode1")
+                            .to_string_lossy()
+                            .to_string(),
+                    ),
+                    OsString::from("--max-storage"),
+                    OsString::from("64000000000"),
+                    OsString::from("--rewards-address"),
+                    OsString::from("0x03B770D9cD32077cC0bF330c13C114a87643B124"),
+                    OsString::from("evm-custom"),
+                    OsString::from("--rpc-url"),
+                    OsString::from("http://localhost:8545/"),
+                    OsString::from("--payment-token-address"),
+                    OsString::from("0x5FbDB2315678afecb367f032d93F642f64180aa3"),
+                    OsString::from("--data-payments-address"),
+                    OsString::from("0x8464135c8F25Da09e49BC8782676a84730C318bC"),
+                ],
+                autostart: false,
+                contents: None,
+                environment: None,
+                label: "antnode1".parse()?,
+                program: node_data_dir
+                    .to_path_buf()
+                    .join("antnode1")
+                    .join(ANTNODE_FILE_NAME),
+                username: Some(get_username()),
+                working_directory: None,
+            }),
+            eq(false),
+        )
+        .returning(|_, _| Ok(()))
+        .in_sequence(&mut seq);
+
+    add_node(
+        AddNodeServiceOptions {
+            auto_restart: false,
+            auto_set_nat_flags: false,
+            count: Some(1),
+            delete_antnode_src: false,
+            enable_metrics_server: false,
+            env_variables: None,
+            home_network: false,
+            log_format: None,
+            max_archived_log_files: None,
+            max_log_files: None,
+            max_storage: Some(64_000_000_000),
+            metrics_port: None,
+            network_id: None,
+            node_ip: None,
+            node_port: None,
+            owner: None,
+            peers_args: PeersArgs::default(),
+            rpc_address: None,
+            rpc_port: None,
+            antnode_dir_path: temp_dir.to_path_buf(),
+            antnode_src_path: antnode_download_path.to_path_buf(),
+            service_data_dir_path: node_data_dir.to_path_buf(),
+            service_log_dir_path: node_logs_dir.to_path_buf(),
+            upnp: false,
+            user: Some(get_username()),
+            user_mode: false,
+            version: latest_version.to_string(),
+            evm_network: EvmNetwork::Custom(CustomNetwork {
+                rpc_url_http: "http://localhost:8545".parse()?,
+                payment_token_address: RewardsAddress::from_str(
+                    "0x5FbDB2315678afecb367f032d93F642f64180aa3",
+                )?,
+                data_payments_address: RewardsAddress::from_str(
+                    "0x8464135c8F25Da09e49BC8782676a84730C318bC",
+                )?,
+            }),
+            rewards_address: RewardsAddress::from_str(
+                "0x03B770D9cD32077cC0bF330c13C114a87643B124",
+            )?,
+        },
+        &mut node_registry,
+        &mock_service_control,
+        VerbosityLevel::Normal,
+    )
+    .await?;
+
+    assert_matches!(node_registry.nodes[0].max_storage, Some(64_000_000_000));
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn add_node_should_use_a_custom_port_range_for_metrics_server() -> Result<()> {
     let tmp_data_dir = assert_fs::TempDir::new()?;
@@ -3885,6 +4060,7 @@ async fn add_node_should_use_a_custom_port_range_for_metrics_server() -> Result<
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: Some(PortRange::Range(12000, 12002)),
             network_id: None,
             node_ip: None,
@@ -3957,6 +4133,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_metrics_port_is_use
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: Some(12000),
             network_id: None,
             node_ip: None,
@@ -4003,6 +4180,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_metrics_port_is_use
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: Some(PortRange::Single(12000)),
             network_id: None,
             node_ip: None,
@@ -4077,6 +4255,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_metrics_port_in_ran
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: Some(12000),
             network_id: None,
             node_ip: None,
@@ -4123,6 +4302,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_metrics_port_in_ran
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: Some(PortRange::Range(12000, 12002)),
             network_id: None,
             node_ip: None,
@@ -4359,6 +4539,7 @@ async fn add_node_should_use_a_custom_port_range_for_the_rpc_server() -> Result<
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -4442,6 +4623,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_rpc_port_is_used()
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -4488,6 +4670,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_rpc_port_is_used()
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -4562,6 +4745,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_rpc_port_in_range_i
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -4608,6 +4792,7 @@ async fn add_node_should_return_an_error_if_duplicate_custom_rpc_port_in_range_i
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -4703,6 +4888,7 @@ async fn add_node_should_disable_upnp_and_home_network_if_nat_status_is_public()
         log_format: None,
         max_archived_log_files: None,
         max_log_files: None,
+        max_storage: None,
         metrics_port: None,
         network_id: None,
         name: "antnode1".to_string(),
@@ -4739,6 +4925,7 @@ async fn add_node_should_disable_upnp_and_home_network_if_nat_status_is_public()
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -4831,6 +5018,7 @@ async fn add_node_should_enable_upnp_if_nat_status_is_upnp() -> Result<()> {
         log_format: None,
         max_archived_log_files: None,
         max_log_files: None,
+        max_storage: None,
         metrics_port: None,
         network_id: None,
         name: "antnode1".to_string(),
@@ -4867,6 +5055,7 @@ async fn add_node_should_enable_upnp_if_nat_status_is_upnp() -> Result<()> {
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -4959,6 +5148,7 @@ async fn add_node_should_enable_home_network_if_nat_status_is_private() -> Resul
         log_format: None,
         max_archived_log_files: None,
         max_log_files: None,
+        max_storage: None,
         metrics_port: None,
         network_id: None,
         name: "antnode1".to_string(),
@@ -4995,6 +5185,7 @@ async fn add_node_should_enable_home_network_if_nat_status_is_private() -> Resul
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -5082,6 +5273,7 @@ async fn add_node_should_return_an_error_if_nat_status_is_none_but_auto_set_nat_
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -5703,6 +5895,7 @@ async fn add_node_should_not_delete_the_source_binary_if_path_arg_is_used() -> R
         log_format: None,
         max_archived_log_files: None,
         max_log_files: None,
+        max_storage: None,
         metrics_port: None,
         network_id: None,
         name: "antnode1".to_string(),
@@ -5740,6 +5933,7 @@ async fn add_node_should_not_delete_the_source_binary_if_path_arg_is_used() -> R
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -5833,6 +6027,7 @@ async fn add_node_should_apply_the_home_network_flag_if_it_is_used() -> Result<(
         log_format: None,
         max_archived_log_files: None,
         max_log_files: None,
+        max_storage: None,
         metrics_port: None,
         network_id: None,
         name: "antnode1".to_string(),
@@ -5870,6 +6065,7 @@ async fn add_node_should_apply_the_home_network_flag_if_it_is_used() -> Result<(
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -5963,6 +6159,7 @@ async fn add_node_should_add_the_node_in_user_mode() -> Result<()> {
         log_format: None,
         max_archived_log_files: None,
         max_log_files: None,
+        max_storage: None,
         metrics_port: None,
         network_id: None,
         name: "antnode1".to_string(),
@@ -6000,6 +6197,7 @@ async fn add_node_should_add_the_node_in_user_mode() -> Result<()> {
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -6090,6 +6288,7 @@ async fn add_node_should_add_the_node_with_upnp_enabled() -> Result<()> {
         log_format: None,
         max_archived_log_files: None,
         max_log_files: None,
+        max_storage: None,
         metrics_port: None,
         network_id: None,
         name: "antnode1".to_string(),
@@ -6127,6 +6326,7 @@ async fn add_node_should_add_the_node_with_upnp_enabled() -> Result<()> {
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -6264,6 +6464,7 @@ async fn add_node_should_assign_an_owner_in_lowercase() -> Result<()> {
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -6403,6 +6604,7 @@ async fn add_node_should_auto_restart() -> Result<()> {
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
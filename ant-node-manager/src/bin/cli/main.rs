@@ -159,6 +159,13 @@ pub enum SubCmd {
         /// After reaching this limit, the older archived files are deleted.
         #[clap(long, verbatim_doc_comment)]
         max_archived_log_files: Option<usize>,
+        /// Specify the disk space, in bytes, the records of each node may use.
+        ///
+        /// A node with more space accepts more records and quotes lower prices.
+        ///
+        /// If the argument is not used, nodes use up to 32GB.
+        #[clap(long, verbatim_doc_comment)]
+        max_storage: Option<u64>,
         /// Specify a port for the open metrics server.
         ///
         /// If you're passing the compiled antnode via --node-path, make sure to enable the open-metrics feature
@@ -1079,6 +1086,7 @@ async fn main() -> Result<()> {
             log_format,
             max_archived_log_files,
             max_log_files,
+            max_storage,
             metrics_port,
             network_id,
             node_ip,
@@ -1107,6 +1115,7 @@ async fn main() -> Result<()> {
                 log_format,
                 max_archived_log_files,
                 max_log_files,
+                max_storage,
                 metrics_port,
                 network_id,
                 node_ip,
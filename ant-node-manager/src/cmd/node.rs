@@ -48,6 +48,7 @@ pub async fn add(
     log_format: Option<LogFormat>,
     max_archived_log_files: Option<usize>,
     max_log_files: Option<usize>,
+    max_storage: Option<u64>,
     metrics_port: Option<PortRange>,
     network_id: Option<u8>,
     node_ip: Option<Ipv4Addr>,
@@ -125,6 +126,7 @@ pub async fn add(
         log_format,
         max_archived_log_files,
         max_log_files,
+        max_storage,
         metrics_port,
         network_id,
         node_ip,
@@ -594,6 +596,7 @@ pub async fn maintain_n_running_nodes(
     log_format: Option<LogFormat>,
     max_archived_log_files: Option<usize>,
     max_log_files: Option<usize>,
+    max_storage: Option<u64>,
     metrics_port: Option<PortRange>,
     network_id: Option<u8>,
     node_ip: Option<Ipv4Addr>,
@@ -699,6 +702,7 @@ pub async fn maintain_n_running_nodes(
                         log_format,
                         max_archived_log_files,
                         max_log_files,
+                        max_storage,
                         metrics_port.clone(),
                         network_id,
                         node_ip,
@@ -766,6 +766,7 @@ mod tests {
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -880,6 +881,7 @@ mod tests {
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -959,6 +961,7 @@ mod tests {
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -1078,6 +1081,7 @@ mod tests {
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -1170,6 +1174,7 @@ mod tests {
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -1272,6 +1277,7 @@ mod tests {
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -1373,6 +1379,7 @@ mod tests {
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -1444,6 +1451,7 @@ mod tests {
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -1507,6 +1515,7 @@ mod tests {
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -1568,6 +1577,7 @@ mod tests {
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -1632,6 +1642,7 @@ mod tests {
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -1707,6 +1718,7 @@ mod tests {
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -1847,6 +1859,7 @@ mod tests {
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -1948,6 +1961,7 @@ mod tests {
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -2094,6 +2108,7 @@ mod tests {
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -2252,6 +2267,7 @@ mod tests {
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -2405,6 +2421,7 @@ mod tests {
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -2559,6 +2576,7 @@ mod tests {
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -2738,6 +2756,7 @@ mod tests {
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -2910,6 +2929,7 @@ mod tests {
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
 network_id: None,
             node_ip: None,
@@ -3088,6 +3108,7 @@ network_id: None,
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: Some(5),
             node_ip: None,
@@ -3249,6 +3270,7 @@ network_id: None,
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -3419,6 +3441,7 @@ network_id: None,
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -3599,6 +3622,7 @@ network_id: None,
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -3774,6 +3798,7 @@ network_id: None,
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -3944,6 +3969,7 @@ network_id: None,
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -4124,6 +4150,7 @@ network_id: None,
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -4286,6 +4313,7 @@ network_id: None,
             log_format: Some(LogFormat::Json),
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -4451,6 +4479,7 @@ network_id: None,
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -4613,6 +4642,7 @@ network_id: None,
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             number: 1,
@@ -4778,6 +4808,7 @@ network_id: None,
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             number: 1,
@@ -4939,6 +4970,7 @@ network_id: None,
             log_format: None,
             max_archived_log_files: Some(20),
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -5104,6 +5136,7 @@ network_id: None,
             log_format: None,
             max_archived_log_files: None,
             max_log_files: Some(20),
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -5267,6 +5300,7 @@ network_id: None,
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: Some(12000),
             network_id: None,
             node_ip: None,
@@ -5432,6 +5466,7 @@ network_id: None,
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: Some(12000),
             network_id: None,
             node_ip: None,
@@ -5597,6 +5632,7 @@ network_id: None,
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -5762,6 +5798,7 @@ network_id: None,
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -5938,6 +5975,7 @@ network_id: None,
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -6115,6 +6153,7 @@ network_id: None,
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -6280,6 +6319,7 @@ network_id: None,
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -6365,6 +6405,7 @@ network_id: None,
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -6434,6 +6475,7 @@ network_id: None,
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -6518,6 +6560,7 @@ network_id: None,
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -6597,6 +6640,7 @@ network_id: None,
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
@@ -6674,6 +6718,7 @@ network_id: None,
             log_format: None,
             max_archived_log_files: None,
             max_log_files: None,
+            max_storage: None,
             metrics_port: None,
             network_id: None,
             node_ip: None,
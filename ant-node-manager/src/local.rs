@@ -434,6 +434,7 @@ pub async fn run_node(
         log_format: run_options.log_format,
         max_archived_log_files: None,
         max_log_files: None,
+        max_storage: None,
         metrics_port: run_options.metrics_port,
         network_id: None,
         node_ip: None,
@@ -72,6 +72,7 @@ pub async fn restart_node_service(
             log_format: current_node_clone.log_format,
             max_archived_log_files: current_node_clone.max_archived_log_files,
             max_log_files: current_node_clone.max_log_files,
+            max_storage: current_node_clone.max_storage,
             metrics_port: None,
             name: current_node_clone.service_name.clone(),
             network_id: current_node_clone.network_id,
@@ -189,6 +190,7 @@ pub async fn restart_node_service(
             name: new_service_name.clone(),
             max_archived_log_files: current_node_clone.max_archived_log_files,
             max_log_files: current_node_clone.max_log_files,
+            max_storage: current_node_clone.max_storage,
             metrics_port: None,
             network_id: current_node_clone.network_id,
             node_ip: current_node_clone.node_ip,
@@ -218,6 +220,7 @@ pub async fn restart_node_service(
             log_format: current_node_clone.log_format,
             max_archived_log_files: current_node_clone.max_archived_log_files,
             max_log_files: current_node_clone.max_log_files,
+            max_storage: current_node_clone.max_storage,
             metrics_port: None,
             network_id: current_node_clone.network_id,
             node_ip: current_node_clone.node_ip,
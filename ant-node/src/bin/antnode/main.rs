@@ -10,14 +10,16 @@
 extern crate tracing;
 
 mod rpc_service;
+mod store;
 mod subcommands;
 
-use crate::subcommands::EvmNetworkCommand;
+use crate::subcommands::SubCmd;
 use ant_bootstrap::{BootstrapCacheConfig, BootstrapCacheStore, PeersArgs};
 use ant_evm::{get_evm_network_from_env, EvmNetwork, RewardsAddress};
 #[cfg(feature = "local")]
 use ant_logging::metrics::init_metrics;
 use ant_logging::{Level, LogFormat, LogOutputDest, ReloadHandle};
+use ant_networking::{StorageBackend, TransportProtocol};
 use ant_node::{Marker, NodeBuilder, NodeEvent, NodeEventsReceiver};
 use ant_protocol::{
     node::get_antnode_root_dir,
@@ -144,8 +146,10 @@ struct Opt {
     /// The network can either be a pre-configured one or a custom network.
     /// When setting a custom network, you must specify the RPC URL to a fully synced node and
     /// the addresses of the network token and chunk payments contracts.
+    ///
+    /// The `store` subcommand inspects the record store of a stopped node instead of running one.
     #[command(subcommand)]
-    evm_network: Option<EvmNetworkCommand>,
+    command: Option<SubCmd>,
 
     /// Specify the node's data directory.
     ///
@@ -163,6 +167,45 @@ struct Opt {
     #[clap(long, default_value_t = 0)]
     port: u16,
 
+    /// Specify the transports to listen on, as a comma separated list.
+    ///
+    /// Valid values are "quic", "tcp" and "ws". QUIC and TCP listen on `--port`.
+    #[clap(
+        long,
+        value_delimiter = ',',
+        default_value = "quic",
+        verbatim_doc_comment
+    )]
+    transport: Vec<TransportProtocol>,
+
+    /// Specify the TCP port to listen on for WebSocket connections.
+    ///
+    /// If not set, `--port` is used, or a random port when the TCP transport is also enabled.
+    #[clap(long, verbatim_doc_comment)]
+    ws_port: Option<u16>,
+
+    /// Specify how the records are stored on disk.
+    ///
+    /// Valid values are "flat-file", one file per record, or "kv", an embedded key-value database that
+    /// writes atomically and starts faster with many records. Switching from "flat-file" to "kv" imports the
+    /// existing records.
+    #[clap(long, default_value_t = StorageBackend::FlatFile)]
+    storage_backend: StorageBackend,
+
+    /// Specify the disk space the records may use, in bytes.
+    ///
+    /// The node stops accepting records farther from it than the ones it holds once the budget is used, and its
+    /// quotes reflect the remaining capacity. Defaults to 32GB.
+    #[clap(long)]
+    max_storage: Option<u64>,
+
+    /// Specify the data directory of a previous node, to take over its records.
+    ///
+    /// The records are re-encrypted with the key of this node at startup, and the ones it is not responsible for
+    /// are handed off to their close peers. This is set when the node restarts with a new key.
+    #[clap(long, verbatim_doc_comment)]
+    migrate_records_from: Option<PathBuf>,
+
     /// Specify the IP to listen on.
     ///
     /// The special value `0.0.0.0` binds to all network interfaces available.
@@ -227,6 +270,10 @@ fn main() -> Result<()> {
         version::set_network_id(network_id);
     }
 
+    if let Some(SubCmd::Store(command)) = &opt.command {
+        return store::run(command);
+    }
+
     let identify_protocol_str = version::IDENTIFY_PROTOCOL_STR
         .read()
         .expect("Failed to obtain read lock for IDENTIFY_PROTOCOL_STR");
@@ -263,12 +310,10 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
-    let evm_network: EvmNetwork = opt
-        .evm_network
-        .as_ref()
-        .cloned()
-        .map(|v| Ok(v.into()))
-        .unwrap_or_else(get_evm_network_from_env)?;
+    let evm_network: EvmNetwork = match &opt.command {
+        Some(SubCmd::EvmNetwork(network)) => network.clone().into(),
+        _ => get_evm_network_from_env()?,
+    };
     println!("EVM network: {evm_network:?}");
 
     let node_socket_addr = SocketAddr::new(opt.ip, opt.port);
@@ -324,6 +369,11 @@ fn main() -> Result<()> {
         node_builder.initial_peers(initial_peres);
         node_builder.bootstrap_cache(bootstrap_cache);
         node_builder.is_behind_home_network(opt.home_network);
+        node_builder.transports(opt.transport);
+        node_builder.websocket_port(opt.ws_port);
+        node_builder.storage_backend(opt.storage_backend);
+        node_builder.max_storage(opt.max_storage);
+        node_builder.migrate_records_from(opt.migrate_records_from);
         #[cfg(feature = "open-metrics")]
         let mut node_builder = node_builder;
         // if enable flag is provided or only if the port is specified then enable the server by setting Some()
@@ -732,14 +782,26 @@ fn start_new_node_process(retain_peer_id: bool, root_dir: PathBuf, port: u16) {
     // Create a new Command instance to run the current executable
     let mut cmd = Command::new(current_exe);
 
-    // Set the arguments for the new Command
-    cmd.args(&args[1..]); // Exclude the first argument (binary path)
+    // Set the arguments for the new Command, excluding the first argument (binary path) and
+    // the records migrated by this process
+    let mut args = args.into_iter().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--migrate-records-from" {
+            let _ = args.next();
+        } else if !arg.starts_with("--migrate-records-from=") {
+            cmd.arg(arg);
+        }
+    }
 
     if retain_peer_id {
         cmd.arg("--root-dir");
         cmd.arg(format!("{root_dir:?}"));
         cmd.arg("--port");
         cmd.arg(port.to_string());
+    } else {
+        // the new node has a new key, its records are taken over from this one
+        cmd.arg("--migrate-records-from");
+        cmd.arg(root_dir);
     }
 
     warn!(
@@ -0,0 +1,345 @@
+// Copyright 2024 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Offline inspection and repair of the record store of a stopped node.
+
+use crate::subcommands::{StoreArgs, StoreCommand};
+use ant_networking::{record_type_of, OfflineRecordStore, StorageBackend};
+use ant_protocol::{
+    storage::{
+        try_deserialize_record, Chunk, GraphEntry, Pointer, RecordHeader, RecordKind, Scratchpad,
+    },
+    NetworkAddress,
+};
+use ant_registers::SignedRegister;
+use color_eyre::{
+    eyre::{bail, ensure, eyre, WrapErr},
+    Result,
+};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use libp2p::{
+    identity::Keypair,
+    kad::{Record, RecordKey},
+    PeerId,
+};
+use std::{fs, io::Read, path::Path};
+
+/// Folder of the root directory the corrupt records are moved to.
+const QUARANTINE_DIR: &str = "record_store_quarantine";
+
+pub(crate) fn run(command: &StoreCommand) -> Result<()> {
+    match command {
+        StoreCommand::List { store } => list(store),
+        StoreCommand::Verify { store, quarantine } => verify(store, *quarantine),
+        StoreCommand::Export { store, output } => export(store, output),
+        StoreCommand::Import { store, input } => import(store, input),
+    }
+}
+
+/// Open the record store of the node, along with its peer id.
+fn open(args: &StoreArgs) -> Result<(OfflineRecordStore, PeerId)> {
+    let secret_key_path = args
+        .secret_key
+        .clone()
+        .unwrap_or_else(|| args.root_dir.join("secret-key"));
+    let secret_key = fs::read(&secret_key_path)
+        .wrap_err_with(|| format!("Failed to read the secret key file {secret_key_path:?}"))?;
+    let keypair = Keypair::ed25519_from_bytes(secret_key)
+        .map_err(|err| eyre!("could not read ed25519 key from file: {err}"))?;
+    let peer_id = keypair.public().to_peer_id();
+
+    let storage_dir = args.root_dir.join("record_store");
+    ensure!(
+        storage_dir.is_dir(),
+        "No record store found in {:?}",
+        args.root_dir
+    );
+    // the backend the node was run with
+    let backend = StorageBackend::detect(&storage_dir);
+    let store = OfflineRecordStore::open(&storage_dir, &peer_id, backend)?;
+    Ok((store, peer_id))
+}
+
+fn list(args: &StoreArgs) -> Result<()> {
+    let (store, peer_id) = open(args)?;
+    let node_address = NetworkAddress::from_peer(peer_id);
+    let keys = store.keys()?;
+
+    println!("Records of node {peer_id}:");
+    let mut total_size = 0;
+    for key in &keys {
+        let Some(bytes) = store.read_encrypted(key)? else {
+            continue;
+        };
+        let size = bytes.len();
+        total_size += size;
+        let kind = match store.decrypt(key, bytes) {
+            Some(record) => match RecordHeader::from_record(&record) {
+                Ok(header) => format!("{:?}", header.kind),
+                Err(_) => "Unknown".to_string(),
+            },
+            None => "Undecryptable".to_string(),
+        };
+        let distance = node_address
+            .distance(&NetworkAddress::from_record_key(key))
+            .ilog2()
+            .map_or_else(|| "-".to_string(), |distance| distance.to_string());
+        println!(
+            "{} {kind} {size} bytes, distance {distance}",
+            hex::encode(key)
+        );
+    }
+    println!("{} records, {total_size} bytes", keys.len());
+    Ok(())
+}
+
+fn verify(args: &StoreArgs, quarantine: bool) -> Result<()> {
+    let (store, _) = open(args)?;
+    let keys = store.keys()?;
+
+    let mut corrupt = 0;
+    for key in &keys {
+        let Some(bytes) = store.read_encrypted(key)? else {
+            continue;
+        };
+        let result = store
+            .decrypt(key, bytes.clone())
+            .ok_or_else(|| eyre!("cannot be decrypted with the key of the node"))
+            .and_then(|record| verify_record(&record));
+        if let Err(err) = result {
+            corrupt += 1;
+            println!("{} is corrupt: {err}", hex::encode(key));
+            if quarantine {
+                move_to_quarantine(&store, &args.root_dir, key, &bytes)?;
+            }
+        }
+    }
+
+    println!("{} records verified, {corrupt} corrupt", keys.len());
+    if corrupt > 0 {
+        if !quarantine {
+            bail!(
+                "{corrupt} corrupt records found, use --quarantine to move them out of the store"
+            );
+        }
+        println!(
+            "Corrupt records moved to {:?}",
+            args.root_dir.join(QUARANTINE_DIR)
+        );
+    }
+    Ok(())
+}
+
+/// Move the encrypted value of a record out of the store, to keep it for investigation.
+fn move_to_quarantine(
+    store: &OfflineRecordStore,
+    root_dir: &Path,
+    key: &RecordKey,
+    bytes: &[u8],
+) -> Result<()> {
+    let quarantine_dir = root_dir.join(QUARANTINE_DIR);
+    fs::create_dir_all(&quarantine_dir)?;
+    fs::write(quarantine_dir.join(hex::encode(key)), bytes)?;
+    store.remove(key)?;
+    Ok(())
+}
+
+/// Check that a record is well formed, stored under its own address and correctly signed.
+fn verify_record(record: &Record) -> Result<RecordKind> {
+    let header = RecordHeader::from_record(record)?;
+    let address = match header.kind {
+        // the address of a chunk is computed from its content when deserialising
+        RecordKind::Chunk => try_deserialize_record::<Chunk>(record)?.network_address(),
+        RecordKind::GraphEntry => {
+            let entries = try_deserialize_record::<Vec<GraphEntry>>(record)?;
+            let first = entries
+                .first()
+                .ok_or_else(|| eyre!("no graph entry in the record"))?;
+            ensure!(
+                entries.iter().all(GraphEntry::verify),
+                "invalid graph entry signature"
+            );
+            ensure!(
+                entries
+                    .iter()
+                    .all(|entry| entry.address() == first.address()),
+                "graph entries of different owners"
+            );
+            NetworkAddress::from_graph_entry_address(first.address())
+        }
+        RecordKind::Register => {
+            let register = try_deserialize_record::<SignedRegister>(record)?;
+            register.verify()?;
+            NetworkAddress::from_register_address(*register.address())
+        }
+        RecordKind::Scratchpad => {
+            let scratchpad = try_deserialize_record::<Scratchpad>(record)?;
+            ensure!(scratchpad.is_valid(), "invalid scratchpad signature");
+            scratchpad.network_address()
+        }
+        RecordKind::Pointer => {
+            let pointer = try_deserialize_record::<Pointer>(record)?;
+            ensure!(pointer.verify(), "invalid pointer signature");
+            NetworkAddress::from_pointer_address(pointer.network_address())
+        }
+        kind => bail!("{kind:?} records are never stored"),
+    };
+    ensure!(
+        address.to_record_key() == record.key,
+        "stored under the wrong key, expected {}",
+        hex::encode(address.to_record_key())
+    );
+    Ok(header.kind)
+}
+
+fn export(args: &StoreArgs, output: &Path) -> Result<()> {
+    let (store, _) = open(args)?;
+    let file = fs::File::create(output)
+        .wrap_err_with(|| format!("Failed to create the tarball {output:?}"))?;
+    let mut tarball = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+
+    let mut exported = 0;
+    for key in store.keys()? {
+        let Some(bytes) = store.read_encrypted(&key)? else {
+            continue;
+        };
+        let Some(record) = store.decrypt(&key, bytes) else {
+            println!(
+                "Skipping {}: cannot be decrypted with the key of the node",
+                hex::encode(&key)
+            );
+            continue;
+        };
+        // the records are exported decrypted, as each node encrypts them with its own key
+        let mut header = tar::Header::new_gnu();
+        header.set_size(record.value.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        tarball.append_data(&mut header, hex::encode(&key), record.value.as_slice())?;
+        exported += 1;
+    }
+    tarball.into_inner()?.finish()?;
+
+    println!("{exported} records exported to {output:?}");
+    Ok(())
+}
+
+fn import(args: &StoreArgs, input: &Path) -> Result<()> {
+    let (store, _) = open(args)?;
+    let file =
+        fs::File::open(input).wrap_err_with(|| format!("Failed to open the tarball {input:?}"))?;
+    let mut tarball = tar::Archive::new(GzDecoder::new(file));
+
+    let mut imported = 0;
+    let mut skipped = 0;
+    for entry in tarball.entries()? {
+        let mut entry = entry?;
+        let name = entry.path()?.to_string_lossy().into_owned();
+        let key = hex::decode(&name)
+            .map(RecordKey::from)
+            .wrap_err_with(|| format!("Invalid record name {name:?} in the tarball"))?;
+        let mut value = vec![];
+        let _ = entry.read_to_end(&mut value)?;
+
+        let record = Record {
+            key,
+            value,
+            publisher: None,
+            expires: None,
+        };
+        if let Err(err) = verify_record(&record) {
+            println!("Skipping invalid record {name}: {err}");
+            skipped += 1;
+            continue;
+        }
+        // verified records always have a valid header
+        let Some(record_type) = record_type_of(&record) else {
+            continue;
+        };
+        store.put(record, &record_type)?;
+        imported += 1;
+    }
+
+    println!("{imported} records imported, {skipped} invalid records skipped");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ant_protocol::storage::try_serialize_record;
+    use assert_fs::TempDir;
+    use bytes::Bytes;
+    use libp2p::identity::ed25519;
+
+    /// Create the root directory of a node with the given records, returns its store arguments.
+    fn node_with_records(records: Vec<Record>) -> Result<(TempDir, StoreArgs)> {
+        let root_dir = TempDir::new()?;
+        let secret_key = ed25519::SecretKey::generate();
+        fs::write(root_dir.join("secret-key"), secret_key.as_ref())?;
+        let keypair: Keypair = ed25519::Keypair::from(secret_key).into();
+        // a node using the key-value store
+        let _ = StorageBackend::Kv.open(&root_dir.join("record_store"))?;
+
+        let args = StoreArgs {
+            root_dir: root_dir.to_path_buf(),
+            secret_key: None,
+        };
+        let (store, peer_id) = open(&args)?;
+        assert_eq!(peer_id, keypair.public().to_peer_id());
+        for record in records {
+            let record_type =
+                record_type_of(&record).ok_or_else(|| eyre!("invalid record header"))?;
+            store.put(record, &record_type)?;
+        }
+        Ok((root_dir, args))
+    }
+
+    fn chunk_record(content: &'static [u8]) -> Result<Record> {
+        let chunk = Chunk::new(Bytes::from_static(content));
+        Ok(Record {
+            key: chunk.network_address().to_record_key(),
+            value: try_serialize_record(&chunk, RecordKind::Chunk)?.to_vec(),
+            publisher: None,
+            expires: None,
+        })
+    }
+
+    #[test]
+    fn corrupt_records_are_quarantined_and_valid_ones_exported() -> Result<()> {
+        let valid = chunk_record(b"valid chunk")?;
+        // a chunk stored under another address
+        let mut corrupt = chunk_record(b"corrupt chunk")?;
+        corrupt.key = RecordKey::new(b"some other key");
+        let (root_dir, args) = node_with_records(vec![valid.clone(), corrupt.clone()])?;
+
+        assert!(verify(&args, false).is_err());
+        verify(&args, true)?;
+        assert!(root_dir
+            .join(QUARANTINE_DIR)
+            .join(hex::encode(&corrupt.key))
+            .is_file());
+        verify(&args, false)?;
+
+        let tarball = root_dir.join("records.tar.gz");
+        export(&args, &tarball)?;
+        let (_other_root_dir, other_args) = node_with_records(vec![])?;
+        import(&other_args, &tarball)?;
+
+        let (other_store, _) = open(&other_args)?;
+        assert_eq!(other_store.keys()?, vec![valid.key.clone()]);
+        let bytes = other_store
+            .read_encrypted(&valid.key)?
+            .ok_or_else(|| eyre!("the record should be imported"))?;
+        let imported = other_store
+            .decrypt(&valid.key, bytes)
+            .ok_or_else(|| eyre!("the record should be encrypted with the key of the node"))?;
+        assert_eq!(imported.value, valid.value);
+        Ok(())
+    }
+}
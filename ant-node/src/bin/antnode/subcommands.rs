@@ -1,5 +1,16 @@
 use ant_evm::EvmNetwork;
-use clap::Subcommand;
+use clap::{Args, Subcommand};
+use std::path::PathBuf;
+
+#[derive(Subcommand, Clone, Debug)]
+pub(crate) enum SubCmd {
+    #[command(flatten)]
+    EvmNetwork(EvmNetworkCommand),
+
+    /// Inspect and repair the record store of a stopped node
+    #[command(subcommand)]
+    Store(StoreCommand),
+}
 
 #[derive(Subcommand, Clone, Debug)]
 #[allow(clippy::enum_variant_names)]
@@ -40,3 +51,56 @@ impl Into<EvmNetwork> for EvmNetworkCommand {
         }
     }
 }
+
+#[derive(Subcommand, Clone, Debug)]
+pub(crate) enum StoreCommand {
+    /// List the records with their kind, size and distance from the node
+    List {
+        #[command(flatten)]
+        store: StoreArgs,
+    },
+
+    /// Verify the integrity of every record
+    Verify {
+        #[command(flatten)]
+        store: StoreArgs,
+
+        /// Move the corrupt records out of the store, into the `record_store_quarantine` folder of the
+        /// root directory
+        #[arg(long)]
+        quarantine: bool,
+    },
+
+    /// Export the decrypted records to a tarball, which can be imported by any node
+    Export {
+        #[command(flatten)]
+        store: StoreArgs,
+
+        /// The path of the tarball to create
+        #[arg(long)]
+        output: PathBuf,
+    },
+
+    /// Import the records of a tarball created by `export`, skipping the invalid ones
+    Import {
+        #[command(flatten)]
+        store: StoreArgs,
+
+        /// The path of the tarball to import
+        #[arg(long)]
+        input: PathBuf,
+    },
+}
+
+#[derive(Args, Clone, Debug)]
+pub(crate) struct StoreArgs {
+    /// The root directory of the node
+    #[arg(long)]
+    pub root_dir: PathBuf,
+
+    /// The secret key file of the node, used to decrypt its records
+    ///
+    /// Defaults to the `secret-key` file in the root directory.
+    #[arg(long)]
+    pub secret_key: Option<PathBuf>,
+}
@@ -44,6 +44,9 @@ pub enum Error {
     // Scratchpad is old version
     #[error("A newer version of this Scratchpad already exists")]
     IgnoringOutdatedScratchpadPut,
+    // Pointer is old version
+    #[error("A newer version of this Pointer already exists")]
+    IgnoringOutdatedPointerPut,
     // Scratchpad is invalid
     #[error("Scratchpad signature is invalid over the counter + content hash")]
     InvalidScratchpadSignature,
@@ -17,7 +17,8 @@ use ant_evm::RewardsAddress;
 #[cfg(feature = "open-metrics")]
 use ant_networking::MetricsRegistries;
 use ant_networking::{
-    time::sleep, Instant, Network, NetworkBuilder, NetworkEvent, NodeIssue, SwarmDriver,
+    time::sleep, Instant, Network, NetworkBuilder, NetworkEvent, NodeIssue, OfflineRecordStore,
+    StorageBackend, StorageBackendError, SwarmDriver, TransportProtocol,
 };
 use ant_protocol::{
     convert_distance_to_u256,
@@ -28,7 +29,7 @@ use ant_protocol::{
 };
 use bytes::Bytes;
 use itertools::Itertools;
-use libp2p::{identity::Keypair, Multiaddr, PeerId};
+use libp2p::{identity::Keypair, kad::RecordKey, Multiaddr, PeerId};
 use num_traits::cast::ToPrimitive;
 use rand::{
     rngs::{OsRng, StdRng},
@@ -37,7 +38,7 @@ use rand::{
 use std::{
     collections::HashMap,
     net::SocketAddr,
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::{
         atomic::{AtomicUsize, Ordering},
         Arc,
@@ -45,7 +46,7 @@ use std::{
     time::Duration,
 };
 use tokio::{
-    sync::mpsc::Receiver,
+    sync::{mpsc::Receiver, Mutex},
     task::{spawn, JoinSet},
 };
 
@@ -96,6 +97,11 @@ pub struct NodeBuilder {
     is_behind_home_network: bool,
     #[cfg(feature = "upnp")]
     upnp: bool,
+    transports: Vec<TransportProtocol>,
+    websocket_port: Option<u16>,
+    storage_backend: StorageBackend,
+    max_storage: Option<u64>,
+    migrate_records_from: Option<PathBuf>,
 }
 
 impl NodeBuilder {
@@ -124,6 +130,11 @@ impl NodeBuilder {
             is_behind_home_network: false,
             #[cfg(feature = "upnp")]
             upnp,
+            transports: vec![],
+            websocket_port: None,
+            storage_backend: StorageBackend::default(),
+            max_storage: None,
+            migrate_records_from: None,
         }
     }
 
@@ -148,6 +159,32 @@ impl NodeBuilder {
         self.is_behind_home_network = is_behind_home_network;
     }
 
+    /// Set the transports the node listens on. Defaults to QUIC only if not set.
+    pub fn transports(&mut self, transports: Vec<TransportProtocol>) {
+        self.transports = transports;
+    }
+
+    /// Set the TCP port used for WebSocket connections.
+    pub fn websocket_port(&mut self, port: Option<u16>) {
+        self.websocket_port = port;
+    }
+
+    /// Set the backend persisting the records. Defaults to one file per record.
+    pub fn storage_backend(&mut self, storage_backend: StorageBackend) {
+        self.storage_backend = storage_backend;
+    }
+
+    /// Set the disk budget of the records, in bytes. Defaults to 32GB if not set.
+    pub fn max_storage(&mut self, max_storage: Option<u64>) {
+        self.max_storage = max_storage;
+    }
+
+    /// Set the root directory of a previous node of this host, whose records are re-encrypted
+    /// with the key of this node at startup. Used when the node key is rotated.
+    pub fn migrate_records_from(&mut self, root_dir: Option<PathBuf>) {
+        self.migrate_records_from = root_dir;
+    }
+
     /// Asynchronously runs a new node instance, setting up the swarm driver,
     /// creating a data storage, and handling network events. Returns the
     /// created `RunningNode` which contains a `NodeEventsChannel` for listening
@@ -161,6 +198,22 @@ impl NodeBuilder {
     ///
     /// Returns an error if there is a problem initializing the `SwarmDriver`.
     pub fn build_and_run(self) -> Result<RunningNode> {
+        // The records have to be migrated before the record store is opened, to get indexed.
+        // A failed migration leaves the records in place, they are fetched from the network instead.
+        let migrated_records = match &self.migrate_records_from {
+            Some(old_root_dir) if *old_root_dir != self.root_dir => migrate_records(
+                old_root_dir,
+                &self.root_dir,
+                &self.identity_keypair.public().to_peer_id(),
+                self.storage_backend,
+            )
+            .unwrap_or_else(|err| {
+                error!("Failed to migrate the records of the previous node key: {err}");
+                vec![]
+            }),
+            _ => vec![],
+        };
+
         let mut network_builder = NetworkBuilder::new(self.identity_keypair, self.local);
 
         #[cfg(feature = "open-metrics")]
@@ -186,6 +239,16 @@ impl NodeBuilder {
 
         #[cfg(feature = "upnp")]
         network_builder.upnp(self.upnp);
+        if !self.transports.is_empty() {
+            network_builder.transports(self.transports);
+        }
+        if let Some(port) = self.websocket_port {
+            network_builder.websocket_port(port);
+        }
+        network_builder.storage_backend(self.storage_backend);
+        if let Some(max_storage) = self.max_storage {
+            network_builder.max_storage(max_storage);
+        }
 
         let (network, network_event_receiver, swarm_driver) =
             network_builder.build_node(self.root_dir.clone())?;
@@ -211,12 +274,38 @@ impl NodeBuilder {
         };
 
         // Run the node
-        node.run(swarm_driver, network_event_receiver);
+        node.run(swarm_driver, network_event_receiver, migrated_records);
 
         Ok(running_node)
     }
 }
 
+/// Move the records of the node previously run in `old_root_dir` into the store of this node,
+/// re-encrypting them with its key. Returns the keys of the migrated records.
+fn migrate_records(
+    old_root_dir: &Path,
+    root_dir: &Path,
+    peer_id: &PeerId,
+    storage_backend: StorageBackend,
+) -> std::result::Result<Vec<RecordKey>, StorageBackendError> {
+    let old_storage_dir = old_root_dir.join("record_store");
+    if !old_storage_dir.is_dir() {
+        info!("No records to migrate from {old_root_dir:?}");
+        return Ok(vec![]);
+    }
+
+    let old_secret_key = std::fs::read(old_root_dir.join("secret-key"))?;
+    let old_peer_id = Keypair::ed25519_from_bytes(old_secret_key)
+        .map_err(std::io::Error::other)?
+        .public()
+        .to_peer_id();
+    info!("Migrating the records of {old_peer_id:?} to {peer_id:?}");
+
+    let old_store = OfflineRecordStore::open(&old_storage_dir, &old_peer_id, storage_backend)?;
+    let store = OfflineRecordStore::open(&root_dir.join("record_store"), peer_id, storage_backend)?;
+    old_store.migrate_into(&store)
+}
+
 /// `Node` represents a single node in the distributed network. It handles
 /// network events, processes incoming requests, interacts with the data
 /// storage, and broadcasts node-related events.
@@ -271,7 +360,12 @@ impl Node {
     }
 
     /// Runs the provided `SwarmDriver` and spawns a task to process for `NetworkEvents`
-    fn run(self, swarm_driver: SwarmDriver, mut network_event_receiver: Receiver<NetworkEvent>) {
+    fn run(
+        self,
+        swarm_driver: SwarmDriver,
+        mut network_event_receiver: Receiver<NetworkEvent>,
+        migrated_records: Vec<RecordKey>,
+    ) {
         let mut rng = StdRng::from_entropy();
 
         let peers_connected = Arc::new(AtomicUsize::new(0));
@@ -324,6 +418,10 @@ impl Node {
                 tokio::time::interval(network_density_sampling_interval_time);
             let _ = network_density_sampling_interval.tick().await; // first tick completes immediately
 
+            // handed off on the replications, once the routing table has been populated,
+            // the records that can't be handed off yet being retried on the next ones
+            let migrated_records = Arc::new(Mutex::new(migrated_records));
+
             loop {
                 let peers_connected = &peers_connected;
 
@@ -352,7 +450,13 @@ impl Node {
                         let network = self.network().clone();
                         self.record_metrics(Marker::IntervalReplicationTriggered);
 
+                        let migrated_records = Arc::clone(&migrated_records);
                         let _handle = spawn(async move {
+                            let pending = std::mem::take(&mut *migrated_records.lock().await);
+                            if !pending.is_empty() {
+                                let remaining = Self::hand_off_migrated_records(network.clone(), pending).await;
+                                migrated_records.lock().await.extend(remaining);
+                            }
                             Self::try_interval_replication(network);
                             trace!("Periodic replication took {:?}", start.elapsed());
                         });
@@ -312,11 +312,21 @@ impl Node {
                 res
             }
             RecordKind::Pointer => {
-                // Pointers should always be paid for
-                error!("Pointer should not be validated at this point");
-                Err(Error::InvalidPutWithoutPayment(
-                    PrettyPrintRecordKey::from(&record.key).into_owned(),
-                ))
+                // make sure we already have this pointer locally, else reject it as first time upload needs payment
+                let key = record.key.clone();
+                let pointer = try_deserialize_record::<Pointer>(&record)?;
+                let net_addr = NetworkAddress::from_pointer_address(pointer.network_address());
+                let pretty_key = PrettyPrintRecordKey::from(&key);
+                trace!("Got record to store without payment for pointer at {pretty_key:?}");
+                if !self.validate_key_and_existence(&net_addr, &key).await? {
+                    warn!("Ignore store without payment for pointer at {pretty_key:?}");
+                    return Err(Error::InvalidPutWithoutPayment(
+                        PrettyPrintRecordKey::from(&record.key).into_owned(),
+                    ));
+                }
+
+                // store the updated pointer
+                self.validate_and_store_pointer_record(pointer, key).await
             }
             RecordKind::PointerWithPayment => {
                 let (payment, pointer) =
@@ -349,7 +359,7 @@ impl Node {
                     }
                 }
 
-                let res = self.validate_and_store_pointer_record(pointer, key);
+                let res = self.validate_and_store_pointer_record(pointer, key).await;
                 if res.is_ok() {
                     let content_hash = XorName::from_content(&record.value);
                     Marker::ValidPointerPutFromClient(&PrettyPrintRecordKey::from(&record.key))
@@ -432,7 +442,7 @@ impl Node {
             RecordKind::Pointer => {
                 let pointer = try_deserialize_record::<Pointer>(&record)?;
                 let key = record.key.clone();
-                self.validate_and_store_pointer_record(pointer, key)
+                self.validate_and_store_pointer_record(pointer, key).await
             }
         }
     }
@@ -850,7 +860,9 @@ impl Node {
     }
 
     /// Validate and store a pointer record
-    pub(crate) fn validate_and_store_pointer_record(
+    ///
+    /// A pointer already stored is only replaced by one with a higher counter.
+    pub(crate) async fn validate_and_store_pointer_record(
         &self,
         pointer: Pointer,
         key: RecordKey,
@@ -868,6 +880,21 @@ impl Node {
             return Err(Error::RecordKeyMismatch);
         }
 
+        // check that we don't have a newer version of the Pointer locally
+        if let Some(local_record) = self.network().get_local_record(&key).await? {
+            let local_pointer = try_deserialize_record::<Pointer>(&local_record)?;
+            if local_pointer == pointer {
+                debug!("Pointer {net_addr:?} is already stored, nothing to do");
+                return Ok(());
+            }
+            if local_pointer.count() >= pointer.count() {
+                warn!(
+                    "Rejecting Pointer PUT with counter less than or equal to the current counter"
+                );
+                return Err(Error::IgnoringOutdatedPointerPut);
+            }
+        }
+
         // Store the pointer
         let record = Record {
             key: key.clone(),
@@ -875,9 +902,10 @@ impl Node {
             publisher: None,
             expires: None,
         };
+        // Every version of the pointer lives at the same address, only its content tells them apart
+        let content_hash = XorName::from_content(&record.value);
         self.network().put_local_record(record);
 
-        let content_hash = XorName::from_content(&pointer.network_address().to_bytes());
         self.replicate_valid_fresh_record(key, RecordType::NonChunk(content_hash));
 
         Ok(())
@@ -11,12 +11,13 @@ use ant_networking::{GetRecordCfg, Network};
 use ant_protocol::{
     messages::{Cmd, Query, QueryResponse, Request, Response},
     storage::RecordType,
-    NetworkAddress, PrettyPrintRecordKey,
+    NetworkAddress, PrettyPrintRecordKey, CLOSE_GROUP_SIZE,
 };
 use libp2p::{
     kad::{Quorum, Record, RecordKey},
     PeerId,
 };
+use std::collections::HashMap;
 use tokio::task::spawn;
 
 impl Node {
@@ -25,6 +26,87 @@ impl Node {
         network.trigger_interval_replication()
     }
 
+    /// Notify the close peers of the migrated records this node is no longer responsible for,
+    /// so they fetch them from us instead of the records being lost on cleanup.
+    ///
+    /// Returns the records that could not be told in or out of our range yet, to be retried later.
+    pub(crate) async fn hand_off_migrated_records(
+        network: Network,
+        migrated_records: Vec<RecordKey>,
+    ) -> Vec<RecordKey> {
+        let stored_records = match network.get_all_local_record_addresses().await {
+            Ok(records) => records,
+            Err(err) => {
+                error!(
+                    "Handing off migrated records, get_all_local_record_addresses errored: {err:?}"
+                );
+                return migrated_records;
+            }
+        };
+
+        let our_address = NetworkAddress::from_peer(network.peer_id());
+        let mut keys_per_peer: HashMap<PeerId, Vec<(NetworkAddress, RecordType)>> = HashMap::new();
+        let mut remaining = vec![];
+        for key in migrated_records {
+            let data_addr = NetworkAddress::from_record_key(&key);
+            // the record may have been pruned in the meantime
+            let Some(record_type) = stored_records.get(&data_addr) else {
+                continue;
+            };
+            let replicate_candidates = match network
+                .get_replicate_candidates(data_addr.clone())
+                .await
+            {
+                Ok(peers) => peers,
+                Err(err) => {
+                    error!("Handing off migrated record {:?}, get_replicate_candidates errored: {err:?}", PrettyPrintRecordKey::from(&key));
+                    remaining.push(key);
+                    continue;
+                }
+            };
+            // not enough known peers to tell whether the record is still ours
+            if replicate_candidates.len() < CLOSE_GROUP_SIZE {
+                remaining.push(key);
+                continue;
+            }
+
+            // we are still responsible for the record if closer to it than one of the candidates
+            let our_distance = our_address.distance(&data_addr);
+            if replicate_candidates
+                .iter()
+                .any(|peer| NetworkAddress::from_peer(*peer).distance(&data_addr) > our_distance)
+            {
+                continue;
+            }
+
+            for peer_id in replicate_candidates {
+                keys_per_peer
+                    .entry(peer_id)
+                    .or_default()
+                    .push((data_addr.clone(), record_type.clone()));
+            }
+        }
+
+        info!(
+            "Handing off migrated records out of our range to {} peers",
+            keys_per_peer.len()
+        );
+        for (peer_id, keys) in keys_per_peer {
+            let request = Request::Cmd(Cmd::Replicate {
+                holder: our_address.clone(),
+                keys,
+            });
+            network.send_req_ignore_reply(request, peer_id);
+        }
+        if !remaining.is_empty() {
+            debug!(
+                "{} migrated records can't be handed off yet, retrying on the next replication",
+                remaining.len()
+            );
+        }
+        remaining
+    }
+
     /// Cleanup unrelevant records if accumulated too many.
     pub(crate) fn trigger_irrelevant_record_cleanup(network: Network) {
         network.trigger_irrelevant_record_cleanup()
@@ -0,0 +1,250 @@
+// Copyright 2024 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+//! Hierarchical derivation of the keys owning pointers, scratchpads and graph entries.
+//!
+//! Each of these objects lives at an address computed from its owner's public key, so one key can only own one
+//! of each. A [`MasterSecretKey`] derives as many owner keys as needed from a single secret, one per
+//! [`DerivationIndex`]. The derivation is deterministic and also works from the [`MasterPublicKey`], so the
+//! addresses of all the objects of a master key can be listed without knowing its secret.
+//!
+//! The derivation is not hardened: the master public key along with any single derived secret key reveals the
+//! master secret key, hence every other derived key. Derived secret keys must be kept as secret as the master key.
+
+use std::ops::Range;
+
+use bls::{PublicKey, SecretKey};
+use serde::{Deserialize, Serialize};
+use xor_name::XorName;
+
+use super::{GraphEntryAddress, PointerAddress, ScratchpadAddress};
+
+/// Pointers, scratchpads and graph entries owned by the same key share the same network name, the owner keys of
+/// each kind of object are derived under their own tag so that they never collide.
+const POINTER_TAG: &[u8] = b"pointer";
+const SCRATCHPAD_TAG: &[u8] = b"scratchpad";
+const GRAPH_ENTRY_TAG: &[u8] = b"graph_entry";
+
+/// Identifies one of the keys derived from a master key, either by name or by number.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct DerivationIndex([u8; 32]);
+
+impl DerivationIndex {
+    /// The index of the given name.
+    pub fn from_name(name: &str) -> Self {
+        Self(XorName::from_content_parts(&[b"name", name.as_bytes()]).0)
+    }
+
+    /// The index of the given number.
+    pub fn from_number(number: u64) -> Self {
+        Self(XorName::from_content_parts(&[b"number", &number.to_be_bytes()]).0)
+    }
+
+    /// The indexes of the given numbers, to list the objects of a master key in order.
+    pub fn range(numbers: Range<u64>) -> impl Iterator<Item = Self> {
+        numbers.map(Self::from_number)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl From<&str> for DerivationIndex {
+    fn from(name: &str) -> Self {
+        Self::from_name(name)
+    }
+}
+
+impl From<u64> for DerivationIndex {
+    fn from(number: u64) -> Self {
+        Self::from_number(number)
+    }
+}
+
+/// The secret key all the owner keys of an app or user are derived from.
+///
+/// # Security
+///
+/// The keys are derived without hardening, so that the [`MasterPublicKey`] can derive the owners. The flip side
+/// is that anyone knowing the master public key and one of the derived secret keys can compute the master secret
+/// key, and from it all the other derived keys. Never hand a derived secret key to someone who must not control
+/// all the objects of the master key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MasterSecretKey(SecretKey);
+
+impl MasterSecretKey {
+    pub fn new(key: SecretKey) -> Self {
+        Self(key)
+    }
+
+    pub fn random() -> Self {
+        Self(SecretKey::random())
+    }
+
+    pub fn public_key(&self) -> MasterPublicKey {
+        MasterPublicKey(self.0.public_key())
+    }
+
+    /// The key owning the pointer of the given index.
+    ///
+    /// Leaking it along with the master public key leaks the master secret key, see [`MasterSecretKey`].
+    pub fn pointer_key(&self, index: impl Into<DerivationIndex>) -> SecretKey {
+        self.derive(POINTER_TAG, &index.into())
+    }
+
+    /// The key owning the scratchpad of the given index.
+    ///
+    /// Leaking it along with the master public key leaks the master secret key, see [`MasterSecretKey`].
+    pub fn scratchpad_key(&self, index: impl Into<DerivationIndex>) -> SecretKey {
+        self.derive(SCRATCHPAD_TAG, &index.into())
+    }
+
+    /// The key owning the graph entry of the given index.
+    ///
+    /// Leaking it along with the master public key leaks the master secret key, see [`MasterSecretKey`].
+    pub fn graph_entry_key(&self, index: impl Into<DerivationIndex>) -> SecretKey {
+        self.derive(GRAPH_ENTRY_TAG, &index.into())
+    }
+
+    fn derive(&self, tag: &[u8], index: &DerivationIndex) -> SecretKey {
+        self.0.derive_child(tag).derive_child(index.as_bytes())
+    }
+}
+
+impl From<SecretKey> for MasterSecretKey {
+    fn from(key: SecretKey) -> Self {
+        Self(key)
+    }
+}
+
+/// The public key of a [`MasterSecretKey`], from which the addresses of its objects can be derived.
+///
+/// Sharing it lets others list the objects of the master key, but it also turns any leaked derived secret key
+/// into a leak of the master secret key, see [`MasterSecretKey`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct MasterPublicKey(PublicKey);
+
+impl MasterPublicKey {
+    pub fn new(key: PublicKey) -> Self {
+        Self(key)
+    }
+
+    pub fn public_key(&self) -> &PublicKey {
+        &self.0
+    }
+
+    /// The owner of the pointer of the given index.
+    pub fn pointer_owner(&self, index: impl Into<DerivationIndex>) -> PublicKey {
+        self.derive(POINTER_TAG, &index.into())
+    }
+
+    /// The owner of the scratchpad of the given index.
+    pub fn scratchpad_owner(&self, index: impl Into<DerivationIndex>) -> PublicKey {
+        self.derive(SCRATCHPAD_TAG, &index.into())
+    }
+
+    /// The owner of the graph entry of the given index.
+    pub fn graph_entry_owner(&self, index: impl Into<DerivationIndex>) -> PublicKey {
+        self.derive(GRAPH_ENTRY_TAG, &index.into())
+    }
+
+    pub fn pointer_address(&self, index: impl Into<DerivationIndex>) -> PointerAddress {
+        PointerAddress::from_owner(self.pointer_owner(index))
+    }
+
+    pub fn scratchpad_address(&self, index: impl Into<DerivationIndex>) -> ScratchpadAddress {
+        ScratchpadAddress::new(self.scratchpad_owner(index))
+    }
+
+    pub fn graph_entry_address(&self, index: impl Into<DerivationIndex>) -> GraphEntryAddress {
+        GraphEntryAddress::from_owner(self.graph_entry_owner(index))
+    }
+
+    fn derive(&self, tag: &[u8], index: &DerivationIndex) -> PublicKey {
+        self.0.derive_child(tag).derive_child(index.as_bytes())
+    }
+}
+
+impl From<PublicKey> for MasterPublicKey {
+    fn from(key: PublicKey) -> Self {
+        Self(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn public_derivation_matches_secret_derivation() {
+        let master = MasterSecretKey::random();
+        let public = master.public_key();
+
+        for index in DerivationIndex::range(0..5).chain([DerivationIndex::from_name("photos")]) {
+            assert_eq!(
+                PointerAddress::from_owner(master.pointer_key(index).public_key()),
+                public.pointer_address(index)
+            );
+            assert_eq!(
+                ScratchpadAddress::new(master.scratchpad_key(index).public_key()),
+                public.scratchpad_address(index)
+            );
+            assert_eq!(
+                GraphEntryAddress::from_owner(master.graph_entry_key(index).public_key()),
+                public.graph_entry_address(index)
+            );
+        }
+    }
+
+    #[test]
+    fn derived_keys_are_distinct() {
+        let master = MasterSecretKey::random();
+        let public = master.public_key();
+
+        let mut owners = HashSet::new();
+        let mut names = HashSet::new();
+        for index in DerivationIndex::range(0..100) {
+            // the keys of the different kinds of objects with the same index are all different
+            let keys = [
+                master.pointer_key(index),
+                master.scratchpad_key(index),
+                master.graph_entry_key(index),
+            ];
+            assert_ne!(keys[0], keys[1]);
+            assert_ne!(keys[0], keys[2]);
+            assert_ne!(keys[1], keys[2]);
+
+            for owner in [
+                public.pointer_owner(index),
+                public.scratchpad_owner(index),
+                public.graph_entry_owner(index),
+            ] {
+                assert!(owners.insert(owner));
+            }
+            // so the objects of different kinds never share a network name
+            for name in [
+                *public.pointer_address(index).xorname(),
+                public.scratchpad_address(index).xorname(),
+                *public.graph_entry_address(index).xorname(),
+            ] {
+                assert!(names.insert(name));
+            }
+        }
+        assert_eq!(owners.len(), 300);
+        assert_eq!(names.len(), 300);
+
+        let index = DerivationIndex::from_number(0);
+        assert_ne!(DerivationIndex::from_name("0"), index);
+        assert_ne!(
+            master.pointer_key(index),
+            MasterSecretKey::random().pointer_key(index)
+        );
+    }
+}
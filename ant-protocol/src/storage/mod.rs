@@ -8,6 +8,7 @@
 
 mod address;
 mod chunks;
+mod derivation;
 mod graph;
 mod header;
 pub mod pointer;
@@ -21,6 +22,7 @@ use std::{num::NonZeroUsize, time::Duration};
 pub use self::{
     address::{ChunkAddress, GraphEntryAddress, PointerAddress, ScratchpadAddress},
     chunks::Chunk,
+    derivation::{DerivationIndex, MasterPublicKey, MasterSecretKey},
     graph::GraphEntry,
     header::{try_deserialize_record, try_serialize_record, RecordHeader, RecordKind, RecordType},
     scratchpad::Scratchpad,
@@ -104,6 +104,16 @@ impl Pointer {
         self.target.xorname()
     }
 
+    /// Get the owner of the pointer
+    pub fn owner(&self) -> &PublicKey {
+        &self.owner
+    }
+
+    /// Get the target of the pointer
+    pub fn target(&self) -> &PointerTarget {
+        &self.target
+    }
+
     pub fn count(&self) -> u32 {
         self.counter
     }
@@ -109,6 +109,10 @@ impl ServiceStateActions for NodeService<'_> {
             args.push(OsString::from("--max-log-files"));
             args.push(OsString::from(max_log_files.to_string()));
         }
+        if let Some(max_storage) = self.service_data.max_storage {
+            args.push(OsString::from("--max-storage"));
+            args.push(OsString::from(max_storage.to_string()));
+        }
 
         if let Some(owner) = &self.service_data.owner {
             args.push(OsString::from("--owner"));
@@ -287,6 +291,8 @@ pub struct NodeServiceData {
     pub max_archived_log_files: Option<usize>,
     pub max_log_files: Option<usize>,
     #[serde(default)]
+    pub max_storage: Option<u64>,
+    #[serde(default)]
     pub metrics_port: Option<u16>,
     #[serde(default)]
     pub owner: Option<String>,
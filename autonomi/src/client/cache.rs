@@ -0,0 +1,359 @@
+// Copyright 2024 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::{File, FileTimes},
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::SystemTime,
+};
+
+use ant_protocol::storage::Chunk;
+use bytes::Bytes;
+use xor_name::XorName;
+
+/// Default size cap of the chunk cache: 1 GiB.
+pub const DEFAULT_CHUNK_CACHE_MAX_SIZE: u64 = 1024 * 1024 * 1024;
+
+/// Extension of the files chunks are written to before being moved in place.
+const TMP_EXTENSION: &str = "tmp";
+
+/// Configuration of the on-disk chunk cache, see [`crate::client::ClientConfig::chunk_cache`].
+#[derive(Debug, Clone)]
+pub struct ChunkCacheConfig {
+    /// Directory the cached chunks are stored in.
+    pub dir: PathBuf,
+    /// Maximum total size in bytes of the cached chunks.
+    /// The least recently used chunks are evicted once it is exceeded.
+    pub max_size: u64,
+}
+
+impl ChunkCacheConfig {
+    /// Cache chunks in `dir`, using the default size cap.
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            max_size: DEFAULT_CHUNK_CACHE_MAX_SIZE,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    size: u64,
+    last_used: u64,
+}
+
+#[derive(Debug, Default)]
+struct CacheIndex {
+    entries: HashMap<XorName, CacheEntry>,
+    // The entries by last use, the least recently used first.
+    lru: BTreeMap<u64, XorName>,
+    total_size: u64,
+    // Monotonic counter ordering the entries by last use.
+    clock: u64,
+}
+
+impl CacheIndex {
+    /// Add an entry, returns false if it was already there.
+    fn insert(&mut self, xor_name: XorName, size: u64) -> bool {
+        if self.entries.contains_key(&xor_name) {
+            return false;
+        }
+        self.clock += 1;
+        let _ = self.lru.insert(self.clock, xor_name);
+        let _ = self.entries.insert(
+            xor_name,
+            CacheEntry {
+                size,
+                last_used: self.clock,
+            },
+        );
+        self.total_size += size;
+        true
+    }
+
+    fn touch(&mut self, xor_name: &XorName) {
+        self.clock += 1;
+        if let Some(entry) = self.entries.get_mut(xor_name) {
+            let _ = self.lru.remove(&entry.last_used);
+            entry.last_used = self.clock;
+            let _ = self.lru.insert(self.clock, *xor_name);
+        }
+    }
+
+    fn remove(&mut self, xor_name: &XorName) {
+        if let Some(entry) = self.entries.remove(xor_name) {
+            let _ = self.lru.remove(&entry.last_used);
+            self.total_size -= entry.size;
+        }
+    }
+
+    /// Remove the least recently used entries until the total size fits in `max_size`, returns the removed entries.
+    fn evict(&mut self, max_size: u64) -> Vec<XorName> {
+        let mut evicted = vec![];
+        while self.total_size > max_size {
+            let Some((_, oldest)) = self.lru.pop_first() else {
+                break;
+            };
+            if let Some(entry) = self.entries.remove(&oldest) {
+                self.total_size -= entry.size;
+            }
+            evicted.push(oldest);
+        }
+        evicted
+    }
+}
+
+/// Content-addressed on-disk cache of chunks with LRU eviction.
+///
+/// Each chunk is stored in a file named after its hex encoded [`XorName`]. The last use of an entry is
+/// recorded as the modification time of its file, so the LRU order survives restarts of the client.
+/// Failures to read or write the cache are logged and never fail the operation using it.
+///
+/// The index of the cached chunks is kept in memory behind a lock that is never held while files are accessed.
+/// Files are read and written on the blocking thread pool, so concurrent gets neither wait on each other nor block
+/// the async runtime.
+#[derive(Debug)]
+pub(crate) struct ChunkCache {
+    config: ChunkCacheConfig,
+    index: Mutex<CacheIndex>,
+}
+
+impl ChunkCache {
+    /// Open the cache, indexing the chunks already stored in its directory.
+    pub(crate) async fn open(config: ChunkCacheConfig) -> std::io::Result<Self> {
+        blocking(move || Self::open_blocking(config)).await
+    }
+
+    fn open_blocking(config: ChunkCacheConfig) -> std::io::Result<Self> {
+        std::fs::create_dir_all(&config.dir)?;
+
+        let mut found = vec![];
+        for entry in std::fs::read_dir(&config.dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            // left behind by an interrupted write
+            if path.extension().is_some_and(|ext| ext == TMP_EXTENSION) {
+                let _ = std::fs::remove_file(&path);
+                continue;
+            }
+            let Some(xor_name) = entry
+                .file_name()
+                .to_str()
+                .and_then(|name| hex::decode(name).ok())
+                .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+                .map(XorName)
+            else {
+                continue;
+            };
+            let metadata = entry.metadata()?;
+            let last_used = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+            found.push((last_used, xor_name, metadata.len()));
+        }
+        found.sort();
+
+        let mut index = CacheIndex::default();
+        for (_, xor_name, size) in found {
+            let _ = index.insert(xor_name, size);
+        }
+        debug!(
+            "Opened chunk cache at {:?} with {} chunks ({} bytes)",
+            config.dir,
+            index.entries.len(),
+            index.total_size
+        );
+
+        // The size cap may have been lowered since the last use.
+        for xor_name in index.evict(config.max_size) {
+            remove_file(&chunk_path(&config.dir, &xor_name));
+        }
+
+        Ok(Self {
+            config,
+            index: Mutex::new(index),
+        })
+    }
+
+    /// Get a chunk from the cache. Corrupted entries are removed.
+    pub(crate) async fn get(&self, xor_name: &XorName) -> Option<Chunk> {
+        if !self.lock().entries.contains_key(xor_name) {
+            return None;
+        }
+
+        let path = self.chunk_path(xor_name);
+        let read = blocking(move || {
+            let bytes = std::fs::read(&path)?;
+            let touched = File::options()
+                .write(true)
+                .open(&path)
+                .and_then(|file| file.set_times(FileTimes::new().set_modified(SystemTime::now())));
+            if let Err(err) = touched {
+                debug!("Failed to update the last use of cached chunk {path:?}: {err}");
+            }
+            Ok(bytes)
+        })
+        .await;
+        let chunk = match read {
+            Ok(bytes) => Chunk::new(Bytes::from(bytes)),
+            Err(err) => {
+                warn!("Failed to read cached chunk {xor_name:?}: {err}");
+                self.remove(xor_name).await;
+                return None;
+            }
+        };
+        if chunk.name() != xor_name {
+            warn!("Cached chunk {xor_name:?} is corrupted, removing it");
+            self.remove(xor_name).await;
+            return None;
+        }
+
+        self.lock().touch(xor_name);
+        trace!("Chunk cache hit for {xor_name:?}");
+        Some(chunk)
+    }
+
+    /// Store a chunk in the cache, evicting the least recently used chunks if needed.
+    pub(crate) async fn put(&self, chunk: &Chunk) {
+        let xor_name = *chunk.name();
+        let size = chunk.value().len() as u64;
+        if size > self.config.max_size || self.lock().entries.contains_key(&xor_name) {
+            return;
+        }
+
+        // Write to a temporary file first, so that a crash never leaves a partial chunk behind. Its name is unique
+        // so that concurrent puts of the same chunk do not write to the same file.
+        let path = self.chunk_path(&xor_name);
+        let tmp_path =
+            path.with_extension(format!("{:016x}.{TMP_EXTENSION}", rand::random::<u64>()));
+        let value = chunk.value().clone();
+        let written = blocking(move || {
+            let written =
+                std::fs::write(&tmp_path, value).and_then(|()| std::fs::rename(&tmp_path, &path));
+            if written.is_err() {
+                let _ = std::fs::remove_file(&tmp_path);
+            }
+            written
+        })
+        .await;
+        if let Err(err) = written {
+            warn!("Failed to cache chunk {xor_name:?}: {err}");
+            return;
+        }
+
+        let evicted = {
+            let mut index = self.lock();
+            let _ = index.insert(xor_name, size);
+            index.evict(self.config.max_size)
+        };
+        if evicted.is_empty() {
+            return;
+        }
+        let dir = self.config.dir.clone();
+        let _ = blocking(move || {
+            for xor_name in evicted {
+                trace!("Evicting chunk {xor_name:?} from the chunk cache");
+                remove_file(&chunk_path(&dir, &xor_name));
+            }
+            Ok(())
+        })
+        .await;
+    }
+
+    async fn remove(&self, xor_name: &XorName) {
+        self.lock().remove(xor_name);
+        let path = self.chunk_path(xor_name);
+        let _ = blocking(move || {
+            remove_file(&path);
+            Ok(())
+        })
+        .await;
+    }
+
+    fn chunk_path(&self, xor_name: &XorName) -> PathBuf {
+        chunk_path(&self.config.dir, xor_name)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheIndex> {
+        self.index
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+fn chunk_path(dir: &Path, xor_name: &XorName) -> PathBuf {
+    dir.join(hex::encode(xor_name))
+}
+
+fn remove_file(path: &Path) {
+    if let Err(err) = std::fs::remove_file(path) {
+        if err.kind() != std::io::ErrorKind::NotFound {
+            warn!("Failed to remove cached chunk {path:?}: {err}");
+        }
+    }
+}
+
+/// Run file system operations on the blocking thread pool.
+async fn blocking<T: Send + 'static>(
+    f: impl FnOnce() -> std::io::Result<T> + Send + 'static,
+) -> std::io::Result<T> {
+    tokio::task::spawn_blocking(f)
+        .await
+        .unwrap_or_else(|err| Err(std::io::Error::other(err)))
+}
+
+#[cfg(all(test, feature = "fs"))]
+mod tests {
+    use super::*;
+
+    fn chunk(content: &'static [u8]) -> Chunk {
+        Chunk::new(Bytes::from_static(content))
+    }
+
+    #[tokio::test]
+    async fn chunk_cache_evicts_least_recently_used() -> std::io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let config = ChunkCacheConfig {
+            dir: dir.path().to_path_buf(),
+            max_size: 10,
+        };
+        let cache = ChunkCache::open(config.clone()).await?;
+
+        let (a, b, c) = (chunk(b"aaaa"), chunk(b"bbbb"), chunk(b"cccc"));
+        cache.put(&a).await;
+        cache.put(&b).await;
+        assert_eq!(cache.get(a.name()).await, Some(a.clone()));
+        cache.put(&c).await;
+
+        assert_eq!(cache.get(a.name()).await, Some(a.clone()));
+        assert_eq!(cache.get(b.name()).await, None);
+        assert_eq!(cache.get(c.name()).await, Some(c.clone()));
+
+        // the cache survives a restart
+        drop(cache);
+        let cache = ChunkCache::open(config).await?;
+        assert_eq!(cache.get(a.name()).await, Some(a));
+        assert_eq!(cache.get(c.name()).await, Some(c));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn chunk_cache_drops_corrupted_entries() -> std::io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let cache = ChunkCache::open(ChunkCacheConfig::new(dir.path().to_path_buf())).await?;
+
+        let a = chunk(b"aaaa");
+        cache.put(&a).await;
+        std::fs::write(dir.path().join(hex::encode(a.name())), b"tampered")?;
+
+        assert_eq!(cache.get(a.name()).await, None);
+        assert!(!dir.path().join(hex::encode(a.name())).exists());
+        Ok(())
+    }
+}
@@ -17,11 +17,18 @@ use bytes::Bytes;
 use serde::{Deserialize, Serialize};
 use xor_name::XorName;
 
-use crate::client::payment::PaymentOption;
+use crate::client::payment::{PaymentOption, PaymentTransactions, Receipt};
 use crate::client::{ClientEvent, UploadSummary};
-use crate::{self_encryption::encrypt, Client};
+use crate::self_encryption::{encrypt, encrypt_with_parity, ParityConfig};
+use crate::Client;
 
+pub mod prepared;
 pub mod public;
+pub mod range;
+pub mod session;
+#[cfg(feature = "fs")]
+#[cfg_attr(docsrs, doc(cfg(feature = "fs")))]
+pub mod stream;
 
 /// Number of chunks to upload in parallel.
 ///
@@ -86,6 +93,10 @@ pub enum PutError {
     PaymentUnexpectedlyInvalid(NetworkAddress),
     #[error("The payment proof contains no payees.")]
     PayeesMissing,
+    #[error("The quotes have expired, the upload has to be prepared again")]
+    QuotesExpired,
+    #[error("IO failure")]
+    IoError(#[from] std::io::Error),
 }
 
 /// Errors that can occur during the pay operation.
@@ -129,11 +140,13 @@ pub enum CostError {
     Serialization(String),
     #[error("Market price error: {0:?}")]
     MarketPriceError(#[from] ant_evm::payment_vault::error::Error),
+    #[error("Storing {0:?} costs {1}, above the cap of {2} per chunk")]
+    PriceAboveCap(XorName, Amount, Amount),
 }
 
 /// Private data on the network can be accessed with this
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
-pub struct DataMapChunk(Chunk);
+pub struct DataMapChunk(pub(crate) Chunk);
 
 impl DataMapChunk {
     pub fn to_hex(&self) -> String {
@@ -219,19 +232,83 @@ impl Client {
         let (data_map_chunk, chunks) = encrypt(data)?;
         debug!("Encryption took: {:.2?}", now.elapsed());
 
+        self.pay_and_upload_chunks(&chunks, payment_option).await?;
+        Ok(DataMapChunk(data_map_chunk))
+    }
+
+    /// Upload a piece of private data to the network along with Reed-Solomon parity chunks.
+    ///
+    /// Every group of `parity.data_shards` encrypted chunks gets `parity.parity_shards` extra parity chunks,
+    /// so that up to `parity.parity_shards` chunks of each group can be lost and still be rebuilt
+    /// when fetching the data, be it whole, streamed or by range. The parity chunks are paid for like any other chunk.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use autonomi::{Client, Bytes};
+    /// use autonomi::self_encryption::ParityConfig;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = Client::init().await?;
+    /// # let wallet = todo!();
+    /// let data = Bytes::from("Hello, World");
+    /// let data_map = client.data_put_with_parity(data, ParityConfig::default(), wallet).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn data_put_with_parity(
+        &self,
+        data: Bytes,
+        parity: ParityConfig,
+        payment_option: PaymentOption,
+    ) -> Result<DataMapChunk, PutError> {
+        let now = ant_networking::time::Instant::now();
+        let (data_map_chunk, chunks) = encrypt_with_parity(data, parity)?;
+        debug!("Encryption with parity took: {:.2?}", now.elapsed());
+
+        self.pay_and_upload_chunks(&chunks, payment_option).await?;
+        Ok(DataMapChunk(data_map_chunk))
+    }
+
+    // Pay for and upload the given chunks, reporting the upload to the client event channel.
+    async fn pay_and_upload_chunks(
+        &self,
+        chunks: &[Chunk],
+        payment_option: PaymentOption,
+    ) -> Result<(), PutError> {
+        let started = ant_networking::time::Instant::now();
+
         // Pay for all chunks
         let xor_names: Vec<_> = chunks.iter().map(|chunk| *chunk.name()).collect();
         info!("Paying for {} addresses", xor_names.len());
-        let receipt = self
+        let (receipt, transactions) = self
             .pay_for_content_addrs(xor_names.into_iter(), payment_option)
             .await
             .inspect_err(|err| error!("Error paying for data: {err:?}"))?;
 
+        self.upload_paid_chunks(chunks.iter().collect(), &receipt, transactions, started)
+            .await
+    }
+
+    // Upload chunks paid for by the receipt, reporting the upload to the client event channel.
+    pub(crate) async fn upload_paid_chunks(
+        &self,
+        chunks: Vec<&Chunk>,
+        receipt: &Receipt,
+        transactions: PaymentTransactions,
+        started: ant_networking::time::Instant,
+    ) -> Result<(), PutError> {
+        let mut summary = UploadSummary {
+            record_count: chunks.len(),
+            ..Default::default()
+        };
+        summary.add_payments(receipt, transactions);
+
         // Upload the chunks with the payments
         debug!("Uploading {} chunks", chunks.len());
 
         let mut failed_uploads = self
-            .upload_chunks_with_retries(chunks.iter().collect(), &receipt)
+            .upload_chunks_with_retries(chunks, receipt, &mut summary)
             .await;
 
         // Return the last chunk upload error
@@ -244,25 +321,12 @@ impl Client {
             return Err(last_chunk_fail.1);
         }
 
-        let record_count = chunks.len();
-
         // Reporting
-        if let Some(channel) = self.client_event_sender.as_ref() {
-            let tokens_spent = receipt
-                .values()
-                .map(|(_, cost)| cost.as_atto())
-                .sum::<Amount>();
-
-            let summary = UploadSummary {
-                record_count,
-                tokens_spent,
-            };
-            if let Err(err) = channel.send(ClientEvent::UploadComplete(summary)).await {
-                error!("Failed to send client event: {err:?}");
-            }
-        }
+        summary.elapsed = started.elapsed();
+        self.send_client_event(ClientEvent::UploadComplete(summary))
+            .await;
 
-        Ok(DataMapChunk(data_map_chunk))
+        Ok(())
     }
 }
 
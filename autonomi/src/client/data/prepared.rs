@@ -0,0 +1,168 @@
+// Copyright 2024 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use ant_evm::{AttoTokens, EvmWallet};
+use ant_protocol::storage::Chunk;
+use bytes::Bytes;
+
+use super::{CostError, DataAddr, DataMapChunk, PutError};
+use crate::client::quote::StoreQuote;
+use crate::{self_encryption::encrypt, Client};
+
+/// An upload whose cost is known before paying for it.
+///
+/// Created with [`Client::prepare_upload`] or [`Client::prepare_upload_public`]. The quotes are paid and the
+/// chunks uploaded with [`PreparedUpload::commit`], or nothing is spent with [`PreparedUpload::abort`].
+/// Nodes refuse payments for quotes older than an hour, so an upload must be committed before then.
+pub struct PreparedUpload {
+    client: Client,
+    data_map_chunk: Chunk,
+    /// The chunks to upload, including the data map chunk for public data.
+    chunks: Vec<Chunk>,
+    store_quote: StoreQuote,
+}
+
+impl Client {
+    /// Encrypt a piece of private data and get the quotes for storing it, without paying anything.
+    ///
+    /// Like [`Client::data_put`], the [`DataMapChunk`] is not uploaded.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use autonomi::{Client, Bytes};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = Client::init().await?;
+    /// # let wallet = todo!();
+    /// # let budget = todo!();
+    /// let upload = client.prepare_upload(Bytes::from("Hello, World")).await?;
+    /// if upload.total_cost() > budget {
+    ///     upload.abort();
+    /// } else {
+    ///     let data_map = upload.commit(&wallet).await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn prepare_upload(&self, data: Bytes) -> Result<PreparedUpload, CostError> {
+        let (data_map_chunk, chunks) = encrypt(data)?;
+        self.prepare_chunks_upload(data_map_chunk, chunks).await
+    }
+
+    /// Encrypt a piece of public data and get the quotes for storing it along with its data map, without
+    /// paying anything.
+    ///
+    /// Like [`Client::data_put_public`], the data is available at [`PreparedUpload::data_address`] once committed.
+    pub async fn prepare_upload_public(&self, data: Bytes) -> Result<PreparedUpload, CostError> {
+        let (data_map_chunk, mut chunks) = encrypt(data)?;
+        chunks.push(data_map_chunk.clone());
+        self.prepare_chunks_upload(data_map_chunk, chunks).await
+    }
+
+    async fn prepare_chunks_upload(
+        &self,
+        data_map_chunk: Chunk,
+        chunks: Vec<Chunk>,
+    ) -> Result<PreparedUpload, CostError> {
+        info!("Preparing the upload of {} chunks", chunks.len());
+        let store_quote = self
+            .get_store_quotes(chunks.iter().map(|chunk| *chunk.name()))
+            .await
+            .inspect_err(|err| error!("Error getting store quotes: {err:?}"))?;
+
+        Ok(PreparedUpload {
+            client: self.clone(),
+            data_map_chunk,
+            chunks,
+            store_quote,
+        })
+    }
+}
+
+impl PreparedUpload {
+    /// The quotes that will be paid.
+    pub fn store_quote(&self) -> &StoreQuote {
+        &self.store_quote
+    }
+
+    /// The total amount that will be paid.
+    pub fn total_cost(&self) -> AttoTokens {
+        AttoTokens::from_atto(self.store_quote.price())
+    }
+
+    /// The number of chunks to upload.
+    pub fn chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// The number of chunks to pay for, the others being already stored on the network.
+    pub fn paid_chunk_count(&self) -> usize {
+        self.store_quote.len()
+    }
+
+    /// The map to the private data.
+    pub fn data_map(&self) -> DataMapChunk {
+        DataMapChunk(self.data_map_chunk.clone())
+    }
+
+    /// The address of the data map, where public data can be fetched from.
+    pub fn data_address(&self) -> DataAddr {
+        *self.data_map_chunk.name()
+    }
+
+    /// Pay the quotes and upload the chunks.
+    ///
+    /// Exactly the quoted amount is paid. Fails with [`PutError::QuotesExpired`] without paying anything when
+    /// the quotes are too old to be accepted by the nodes.
+    pub async fn commit(self, wallet: &EvmWallet) -> Result<DataMapChunk, PutError> {
+        if self.has_expired() {
+            return Err(PutError::QuotesExpired);
+        }
+
+        let started = ant_networking::time::Instant::now();
+        info!(
+            "Paying {} for {} chunks",
+            self.total_cost(),
+            self.paid_chunk_count()
+        );
+        let (receipt, transactions) = self
+            .client
+            .pay_for_store_quotes(self.store_quote, wallet)
+            .await
+            .inspect_err(|err| error!("Error paying for data: {err:?}"))?;
+        self.client
+            .upload_paid_chunks(
+                self.chunks.iter().collect(),
+                &receipt,
+                transactions,
+                started,
+            )
+            .await?;
+
+        Ok(DataMapChunk(self.data_map_chunk))
+    }
+
+    /// Whether any of the quotes is too old to be accepted by the nodes.
+    pub(crate) fn has_expired(&self) -> bool {
+        self.store_quote
+            .0
+            .values()
+            .flat_map(|quote| quote.0.iter())
+            .any(|(_, quote, _)| quote.has_expired())
+    }
+
+    /// Drop the upload without paying anything.
+    pub fn abort(self) {
+        info!(
+            "Aborted the upload of {} chunks, quoted at {}",
+            self.chunk_count(),
+            self.total_cost()
+        );
+    }
+}
@@ -12,7 +12,7 @@ use std::collections::HashSet;
 
 use crate::client::payment::{PaymentOption, Receipt};
 use crate::client::utils::process_tasks_with_max_concurrency;
-use crate::client::{ClientEvent, UploadSummary};
+use crate::client::UploadSummary;
 use crate::{self_encryption::encrypt, Client};
 use ant_evm::{Amount, AttoTokens};
 use ant_networking::{GetRecordCfg, NetworkError};
@@ -59,59 +59,37 @@ impl Client {
 
         // Pay for all chunks + data map chunk
         info!("Paying for {} addresses", xor_names.len());
-        let receipt = self
+        let (receipt, transactions) = self
             .pay_for_content_addrs(xor_names.into_iter(), payment_option)
             .await
             .inspect_err(|err| error!("Error paying for data: {err:?}"))?;
 
         // Upload all the chunks in parallel including the data map chunk
-        debug!("Uploading {} chunks", chunks.len());
-
-        let mut failed_uploads = self
-            .upload_chunks_with_retries(
-                chunks
-                    .iter()
-                    .chain(std::iter::once(&data_map_chunk))
-                    .collect(),
-                &receipt,
-            )
-            .await;
-
-        // Return the last chunk upload error
-        if let Some(last_chunk_fail) = failed_uploads.pop() {
-            tracing::error!(
-                "Error uploading chunk ({:?}): {:?}",
-                last_chunk_fail.0.address(),
-                last_chunk_fail.1
-            );
-            return Err(last_chunk_fail.1);
-        }
-
-        let record_count = chunks.len() + 1;
-
-        // Reporting
-        if let Some(channel) = self.client_event_sender.as_ref() {
-            let tokens_spent = receipt
-                .values()
-                .map(|(_proof, price)| price.as_atto())
-                .sum::<Amount>();
-
-            let summary = UploadSummary {
-                record_count,
-                tokens_spent,
-            };
-            if let Err(err) = channel.send(ClientEvent::UploadComplete(summary)).await {
-                error!("Failed to send client event: {err:?}");
-            }
-        }
+        self.upload_paid_chunks(
+            chunks
+                .iter()
+                .chain(std::iter::once(&data_map_chunk))
+                .collect(),
+            &receipt,
+            transactions,
+            now,
+        )
+        .await?;
 
         Ok(map_xor_name)
     }
 
-    /// Get a raw chunk from the network.
+    /// Get a raw chunk from the network, or from the chunk cache when one is configured.
     pub async fn chunk_get(&self, addr: ChunkAddr) -> Result<Chunk, GetError> {
         info!("Getting chunk: {addr:?}");
 
+        if let Some(cache) = self.chunk_cache.as_ref() {
+            if let Some(chunk) = cache.get(&addr).await {
+                debug!("Chunk {addr:?} found in the chunk cache");
+                return Ok(chunk);
+            }
+        }
+
         let key = NetworkAddress::from_chunk_address(ChunkAddress::new(addr)).to_record_key();
         debug!("Fetching chunk from network at: {key:?}");
         let get_cfg = GetRecordCfg {
@@ -131,6 +109,9 @@ impl Client {
 
         if let RecordKind::Chunk = header.kind {
             let chunk: Chunk = try_deserialize_record(&record)?;
+            if let Some(cache) = self.chunk_cache.as_ref() {
+                cache.put(&chunk).await;
+            }
             Ok(chunk)
         } else {
             error!(
@@ -177,10 +158,12 @@ impl Client {
     }
 
     // Upload chunks and retry failed uploads up to `RETRY_ATTEMPTS` times.
+    // The retries and the size of the uploaded chunks are added to the summary.
     pub(crate) async fn upload_chunks_with_retries<'a>(
         &self,
         mut chunks: Vec<&'a Chunk>,
         receipt: &Receipt,
+        summary: &mut UploadSummary,
     ) -> Vec<(&'a Chunk, PutError)> {
         let mut current_attempt: usize = 1;
 
@@ -200,6 +183,7 @@ impl Client {
                         .chunk_upload_with_payment(chunk, proof.clone())
                         .await
                         .inspect_err(|err| error!("Error uploading chunk {address:?} :{err:?}"))
+                        .map(|()| chunk)
                         // Return chunk reference too, to re-use it next attempt/iteration
                         .map_err(|err| (chunk, err))
                 });
@@ -209,7 +193,13 @@ impl Client {
 
             // Check for errors.
             let total_uploads = uploads.len();
-            let uploads_failed: Vec<_> = uploads.into_iter().filter_map(|up| up.err()).collect();
+            let mut uploads_failed = vec![];
+            for upload in uploads {
+                match upload {
+                    Ok(chunk) => summary.bytes_uploaded += chunk.value().len() as u64,
+                    Err(failed) => uploads_failed.push(failed),
+                }
+            }
             info!(
                 "Uploaded {} chunks out of {total_uploads}",
                 total_uploads - uploads_failed.len()
@@ -229,6 +219,7 @@ impl Client {
                 "Retrying putting {} failed chunks (attempt {current_attempt}/3)",
                 uploads_failed.len()
             );
+            summary.retries += uploads_failed.len();
 
             // Re-iterate over the failed chunks
             chunks = uploads_failed.into_iter().map(|(chunk, _)| chunk).collect();
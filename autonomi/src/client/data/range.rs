@@ -0,0 +1,186 @@
+// Copyright 2024 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use bytes::Bytes;
+use self_encryption::{decrypt_range, ChunkInfo, DataMap};
+
+use super::{DataAddr, DataMapChunk, GetError};
+use crate::self_encryption::ParityMap;
+use crate::Client;
+
+impl Client {
+    /// Fetch `len` bytes of (private) data starting at `offset`.
+    ///
+    /// Only the chunks covering the requested range are fetched from the network, the keys needed to
+    /// decrypt them being derived from the hashes of their neighbours recorded in the data map.
+    /// Reading past the end of the data returns fewer bytes, or none at all.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use autonomi::Client;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = Client::init().await?;
+    /// # let data_map = todo!();
+    /// // Read 1 MiB starting at the 10 MiB mark
+    /// let bytes = client.data_get_range(data_map, 10 * 1024 * 1024, 1024 * 1024).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn data_get_range(
+        &self,
+        data_map: DataMapChunk,
+        offset: u64,
+        len: usize,
+    ) -> Result<Bytes, GetError> {
+        info!(
+            "Fetching range {offset}+{len} of private data from Data Map {:?}",
+            data_map.0.address()
+        );
+        let (data_map, parity_map) = self.restore_data_map_from_chunk(data_map.0.value()).await?;
+        self.fetch_range_from_data_map(&data_map, parity_map.as_ref(), offset, len)
+            .await
+    }
+
+    /// Fetch `len` bytes of public data starting at `offset`. See [`Client::data_get_range`].
+    pub async fn data_get_range_public(
+        &self,
+        addr: DataAddr,
+        offset: u64,
+        len: usize,
+    ) -> Result<Bytes, GetError> {
+        info!("Fetching range {offset}+{len} of public data from address: {addr:?}");
+        let data_map_chunk = self.chunk_get(addr).await?;
+        let (data_map, parity_map) = self
+            .restore_data_map_from_chunk(&data_map_chunk.value)
+            .await?;
+        self.fetch_range_from_data_map(&data_map, parity_map.as_ref(), offset, len)
+            .await
+    }
+
+    /// Fetch and decrypt only the chunks of the data map covering the given range.
+    ///
+    /// With the parity of the data, chunks that cannot be fetched are rebuilt from the parity chunks.
+    pub(crate) async fn fetch_range_from_data_map(
+        &self,
+        data_map: &DataMap,
+        parity_map: Option<&ParityMap>,
+        offset: u64,
+        len: usize,
+    ) -> Result<Bytes, GetError> {
+        let Some((infos, relative_pos)) = chunks_covering_range(&data_map.infos(), offset, len)
+        else {
+            debug!("Range {offset}+{len} is empty or beyond the end of the data");
+            return Ok(Bytes::new());
+        };
+
+        debug!(
+            "Fetching {} of {} chunks for range {offset}+{len}",
+            infos.len(),
+            data_map.infos().len()
+        );
+        let encrypted_chunks = self.fetch_encrypted_chunks(&infos, parity_map).await?;
+
+        decrypt_range(data_map, &encrypted_chunks, relative_pos, len).map_err(|err| {
+            error!("Error decrypting encrypted_chunks: {err:?}");
+            GetError::Decryption(crate::self_encryption::Error::SelfEncryption(err))
+        })
+    }
+}
+
+/// Select the chunks covering `len` bytes from `offset`, along with the position of `offset` within the first of them.
+///
+/// Returns `None` when the range is empty or starts beyond the end of the data.
+fn chunks_covering_range(
+    infos: &[ChunkInfo],
+    offset: u64,
+    len: usize,
+) -> Option<(Vec<ChunkInfo>, usize)> {
+    if len == 0 {
+        return None;
+    }
+    let end = offset.saturating_add(len as u64);
+
+    let mut sorted = infos.to_vec();
+    sorted.sort_by_key(|info| info.index);
+
+    let mut covering = vec![];
+    let mut relative_pos = 0;
+    let mut chunk_start = 0u64;
+    for info in sorted {
+        let chunk_end = chunk_start + info.src_size as u64;
+        if chunk_end > offset && chunk_start < end {
+            if covering.is_empty() {
+                relative_pos = (offset - chunk_start) as usize;
+            }
+            covering.push(info);
+        }
+        chunk_start = chunk_end;
+    }
+
+    if covering.is_empty() {
+        None
+    } else {
+        Some((covering, relative_pos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::self_encryption::encrypt;
+    use self_encryption::{DataMap, EncryptedChunk};
+
+    #[test]
+    fn range_decrypts_from_covering_chunks_only() -> Result<(), Box<dyn std::error::Error>> {
+        let data: Vec<u8> = (0..5 * 1024 * 1024 + 123)
+            .map(|i| (i % 251) as u8)
+            .collect();
+        let data = Bytes::from(data);
+        let (data_map_chunk, chunks) = encrypt(data.clone())?;
+        let data_map: DataMap = match rmp_serde::from_slice(data_map_chunk.value())? {
+            crate::self_encryption::DataMapLevel::First(data_map) => data_map,
+            _ => return Err("expected a first level data map".into()),
+        };
+
+        for (offset, len) in [
+            (0, 10),
+            (1024 * 1024 - 5, 10),
+            (3 * 1024 * 1024 + 7, 1024 * 1024),
+            (data.len() as u64 - 3, 100),
+        ] {
+            let (infos, relative_pos) = chunks_covering_range(&data_map.infos(), offset, len)
+                .ok_or("range should not be empty")?;
+            assert!(infos.len() < data_map.infos().len());
+
+            let encrypted_chunks: Vec<EncryptedChunk> = infos
+                .iter()
+                .map(|info| {
+                    let chunk = chunks
+                        .iter()
+                        .find(|chunk| *chunk.name() == info.dst_hash)
+                        .expect("chunk of the data map");
+                    EncryptedChunk {
+                        index: info.index,
+                        content: chunk.value.clone(),
+                    }
+                })
+                .collect();
+            let bytes = decrypt_range(&data_map, &encrypted_chunks, relative_pos, len)?;
+
+            let start = offset as usize;
+            let end = usize::min(start + len, data.len());
+            assert_eq!(bytes, data.slice(start..end));
+        }
+
+        assert!(chunks_covering_range(&data_map.infos(), data.len() as u64, 10).is_none());
+        assert!(chunks_covering_range(&data_map.infos(), 0, 0).is_none());
+        Ok(())
+    }
+}
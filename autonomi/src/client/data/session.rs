@@ -0,0 +1,137 @@
+// Copyright 2024 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use std::collections::HashMap;
+
+use ant_evm::{AttoTokens, EvmWallet};
+use ant_protocol::storage::Chunk;
+use bytes::Bytes;
+use xor_name::XorName;
+
+use super::{CostError, DataAddr, DataMapChunk, PutError, CHUNK_UPLOAD_BATCH_SIZE};
+use crate::client::payment::Receipt;
+use crate::client::quote::StoreQuote;
+use crate::self_encryption::{self, encrypt};
+use crate::Client;
+
+/// Uploads many pieces of data together, paying for all their chunks at once.
+///
+/// Paying for each piece of data separately sends at least one transaction per piece, a session quotes the
+/// chunks of all the pieces together and pays them with as few transactions as possible.
+/// Created with [`Client::upload_session`].
+///
+/// # Example
+///
+/// ```no_run
+/// use autonomi::{Client, Bytes};
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # let client = Client::init().await?;
+/// # let wallet = todo!();
+/// let mut session = client.upload_session();
+/// let first = session.add_data_public(Bytes::from("first file"))?;
+/// let second = session.add_data(Bytes::from("second file"))?;
+/// session.upload(&wallet).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct UploadSession {
+    client: Client,
+    /// The chunks of all the pieces of data, chunks shared by several pieces are only paid once.
+    chunks: HashMap<XorName, Chunk>,
+}
+
+impl Client {
+    /// Start a session uploading many pieces of data with a single payment.
+    pub fn upload_session(&self) -> UploadSession {
+        UploadSession {
+            client: self.clone(),
+            chunks: HashMap::new(),
+        }
+    }
+}
+
+impl UploadSession {
+    /// Add a piece of private data to the session, returns its data map.
+    ///
+    /// Like [`Client::data_put`], the [`DataMapChunk`] is not uploaded.
+    pub fn add_data(&mut self, data: Bytes) -> Result<DataMapChunk, self_encryption::Error> {
+        let (data_map_chunk, chunks) = encrypt(data)?;
+        self.add_chunks(chunks);
+        Ok(DataMapChunk(data_map_chunk))
+    }
+
+    /// Add a piece of public data to the session, returns the address it will be available at.
+    pub fn add_data_public(&mut self, data: Bytes) -> Result<DataAddr, self_encryption::Error> {
+        let (data_map_chunk, chunks) = encrypt(data)?;
+        let data_addr = *data_map_chunk.name();
+        self.add_chunks(chunks);
+        self.add_chunks(std::iter::once(data_map_chunk));
+        Ok(data_addr)
+    }
+
+    fn add_chunks(&mut self, chunks: impl IntoIterator<Item = Chunk>) {
+        self.chunks
+            .extend(chunks.into_iter().map(|chunk| (*chunk.name(), chunk)));
+    }
+
+    /// The number of distinct chunks to upload.
+    pub fn chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Get the estimated cost of uploading all the data of the session.
+    pub async fn cost(&self) -> Result<AttoTokens, CostError> {
+        let store_quote = self.quote().await?;
+        Ok(AttoTokens::from_atto(store_quote.price()))
+    }
+
+    /// Pay for all the chunks of the session at once and upload them.
+    ///
+    /// Returns the receipt of the payment, which can be used to retry the upload of the same data without paying
+    /// again.
+    pub async fn upload(self, wallet: &EvmWallet) -> Result<Receipt, PutError> {
+        let started = ant_networking::time::Instant::now();
+        let store_quote = self.quote().await?;
+        info!(
+            "Paying for {} chunks out of the {} chunks of the session",
+            store_quote.len(),
+            self.chunks.len()
+        );
+        let (receipt, transactions) = self
+            .client
+            .pay_for_store_quotes(store_quote, wallet)
+            .await
+            .inspect_err(|err| error!("Error paying for the session: {err:?}"))?;
+
+        self.client
+            .upload_paid_chunks(
+                self.chunks.values().collect(),
+                &receipt,
+                transactions,
+                started,
+            )
+            .await?;
+        Ok(receipt)
+    }
+
+    /// Quote all the chunks, by batches to bound the number of concurrent quote requests.
+    async fn quote(&self) -> Result<StoreQuote, CostError> {
+        let addrs: Vec<XorName> = self.chunks.keys().copied().collect();
+        let mut store_quote = StoreQuote(HashMap::new());
+        for batch in addrs.chunks(*CHUNK_UPLOAD_BATCH_SIZE) {
+            let batch_quote = self
+                .client
+                .get_store_quotes(batch.iter().copied())
+                .await
+                .inspect_err(|err| error!("Error getting store quotes: {err:?}"))?;
+            store_quote.0.extend(batch_quote.0);
+        }
+        Ok(store_quote)
+    }
+}
@@ -0,0 +1,271 @@
+// Copyright 2024 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use ant_protocol::storage::Chunk;
+use bytes::Bytes;
+use futures::{Stream, StreamExt};
+use self_encryption::{decrypt_range, ChunkInfo, DataMap};
+use tokio::io::{AsyncRead, AsyncWriteExt};
+use tokio_util::io::StreamReader;
+
+use super::{DataMapChunk, GetError, PutError, CHUNK_DOWNLOAD_BATCH_SIZE, CHUNK_UPLOAD_BATCH_SIZE};
+use crate::client::files::journal::UploadJournal;
+use crate::client::payment::PaymentOption;
+use crate::client::{ClientEvent, UploadSummary};
+use crate::self_encryption::{ParityMap, StreamEncryptor};
+use crate::Client;
+
+impl Client {
+    /// Upload private data read from `reader` to the network. This data will be self-encrypted.
+    ///
+    /// Unlike [`Client::data_put`], the data is never held in memory as a whole: it is spooled to a
+    /// temporary file and then encrypted, paid for and uploaded in batches of `CHUNK_UPLOAD_BATCH_SIZE` chunks.
+    ///
+    /// Returns the [`DataMapChunk`] containing the map to the encrypted chunks.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use autonomi::Client;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = Client::init().await?;
+    /// # let wallet = todo!();
+    /// let file = tokio::fs::File::open("video.mp4").await?;
+    /// let data_map = client.data_put_stream(file, wallet).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn data_put_stream(
+        &self,
+        mut reader: impl AsyncRead + Unpin,
+        payment_option: PaymentOption,
+    ) -> Result<DataMapChunk, PutError> {
+        // Self-encryption needs the total size upfront and reads neighbouring chunks out of order,
+        // hence the stream is written to disk first instead of being buffered in memory.
+        let spool = tempfile::NamedTempFile::new()?;
+        let mut spool_file = tokio::fs::File::from_std(spool.reopen()?);
+        let size = tokio::io::copy(&mut reader, &mut spool_file).await?;
+        spool_file.flush().await?;
+        debug!(
+            "Spooled {size} bytes to {:?} for streaming upload",
+            spool.path()
+        );
+
+        let data_map_chunk = self
+            .file_put_stream(spool.path().to_path_buf(), payment_option, false, None)
+            .await?;
+        Ok(DataMapChunk(data_map_chunk))
+    }
+
+    /// Fetch a blob of (private) data from the network as a reader.
+    ///
+    /// Chunks are fetched and decrypted in batches of `CHUNK_DOWNLOAD_BATCH_SIZE` as the reader is consumed,
+    /// so memory use is bounded by the batch size rather than by the size of the data.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use autonomi::Client;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = Client::init().await?;
+    /// # let data_map = todo!();
+    /// let mut reader = client.data_get_stream(data_map).await?;
+    /// let mut file = tokio::fs::File::create("video.mp4").await?;
+    /// tokio::io::copy(&mut reader, &mut file).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn data_get_stream(
+        &self,
+        data_map: DataMapChunk,
+    ) -> Result<impl AsyncRead + Send + Unpin + 'static, GetError> {
+        info!(
+            "Streaming private data from Data Map {:?}",
+            data_map.0.address()
+        );
+        let (data_map, parity_map) = self.restore_data_map_from_chunk(data_map.0.value()).await?;
+        Ok(StreamReader::new(
+            self.decrypted_batches(data_map, parity_map),
+        ))
+    }
+
+    /// Self-encrypt the file at `path` and upload its chunks in batches of `CHUNK_UPLOAD_BATCH_SIZE`,
+    /// each batch being paid for before it is uploaded.
+    ///
+    /// Returns the data map chunk, which is only uploaded as well when `public` is set.
+    /// The progress of the upload is recorded in `journal` when one is given.
+    pub(crate) async fn file_put_stream(
+        &self,
+        path: PathBuf,
+        payment_option: PaymentOption,
+        public: bool,
+        journal: Option<&UploadJournal>,
+    ) -> Result<Chunk, PutError> {
+        let now = ant_networking::time::Instant::now();
+        let mut encryptor = StreamEncryptor::new(path)?;
+        let mut summary = UploadSummary::default();
+
+        let mut last_batch = loop {
+            let chunks = encryptor.next_batch(*CHUNK_UPLOAD_BATCH_SIZE)?;
+            if encryptor.is_done() {
+                break chunks;
+            }
+
+            self.upload_chunk_batch(&chunks, payment_option.clone(), journal, &mut summary)
+                .await?;
+            summary.record_count += chunks.len();
+        };
+
+        let (data_map_chunk, additional_chunks) = encryptor.finish()?;
+        last_batch.extend(additional_chunks);
+        if public {
+            last_batch.push(data_map_chunk.clone());
+        }
+        if !last_batch.is_empty() {
+            self.upload_chunk_batch(&last_batch, payment_option, journal, &mut summary)
+                .await?;
+            summary.record_count += last_batch.len();
+        }
+        summary.elapsed = now.elapsed();
+        debug!(
+            "Streaming upload of {} chunks took: {:.2?}",
+            summary.record_count, summary.elapsed
+        );
+
+        // Reporting
+        self.send_client_event(ClientEvent::UploadComplete(summary))
+            .await;
+
+        Ok(data_map_chunk)
+    }
+
+    /// Pay for and upload a batch of chunks, adding the payments and uploads to the summary.
+    ///
+    /// With a journal, chunks it marks as verified are skipped, its unexpired payments are reused,
+    /// and every new payment and verified chunk is recorded in it.
+    async fn upload_chunk_batch(
+        &self,
+        chunks: &[Chunk],
+        payment_option: PaymentOption,
+        journal: Option<&UploadJournal>,
+        summary: &mut UploadSummary,
+    ) -> Result<(), PutError> {
+        let chunks: Vec<&Chunk> = chunks
+            .iter()
+            .filter(|chunk| !journal.is_some_and(|journal| journal.is_verified(chunk.name())))
+            .collect();
+        if chunks.is_empty() {
+            debug!("All chunks of the batch were already uploaded, skipping");
+            return Ok(());
+        }
+
+        let xor_names: Vec<_> = chunks.iter().map(|chunk| *chunk.name()).collect();
+        let mut receipt = journal
+            .map(|journal| journal.receipt_for(xor_names.iter()))
+            .unwrap_or_default();
+        let unpaid: Vec<_> = xor_names
+            .iter()
+            .filter(|xor_name| !receipt.contains_key(xor_name))
+            .copied()
+            .collect();
+
+        if !unpaid.is_empty() {
+            info!("Paying for {} addresses", unpaid.len());
+            let (new_receipt, transactions) = self
+                .pay_for_content_addrs(unpaid.clone().into_iter(), payment_option)
+                .await
+                .inspect_err(|err| error!("Error paying for data: {err:?}"))?;
+
+            if let Some(journal) = journal {
+                if let Err(err) = journal.record_payments(&new_receipt) {
+                    error!("Failed to record payments in the upload journal: {err:?}");
+                }
+            }
+
+            // A provided receipt may cover more than this batch, only count what was used here.
+            summary.add_payments(
+                unpaid
+                    .iter()
+                    .filter_map(|xor_name| new_receipt.get_key_value(xor_name)),
+                transactions,
+            );
+            receipt.extend(new_receipt);
+        }
+
+        debug!("Uploading {} chunks", chunks.len());
+        let mut failed_uploads = self
+            .upload_chunks_with_retries(chunks, &receipt, summary)
+            .await;
+
+        if let Some(journal) = journal {
+            let failed: HashSet<_> = failed_uploads
+                .iter()
+                .map(|(chunk, _)| *chunk.name())
+                .collect();
+            let verified = xor_names
+                .iter()
+                .filter(|xor_name| !failed.contains(xor_name));
+            if let Err(err) = journal.record_verified(verified) {
+                error!("Failed to record verified chunks in the upload journal: {err:?}");
+            }
+        }
+
+        // Return the last chunk upload error
+        if let Some(last_chunk_fail) = failed_uploads.pop() {
+            tracing::error!(
+                "Error uploading chunk ({:?}): {:?}",
+                last_chunk_fail.0.address(),
+                last_chunk_fail.1
+            );
+            return Err(last_chunk_fail.1);
+        }
+
+        Ok(())
+    }
+
+    /// Lazily fetch and decrypt the chunks of a data map in batches of `CHUNK_DOWNLOAD_BATCH_SIZE`.
+    fn decrypted_batches(
+        &self,
+        data_map: DataMap,
+        parity_map: Option<ParityMap>,
+    ) -> impl Stream<Item = std::io::Result<Bytes>> + Send + Unpin + 'static {
+        let client = self.clone();
+        let batches: Vec<Vec<ChunkInfo>> = data_map
+            .infos()
+            .chunks(*CHUNK_DOWNLOAD_BATCH_SIZE)
+            .map(<[ChunkInfo]>::to_vec)
+            .collect();
+        let data_map = Arc::new(data_map);
+        let parity_map = Arc::new(parity_map);
+
+        Box::pin(futures::stream::iter(batches).then(move |infos| {
+            let client = client.clone();
+            let data_map = Arc::clone(&data_map);
+            let parity_map = Arc::clone(&parity_map);
+            async move {
+                let encrypted_chunks = client
+                    .fetch_encrypted_chunks(&infos, parity_map.as_ref().as_ref())
+                    .await
+                    .map_err(std::io::Error::other)?;
+
+                decrypt_range(&data_map, &encrypted_chunks, 0, usize::MAX).map_err(|err| {
+                    error!("Error decrypting encrypted_chunks: {err:?}");
+                    std::io::Error::other(GetError::Decryption(
+                        crate::self_encryption::Error::SelfEncryption(err),
+                    ))
+                })
+            }
+        }))
+    }
+}
@@ -15,13 +15,17 @@
 // permissions and limitations relating to use of the SAFE Network Software.
 
 use super::archive::{PrivateArchive, PrivateArchiveAccess};
+use super::journal::UploadJournal;
 use crate::client::data::{CostError, DataMapChunk, GetError, PutError};
 use crate::client::files::get_relative_file_path_from_abs_file_and_folder_path;
 use crate::client::utils::process_tasks_with_max_concurrency;
 use crate::client::Client;
 use ant_evm::EvmWallet;
-use bytes::Bytes;
-use std::{path::PathBuf, sync::LazyLock};
+use std::{
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+use tokio::io::{AsyncRead, AsyncWriteExt};
 
 /// Number of files to upload in parallel.
 ///
@@ -88,12 +92,8 @@ impl Client {
         data_access: DataMapChunk,
         to_dest: PathBuf,
     ) -> Result<(), DownloadError> {
-        let data = self.data_get(data_access).await?;
-        if let Some(parent) = to_dest.parent() {
-            tokio::fs::create_dir_all(parent).await?;
-            debug!("Created parent directories for {to_dest:?}");
-        }
-        tokio::fs::write(to_dest.clone(), data).await?;
+        let reader = self.data_get_stream(data_access).await?;
+        write_stream_to_file(reader, &to_dest).await?;
         debug!("Downloaded file to {to_dest:?}");
         Ok(())
     }
@@ -118,6 +118,16 @@ impl Client {
         &self,
         dir_path: PathBuf,
         wallet: &EvmWallet,
+    ) -> Result<PrivateArchive, UploadError> {
+        self.dir_upload_with_journal(dir_path, wallet, None).await
+    }
+
+    /// Same as [`Client::dir_upload`], recording the progress of the upload in `journal` when one is given.
+    pub(crate) async fn dir_upload_with_journal(
+        &self,
+        dir_path: PathBuf,
+        wallet: &EvmWallet,
+        journal: Option<&UploadJournal>,
     ) -> Result<PrivateArchive, UploadError> {
         info!("Uploading directory as private: {dir_path:?}");
         let start = tokio::time::Instant::now();
@@ -133,7 +143,7 @@ impl Client {
             let metadata = super::fs_public::metadata_from_entry(&entry);
             let path = entry.path().to_path_buf();
             upload_tasks.push(async move {
-                let file = self.file_upload(path.clone(), wallet).await;
+                let file = self.file_upload(path.clone(), wallet, journal).await;
                 (path, metadata, file)
             });
         }
@@ -179,19 +189,36 @@ impl Client {
 
     /// Upload a private file to the network.
     /// Reads file, splits into chunks, uploads chunks, uploads datamap, returns [`DataMapChunk`] (pointing to the datamap)
-    async fn file_upload(
+    pub(crate) async fn file_upload(
         &self,
         path: PathBuf,
         wallet: &EvmWallet,
+        journal: Option<&UploadJournal>,
     ) -> Result<DataMapChunk, UploadError> {
         info!("Uploading file: {path:?}");
         #[cfg(feature = "loud")]
         println!("Uploading file: {path:?}");
 
-        let data = tokio::fs::read(path).await?;
-        let data = Bytes::from(data);
-        let addr = self.data_put(data, wallet.into()).await?;
+        let data_map_chunk = self
+            .file_put_stream(path, wallet.into(), false, journal)
+            .await?;
+        let addr = DataMapChunk::from(data_map_chunk);
         debug!("Uploaded file successfully in the privateAchive: {addr:?}");
         Ok(addr)
     }
 }
+
+// Write the content of `reader` to `to_dest`, creating its parent directories if needed.
+pub(crate) async fn write_stream_to_file(
+    mut reader: impl AsyncRead + Unpin,
+    to_dest: &Path,
+) -> Result<(), DownloadError> {
+    if let Some(parent) = to_dest.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+        debug!("Created parent directories for {to_dest:?}");
+    }
+    let mut file = tokio::fs::File::create(to_dest).await?;
+    tokio::io::copy(&mut reader, &mut file).await?;
+    file.flush().await?;
+    Ok(())
+}
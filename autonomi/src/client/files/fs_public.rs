@@ -8,6 +8,7 @@
 
 use super::archive_public::{ArchiveAddr, PublicArchive};
 use super::fs::*;
+use super::journal::UploadJournal;
 use crate::client::data::DataAddr;
 use crate::client::files::archive::Metadata;
 use crate::client::files::get_relative_file_path_from_abs_file_and_folder_path;
@@ -25,12 +26,9 @@ impl Client {
         data_addr: DataAddr,
         to_dest: PathBuf,
     ) -> Result<(), DownloadError> {
-        let data = self.data_get_public(data_addr).await?;
-        if let Some(parent) = to_dest.parent() {
-            tokio::fs::create_dir_all(parent).await?;
-            debug!("Created parent directories {parent:?} for {to_dest:?}");
-        }
-        tokio::fs::write(to_dest.clone(), data).await?;
+        let data_map_chunk = self.chunk_get(data_addr).await?;
+        let reader = self.data_get_stream(data_map_chunk.into()).await?;
+        write_stream_to_file(reader, &to_dest).await?;
         debug!("Downloaded file to {to_dest:?} from the network address {data_addr:?}");
         Ok(())
     }
@@ -63,6 +61,17 @@ impl Client {
         &self,
         dir_path: PathBuf,
         wallet: &EvmWallet,
+    ) -> Result<PublicArchive, UploadError> {
+        self.dir_upload_public_with_journal(dir_path, wallet, None)
+            .await
+    }
+
+    /// Same as [`Client::dir_upload_public`], recording the progress of the upload in `journal` when one is given.
+    pub(crate) async fn dir_upload_public_with_journal(
+        &self,
+        dir_path: PathBuf,
+        wallet: &EvmWallet,
+        journal: Option<&UploadJournal>,
     ) -> Result<PublicArchive, UploadError> {
         info!("Uploading directory: {dir_path:?}");
         let start = tokio::time::Instant::now();
@@ -78,7 +87,7 @@ impl Client {
             let metadata = metadata_from_entry(&entry);
             let path = entry.path().to_path_buf();
             upload_tasks.push(async move {
-                let file = self.file_upload_public(path.clone(), wallet).await;
+                let file = self.file_upload_public(path.clone(), wallet, journal).await;
                 (path, metadata, file)
             });
         }
@@ -124,18 +133,20 @@ impl Client {
 
     /// Upload a file to the network.
     /// Reads file, splits into chunks, uploads chunks, uploads datamap, returns DataAddr (pointing to the datamap)
-    async fn file_upload_public(
+    pub(crate) async fn file_upload_public(
         &self,
         path: PathBuf,
         wallet: &EvmWallet,
+        journal: Option<&UploadJournal>,
     ) -> Result<DataAddr, UploadError> {
         info!("Uploading file: {path:?}");
         #[cfg(feature = "loud")]
         println!("Uploading file: {path:?}");
 
-        let data = tokio::fs::read(path.clone()).await?;
-        let data = Bytes::from(data);
-        let addr = self.data_put_public(data, wallet.into()).await?;
+        let data_map_chunk = self
+            .file_put_stream(path.clone(), wallet.into(), true, journal)
+            .await?;
+        let addr = *data_map_chunk.address().xorname();
         debug!("File {path:?} uploaded to the network at {addr:?}");
         Ok(addr)
     }
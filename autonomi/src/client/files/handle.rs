@@ -0,0 +1,103 @@
+// Copyright 2024 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use std::path::{Path, PathBuf};
+
+use bytes::Bytes;
+use self_encryption::DataMap;
+
+use super::archive::PrivateArchive;
+use super::archive_public::PublicArchive;
+use crate::client::data::{DataAddr, DataMapChunk, GetError};
+use crate::self_encryption::ParityMap;
+use crate::Client;
+
+/// Errors that can occur when opening a file of an archive.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, thiserror::Error)]
+pub enum OpenError {
+    #[error("File not found in archive: {0:?}")]
+    NotFound(PathBuf),
+    #[error("Failed to fetch file")]
+    GetError(#[from] GetError),
+}
+
+/// Handle to a file stored on the network, allowing random-access reads without downloading the whole file.
+///
+/// Opening the handle fetches the data map of the file once, each read then only fetches the chunks it covers.
+#[derive(Clone)]
+pub struct FileHandle {
+    client: Client,
+    data_map: DataMap,
+    parity_map: Option<ParityMap>,
+}
+
+impl FileHandle {
+    /// Size of the file in bytes.
+    pub fn size(&self) -> u64 {
+        self.data_map.file_size() as u64
+    }
+
+    /// Read up to `len` bytes starting at `offset`. Fewer bytes are returned when reading past the end of the file.
+    pub async fn read_at(&self, offset: u64, len: usize) -> Result<Bytes, GetError> {
+        self.client
+            .fetch_range_from_data_map(&self.data_map, self.parity_map.as_ref(), offset, len)
+            .await
+    }
+}
+
+impl Client {
+    /// Open a private file for random-access reads.
+    pub async fn file_open(&self, data_map: DataMapChunk) -> Result<FileHandle, GetError> {
+        let (data_map, parity_map) = self.restore_data_map_from_chunk(data_map.0.value()).await?;
+        Ok(FileHandle {
+            client: self.clone(),
+            data_map,
+            parity_map,
+        })
+    }
+
+    /// Open a public file for random-access reads.
+    pub async fn file_open_public(&self, addr: DataAddr) -> Result<FileHandle, GetError> {
+        let data_map_chunk = self.chunk_get(addr).await?;
+        let (data_map, parity_map) = self
+            .restore_data_map_from_chunk(&data_map_chunk.value)
+            .await?;
+        Ok(FileHandle {
+            client: self.clone(),
+            data_map,
+            parity_map,
+        })
+    }
+
+    /// Open the file at `path` within a private archive for random-access reads.
+    pub async fn archive_file_open(
+        &self,
+        archive: &PrivateArchive,
+        path: &Path,
+    ) -> Result<FileHandle, OpenError> {
+        let (data_map, _meta) = archive
+            .map()
+            .get(path)
+            .ok_or_else(|| OpenError::NotFound(path.to_path_buf()))?;
+        Ok(self.file_open(data_map.clone()).await?)
+    }
+
+    /// Open the file at `path` within a public archive for random-access reads.
+    pub async fn archive_file_open_public(
+        &self,
+        archive: &PublicArchive,
+        path: &Path,
+    ) -> Result<FileHandle, OpenError> {
+        let (data_addr, _meta) = archive
+            .map()
+            .get(path)
+            .ok_or_else(|| OpenError::NotFound(path.to_path_buf()))?;
+        Ok(self.file_open_public(*data_addr).await?)
+    }
+}
@@ -0,0 +1,484 @@
+// Copyright 2024 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{Mutex, MutexGuard},
+};
+
+use ant_evm::{AttoTokens, EvmWallet, ProofOfPayment};
+use serde::{Deserialize, Serialize};
+use xor_name::XorName;
+
+use super::archive::PrivateArchiveAccess;
+use super::archive_public::ArchiveAddr;
+use super::fs::UploadError;
+use crate::client::payment::Receipt;
+use crate::client::receipts::StoredReceipt;
+use crate::Client;
+
+/// Errors that can occur while reading or writing an [`UploadJournal`].
+#[derive(Debug, thiserror::Error)]
+pub enum JournalError {
+    #[error("IO failure")]
+    IoError(#[from] std::io::Error),
+    #[error("Failed to serialize upload journal")]
+    Serialization(#[from] rmp_serde::encode::Error),
+    #[error("Failed to deserialize upload journal")]
+    Deserialization(#[from] rmp_serde::decode::Error),
+    #[error("The upload journal does not start with the upload it records")]
+    MissingUpload,
+}
+
+/// Progress recorded for a single chunk of an upload.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct JournalEntry {
+    /// The proof of the payment made for the chunk.
+    pub proof: ProofOfPayment,
+    /// The amount paid for the chunk.
+    pub price: AttoTokens,
+    /// Whether the chunk was stored and its storage verified on the network.
+    pub verified: bool,
+}
+
+#[derive(Debug)]
+struct JournalContent {
+    source: PathBuf,
+    public: bool,
+    chunks: HashMap<XorName, JournalEntry>,
+}
+
+/// A record of the journal file, which is a sequence of them.
+#[derive(Debug, Serialize, Deserialize)]
+enum JournalRecord {
+    /// The upload being journaled, always the first record.
+    Upload { source: PathBuf, public: bool },
+    /// A batch of chunks paid for.
+    Paid(Vec<(XorName, ProofOfPayment, AttoTokens)>),
+    /// A batch of chunks stored and verified on the network.
+    Verified(Vec<XorName>),
+}
+
+/// On-disk record of the progress of a file or directory upload.
+///
+/// Every batch of payments and of verified chunks is appended to the journal file as soon as it happens, so an
+/// interrupted upload can be picked up again with [`Client::resume_upload`] without paying twice for the same
+/// chunks. The file is compacted when the journal is loaded.
+#[derive(Debug)]
+pub struct UploadJournal {
+    path: PathBuf,
+    content: Mutex<JournalContent>,
+    /// The journal file opened for appending, created along with the first record.
+    log: Mutex<Option<File>>,
+}
+
+/// The archive produced by [`Client::resume_upload`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UploadedArchive {
+    Public(ArchiveAddr),
+    Private(PrivateArchiveAccess),
+}
+
+impl UploadJournal {
+    /// Create a new, empty journal for uploading `source`, to be stored at `path`.
+    /// Note that nothing is written to disk until the first chunk is paid for.
+    pub fn new(path: PathBuf, source: PathBuf, public: bool) -> Self {
+        Self {
+            path,
+            content: Mutex::new(JournalContent {
+                source,
+                public,
+                chunks: HashMap::new(),
+            }),
+            log: Mutex::new(None),
+        }
+    }
+
+    /// Load an existing journal from disk and compact its file.
+    ///
+    /// A record left partially written by an interrupted upload is ignored.
+    pub fn load(path: PathBuf) -> Result<Self, JournalError> {
+        let bytes = std::fs::read(&path)?;
+        let content = replay(&bytes)?;
+        debug!(
+            "Loaded upload journal from {path:?} with {} chunk entries",
+            content.chunks.len()
+        );
+        let journal = Self {
+            path,
+            content: Mutex::new(content),
+            log: Mutex::new(None),
+        };
+        journal.compact()?;
+        Ok(journal)
+    }
+
+    /// Remove the journal from disk, typically once the upload has completed.
+    pub fn remove(self) -> Result<(), JournalError> {
+        let Self { path, .. } = self;
+        match std::fs::remove_file(&path) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// The file or directory being uploaded.
+    pub fn source(&self) -> PathBuf {
+        self.lock().source.clone()
+    }
+
+    /// Whether the upload is public.
+    pub fn is_public(&self) -> bool {
+        self.lock().public
+    }
+
+    /// The recorded progress for every chunk paid for so far.
+    pub fn entries(&self) -> HashMap<XorName, JournalEntry> {
+        self.lock().chunks.clone()
+    }
+
+    /// Whether the chunk was already stored and verified on the network.
+    pub fn is_verified(&self, xor_name: &XorName) -> bool {
+        self.lock()
+            .chunks
+            .get(xor_name)
+            .is_some_and(|entry| entry.verified)
+    }
+
+    /// The payments from the journal that can still be used for the given chunks, i.e. the ones that have not expired.
+    pub fn receipt_for<'a>(&self, xor_names: impl Iterator<Item = &'a XorName>) -> Receipt {
+        let content = self.lock();
+        xor_names
+            .filter_map(|xor_name| {
+                let entry = content.chunks.get(xor_name)?;
+                if entry.proof.has_expired() {
+                    debug!("Journaled payment for {xor_name:?} has expired, it will be paid again");
+                    return None;
+                }
+                Some((*xor_name, (entry.proof.clone(), entry.price)))
+            })
+            .collect()
+    }
+
+    /// Reuse the unexpired payments of a stored receipt for the chunks of this upload, e.g. the payments of an
+    /// earlier upload of the same data that failed, and persist the journal. Chunks the journal already has a
+    /// payment for are left as they are.
+    ///
+    /// Returns the number of payments added to the journal.
+    pub fn reuse_receipt(&self, stored: StoredReceipt) -> Result<usize, JournalError> {
+        let receipt: Receipt = stored
+            .receipt
+            .into_iter()
+            .filter(|(xor_name, (proof, _))| {
+                !proof.has_expired() && !self.lock().chunks.contains_key(xor_name)
+            })
+            .collect();
+        self.record_payments(&receipt)?;
+        debug!(
+            "Reusing {} payments of receipt {} in the upload journal",
+            receipt.len(),
+            stored.id
+        );
+        Ok(receipt.len())
+    }
+
+    /// Record the payments of a receipt and append them to the journal file.
+    pub(crate) fn record_payments(&self, receipt: &Receipt) -> Result<(), JournalError> {
+        let paid: Vec<_> = receipt
+            .iter()
+            .map(|(xor_name, (proof, price))| (*xor_name, proof.clone(), *price))
+            .collect();
+        {
+            let mut content = self.lock();
+            for (xor_name, proof, price) in &paid {
+                let _ = content.chunks.insert(
+                    *xor_name,
+                    JournalEntry {
+                        proof: proof.clone(),
+                        price: *price,
+                        verified: false,
+                    },
+                );
+            }
+        }
+        self.append(&JournalRecord::Paid(paid))
+    }
+
+    /// Mark chunks as stored and verified and append them to the journal file.
+    pub(crate) fn record_verified<'a>(
+        &self,
+        xor_names: impl Iterator<Item = &'a XorName>,
+    ) -> Result<(), JournalError> {
+        let verified: Vec<XorName> = xor_names.copied().collect();
+        {
+            let mut content = self.lock();
+            for xor_name in &verified {
+                if let Some(entry) = content.chunks.get_mut(xor_name) {
+                    entry.verified = true;
+                }
+            }
+        }
+        self.append(&JournalRecord::Verified(verified))
+    }
+
+    /// Append a record to the journal file, creating the file starting with the upload record if needed.
+    fn append(&self, record: &JournalRecord) -> Result<(), JournalError> {
+        let bytes = rmp_serde::to_vec(record)?;
+        let mut log = lock(&self.log);
+        let file = match log.as_mut() {
+            Some(file) => file,
+            None => {
+                if let Some(parent) = self.path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                let mut file = File::create(&self.path)?;
+                file.write_all(&rmp_serde::to_vec(&self.upload_record())?)?;
+                log.insert(file)
+            }
+        };
+        file.write_all(&bytes)?;
+        Ok(())
+    }
+
+    // Rewrite the journal as a single record of each kind. It is written to a temporary file first, so that a crash
+    // mid-write never corrupts the existing journal.
+    fn compact(&self) -> Result<(), JournalError> {
+        let mut bytes = rmp_serde::to_vec(&self.upload_record())?;
+        {
+            let content = self.lock();
+            let paid = content
+                .chunks
+                .iter()
+                .map(|(xor_name, entry)| (*xor_name, entry.proof.clone(), entry.price))
+                .collect();
+            let verified = content
+                .chunks
+                .iter()
+                .filter(|(_, entry)| entry.verified)
+                .map(|(xor_name, _)| *xor_name)
+                .collect();
+            rmp_serde::encode::write(&mut bytes, &JournalRecord::Paid(paid))?;
+            rmp_serde::encode::write(&mut bytes, &JournalRecord::Verified(verified))?;
+        }
+
+        let tmp_path = self.path.with_extension("tmp");
+        std::fs::write(&tmp_path, bytes)?;
+        std::fs::rename(&tmp_path, &self.path)?;
+        let file = std::fs::OpenOptions::new().append(true).open(&self.path)?;
+        *lock(&self.log) = Some(file);
+        Ok(())
+    }
+
+    fn upload_record(&self) -> JournalRecord {
+        let content = self.lock();
+        JournalRecord::Upload {
+            source: content.source.clone(),
+            public: content.public,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, JournalContent> {
+        lock(&self.content)
+    }
+}
+
+// A poisoned lock only means another upload task panicked, the journal itself remains usable.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Rebuild the content of a journal from the records of its file.
+fn replay(mut bytes: &[u8]) -> Result<JournalContent, JournalError> {
+    let JournalRecord::Upload { source, public } = rmp_serde::from_read(&mut bytes)? else {
+        return Err(JournalError::MissingUpload);
+    };
+    let mut content = JournalContent {
+        source,
+        public,
+        chunks: HashMap::new(),
+    };
+    while !bytes.is_empty() {
+        match rmp_serde::from_read(&mut bytes) {
+            Ok(JournalRecord::Paid(paid)) => {
+                for (xor_name, proof, price) in paid {
+                    let _ = content.chunks.insert(
+                        xor_name,
+                        JournalEntry {
+                            proof,
+                            price,
+                            verified: false,
+                        },
+                    );
+                }
+            }
+            Ok(JournalRecord::Verified(verified)) => {
+                for xor_name in verified {
+                    if let Some(entry) = content.chunks.get_mut(&xor_name) {
+                        entry.verified = true;
+                    }
+                }
+            }
+            Ok(JournalRecord::Upload { .. }) => return Err(JournalError::MissingUpload),
+            Err(err) => {
+                warn!("Ignoring the partially written end of an upload journal: {err:?}");
+                break;
+            }
+        }
+    }
+    Ok(content)
+}
+
+impl Client {
+    /// Upload the file or directory recorded in the journal, along with its archive.
+    ///
+    /// Chunks the journal marks as verified are skipped and unexpired payments from the journal are reused,
+    /// so calling this again after an interrupted upload only pays for what is still missing.
+    /// A freshly created [`UploadJournal`] simply starts a new upload.
+    pub async fn resume_upload(
+        &self,
+        journal: &UploadJournal,
+        wallet: &EvmWallet,
+    ) -> Result<UploadedArchive, UploadError> {
+        let source = journal.source();
+        info!(
+            "Uploading {source:?} using the upload journal at {:?}",
+            journal.path
+        );
+
+        if journal.is_public() {
+            let archive = self
+                .dir_upload_public_with_journal(source, wallet, Some(journal))
+                .await?;
+            let archive_addr = self.archive_put_public(&archive, wallet).await?;
+            Ok(UploadedArchive::Public(archive_addr))
+        } else {
+            let archive = self
+                .dir_upload_with_journal(source, wallet, Some(journal))
+                .await?;
+            let archive_access = self.archive_put(&archive, wallet.into()).await?;
+            Ok(UploadedArchive::Private(archive_access))
+        }
+    }
+}
+
+/// Path of the journal file for an upload of `source`, within `journal_dir`.
+///
+/// The same source and visibility always map to the same journal, so an interrupted upload can be found again.
+pub fn journal_path_for(journal_dir: &Path, source: &Path, public: bool) -> PathBuf {
+    let source = source
+        .canonicalize()
+        .unwrap_or_else(|_| source.to_path_buf());
+    let visibility = if public { "public" } else { "private" };
+    let key = format!("{}:{visibility}", source.display());
+    let name = XorName::from_content(key.as_bytes());
+    journal_dir.join(format!("{}.journal", hex::encode(name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn journal_persists_progress() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("upload.journal");
+        let journal = UploadJournal::new(path.clone(), PathBuf::from("/some/file"), true);
+
+        let paid = XorName::random(&mut rand::thread_rng());
+        let stored = XorName::random(&mut rand::thread_rng());
+        let proof = ProofOfPayment {
+            peer_quotes: vec![],
+        };
+        let receipt: Receipt = [
+            (paid, (proof.clone(), AttoTokens::from_u64(1))),
+            (stored, (proof, AttoTokens::from_u64(2))),
+        ]
+        .into_iter()
+        .collect();
+        journal.record_payments(&receipt)?;
+        journal.record_verified(std::iter::once(&stored))?;
+
+        let reloaded = UploadJournal::load(path.clone())?;
+        assert_eq!(reloaded.source(), PathBuf::from("/some/file"));
+        assert!(reloaded.is_public());
+        assert!(!reloaded.is_verified(&paid));
+        assert!(reloaded.is_verified(&stored));
+        assert_eq!(reloaded.entries(), journal.entries());
+        assert_eq!(reloaded.receipt_for([paid, stored].iter()).len(), 2);
+
+        // only the chunks without a payment yet are taken from a stored receipt
+        let unpaid = XorName::random(&mut rand::thread_rng());
+        let proof = ProofOfPayment {
+            peer_quotes: vec![],
+        };
+        let stored_receipt = StoredReceipt {
+            id: "earlier-upload".to_string(),
+            paid_at: std::time::SystemTime::now(),
+            receipt: [
+                (paid, (proof.clone(), AttoTokens::from_u64(3))),
+                (unpaid, (proof, AttoTokens::from_u64(4))),
+            ]
+            .into_iter()
+            .collect(),
+        };
+        assert_eq!(reloaded.reuse_receipt(stored_receipt)?, 1);
+        let entries = reloaded.entries();
+        assert_eq!(entries[&paid].price, AttoTokens::from_u64(1));
+        assert_eq!(entries[&unpaid].price, AttoTokens::from_u64(4));
+
+        reloaded.remove()?;
+        assert!(!path.exists());
+        Ok(())
+    }
+
+    #[test]
+    fn journal_ignores_a_partially_written_record() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("upload.journal");
+        let journal = UploadJournal::new(path.clone(), PathBuf::from("/some/dir"), false);
+
+        let chunks: Vec<XorName> = (0..3)
+            .map(|_| XorName::random(&mut rand::thread_rng()))
+            .collect();
+        for (i, xor_name) in chunks.iter().enumerate() {
+            let proof = ProofOfPayment {
+                peer_quotes: vec![],
+            };
+            let receipt: Receipt =
+                std::iter::once((*xor_name, (proof, AttoTokens::from_u64(i as u64)))).collect();
+            journal.record_payments(&receipt)?;
+        }
+        journal.record_verified(chunks[..2].iter())?;
+        drop(journal);
+
+        // an upload interrupted while appending a batch of verified chunks
+        let mut torn = rmp_serde::to_vec(&JournalRecord::Verified(vec![chunks[2]]))?;
+        torn.truncate(torn.len() - 1);
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)?
+            .write_all(&torn)?;
+
+        let reloaded = UploadJournal::load(path.clone())?;
+        assert_eq!(reloaded.entries().len(), 3);
+        assert!(reloaded.is_verified(&chunks[0]) && reloaded.is_verified(&chunks[1]));
+        assert!(!reloaded.is_verified(&chunks[2]));
+
+        // the compacted journal keeps the progress and new records are appended after it
+        reloaded.record_verified(std::iter::once(&chunks[2]))?;
+        let reloaded = UploadJournal::load(path)?;
+        assert!(chunks.iter().all(|xor_name| reloaded.is_verified(xor_name)));
+        Ok(())
+    }
+}
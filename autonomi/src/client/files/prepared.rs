@@ -0,0 +1,157 @@
+// Copyright 2024 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use std::path::PathBuf;
+
+use ant_evm::{AttoTokens, EvmWallet};
+use bytes::Bytes;
+
+use super::archive::PrivateArchive;
+use super::archive_public::PublicArchive;
+use super::fs::{FileCostError, UploadError, FILE_UPLOAD_BATCH_SIZE};
+use super::fs_public::metadata_from_entry;
+use super::get_relative_file_path_from_abs_file_and_folder_path;
+use super::journal::UploadedArchive;
+use crate::client::data::prepared::PreparedUpload;
+use crate::client::data::PutError;
+use crate::client::utils::process_tasks_with_max_concurrency;
+use crate::Client;
+
+/// An upload of a file or directory along with its archive, whose cost is known before paying for it.
+///
+/// Created with [`Client::dir_prepare_upload`], see [`PreparedUpload`] for a single piece of data.
+pub struct PreparedDirUpload {
+    files: Vec<PreparedUpload>,
+    archive: PreparedUpload,
+    public: bool,
+}
+
+impl Client {
+    /// Encrypt the files of a directory, or a single file, and their archive and get the quotes for storing them,
+    /// without paying anything.
+    ///
+    /// The files are read in memory until the upload is committed or aborted.
+    pub async fn dir_prepare_upload(
+        &self,
+        dir_path: PathBuf,
+        public: bool,
+    ) -> Result<PreparedDirUpload, FileCostError> {
+        info!("Preparing the upload of {dir_path:?}");
+        let mut files = vec![];
+        let mut private_archive = PrivateArchive::new();
+        let mut public_archive = PublicArchive::new();
+        for entry in walkdir::WalkDir::new(&dir_path) {
+            let entry = entry?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+
+            let path = entry.path().to_path_buf();
+            let data = Bytes::from(tokio::fs::read(&path).await?);
+            let rel_path = get_relative_file_path_from_abs_file_and_folder_path(&path, &dir_path);
+            let metadata = metadata_from_entry(&entry);
+            let file = if public {
+                let file = self.prepare_upload_public(data).await?;
+                public_archive.add_file(rel_path, file.data_address(), metadata);
+                file
+            } else {
+                let file = self.prepare_upload(data).await?;
+                private_archive.add_file(rel_path, file.data_map(), metadata);
+                file
+            };
+            files.push(file);
+        }
+
+        let archive = if public {
+            self.prepare_upload_public(public_archive.to_bytes()?)
+                .await?
+        } else {
+            self.prepare_upload(private_archive.to_bytes()?).await?
+        };
+
+        let prepared = PreparedDirUpload {
+            files,
+            archive,
+            public,
+        };
+        debug!(
+            "Prepared the upload of {} files from {dir_path:?}, quoted at {}",
+            prepared.files.len(),
+            prepared.total_cost()
+        );
+        Ok(prepared)
+    }
+}
+
+impl PreparedDirUpload {
+    /// The total amount that will be paid, for the files and the archive.
+    pub fn total_cost(&self) -> AttoTokens {
+        let total = self
+            .uploads()
+            .map(|upload| upload.total_cost().as_atto())
+            .sum();
+        AttoTokens::from_atto(total)
+    }
+
+    /// The number of files to upload.
+    pub fn file_count(&self) -> usize {
+        self.files.len()
+    }
+
+    /// The number of chunks to upload, including the chunks of the archive.
+    pub fn chunk_count(&self) -> usize {
+        self.uploads().map(PreparedUpload::chunk_count).sum()
+    }
+
+    /// The number of chunks to pay for, the others being already stored on the network.
+    pub fn paid_chunk_count(&self) -> usize {
+        self.uploads().map(PreparedUpload::paid_chunk_count).sum()
+    }
+
+    /// Pay the quotes and upload the files, then their archive.
+    ///
+    /// Exactly the quoted amount is paid. Fails with [`PutError::QuotesExpired`] without paying anything when
+    /// any of the quotes is too old to be accepted by the nodes.
+    pub async fn commit(self, wallet: &EvmWallet) -> Result<UploadedArchive, UploadError> {
+        if self.uploads().any(PreparedUpload::has_expired) {
+            return Err(PutError::QuotesExpired.into());
+        }
+
+        info!(
+            "Uploading {} prepared files, quoted at {}",
+            self.files.len(),
+            self.total_cost()
+        );
+        let tasks = self.files.into_iter().map(|file| file.commit(wallet));
+        for result in process_tasks_with_max_concurrency(tasks, *FILE_UPLOAD_BATCH_SIZE).await {
+            result?;
+        }
+
+        if self.public {
+            let archive_addr = self.archive.data_address();
+            self.archive.commit(wallet).await?;
+            Ok(UploadedArchive::Public(archive_addr))
+        } else {
+            let archive_access = self.archive.commit(wallet).await?;
+            Ok(UploadedArchive::Private(archive_access))
+        }
+    }
+
+    /// Drop the upload without paying anything.
+    pub fn abort(self) {
+        info!(
+            "Aborted the upload of {} files, quoted at {}",
+            self.files.len(),
+            self.total_cost()
+        );
+    }
+
+    fn uploads(&self) -> impl Iterator<Item = &PreparedUpload> {
+        self.files.iter().chain(std::iter::once(&self.archive))
+    }
+}
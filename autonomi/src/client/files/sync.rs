@@ -0,0 +1,231 @@
+// Copyright 2024 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+
+use ant_evm::EvmWallet;
+
+use super::archive::{Metadata, PrivateArchive};
+use super::archive_public::PublicArchive;
+use super::fs::{UploadError, FILE_UPLOAD_BATCH_SIZE};
+use super::fs_public::metadata_from_entry;
+use super::get_relative_file_path_from_abs_file_and_folder_path;
+use crate::client::utils::process_tasks_with_max_concurrency;
+use crate::Client;
+
+/// The changes between a local directory and the archive it was synced against.
+/// Paths are relative, as they appear in the archive.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncDiff {
+    /// Files that were not in the previous archive.
+    pub added: Vec<PathBuf>,
+    /// Files whose size or modification time changed since the previous archive.
+    pub modified: Vec<PathBuf>,
+    /// Files of the previous archive that no longer exist locally.
+    pub removed: Vec<PathBuf>,
+    /// Files that were left untouched and not uploaded again.
+    pub unchanged: Vec<PathBuf>,
+}
+
+impl SyncDiff {
+    /// Whether the directory is identical to the previous archive.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.modified.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl Client {
+    /// Sync a directory against a previous version of its private archive.
+    ///
+    /// Only new files and files whose size or modification time differ from the [`Metadata`] in `previous_archive`
+    /// are uploaded, unchanged files keep their existing data maps. Files that no longer exist locally are left out.
+    ///
+    /// Returns the new version of the archive, which is not uploaded, along with the [`SyncDiff`].
+    pub async fn dir_sync(
+        &self,
+        dir_path: PathBuf,
+        previous_archive: &PrivateArchive,
+        wallet: &EvmWallet,
+    ) -> Result<(PrivateArchive, SyncDiff), UploadError> {
+        info!("Syncing private directory: {dir_path:?}");
+        let (files, diff) = sync_files(&dir_path, previous_archive.map(), |path| async move {
+            self.file_upload(path, wallet, None).await
+        })
+        .await?;
+
+        let mut archive = PrivateArchive::new();
+        for (path, data_map, metadata) in files {
+            archive.add_file(path, data_map, metadata);
+        }
+        Ok((archive, diff))
+    }
+
+    /// Sync a directory against a previous version of its public archive. See [`Client::dir_sync`].
+    pub async fn dir_sync_public(
+        &self,
+        dir_path: PathBuf,
+        previous_archive: &PublicArchive,
+        wallet: &EvmWallet,
+    ) -> Result<(PublicArchive, SyncDiff), UploadError> {
+        info!("Syncing public directory: {dir_path:?}");
+        let (files, diff) = sync_files(&dir_path, previous_archive.map(), |path| async move {
+            self.file_upload_public(path, wallet, None).await
+        })
+        .await?;
+
+        let mut archive = PublicArchive::new();
+        for (path, data_addr, metadata) in files {
+            archive.add_file(path, data_addr, metadata);
+        }
+        Ok((archive, diff))
+    }
+}
+
+// Walk the directory and compare every file with the previous archive, uploading the new and modified ones.
+async fn sync_files<A, F, Fut>(
+    dir_path: &Path,
+    previous: &HashMap<PathBuf, (A, Metadata)>,
+    upload: F,
+) -> Result<(Vec<(PathBuf, A, Metadata)>, SyncDiff), UploadError>
+where
+    A: Clone + Send,
+    F: Fn(PathBuf) -> Fut,
+    Fut: Future<Output = Result<A, UploadError>> + Send,
+{
+    let start = tokio::time::Instant::now();
+    let mut diff = SyncDiff::default();
+    let mut files = vec![];
+    let mut to_upload = vec![];
+    let mut seen = HashSet::new();
+
+    for entry in walkdir::WalkDir::new(dir_path) {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        let metadata = metadata_from_entry(&entry);
+        let path = entry.path().to_path_buf();
+        let rel_path = get_relative_file_path_from_abs_file_and_folder_path(&path, dir_path);
+        seen.insert(rel_path.clone());
+
+        match previous.get(&rel_path) {
+            Some((addr, previous_metadata)) if is_unchanged(previous_metadata, &metadata) => {
+                diff.unchanged.push(rel_path.clone());
+                files.push((rel_path, addr.clone(), previous_metadata.clone()));
+            }
+            Some(_) => {
+                diff.modified.push(rel_path.clone());
+                to_upload.push((path, rel_path, metadata));
+            }
+            None => {
+                diff.added.push(rel_path.clone());
+                to_upload.push((path, rel_path, metadata));
+            }
+        }
+    }
+
+    diff.removed = previous
+        .keys()
+        .filter(|path| !seen.contains(*path))
+        .cloned()
+        .collect();
+
+    let upload_tasks = to_upload.into_iter().map(|(path, rel_path, metadata)| {
+        let upload = upload(path.clone());
+        async move { (path, rel_path, metadata, upload.await) }
+    });
+    let uploads = process_tasks_with_max_concurrency(upload_tasks, *FILE_UPLOAD_BATCH_SIZE).await;
+    for (path, rel_path, metadata, maybe_file) in uploads {
+        match maybe_file {
+            Ok(addr) => files.push((rel_path, addr, metadata)),
+            Err(err) => {
+                error!("Failed to upload file: {path:?}: {err:?}");
+                return Err(err);
+            }
+        }
+    }
+
+    diff.added.sort();
+    diff.modified.sort();
+    diff.removed.sort();
+    diff.unchanged.sort();
+    info!(
+        "Sync of {dir_path:?} completed in {:?}: {} added, {} modified, {} removed, {} unchanged",
+        start.elapsed(),
+        diff.added.len(),
+        diff.modified.len(),
+        diff.removed.len(),
+        diff.unchanged.len()
+    );
+    Ok((files, diff))
+}
+
+// Files are considered unchanged when both their size and last modification time match.
+fn is_unchanged(previous: &Metadata, current: &Metadata) -> bool {
+    previous.size == current.size && previous.modified == current.modified
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::data::DataAddr;
+    use xor_name::XorName;
+
+    #[tokio::test]
+    async fn sync_uploads_only_new_and_modified_files() -> Result<(), Box<dyn std::error::Error>> {
+        let root = tempfile::tempdir()?;
+        let dir = root.path().join("photos");
+        std::fs::create_dir_all(&dir)?;
+        std::fs::write(dir.join("kept.txt"), b"kept")?;
+        std::fs::write(dir.join("changed.txt"), b"changed content")?;
+        std::fs::write(dir.join("new.txt"), b"new")?;
+
+        let kept_addr = XorName::from_content(b"kept");
+        let kept_metadata = metadata_from_entry(
+            &walkdir::WalkDir::new(dir.join("kept.txt"))
+                .into_iter()
+                .next()
+                .ok_or("missing entry")??,
+        );
+        let previous: HashMap<PathBuf, (DataAddr, Metadata)> = [
+            (
+                PathBuf::from("photos/kept.txt"),
+                (kept_addr, kept_metadata.clone()),
+            ),
+            (
+                PathBuf::from("photos/changed.txt"),
+                (XorName::from_content(b"old"), Metadata::new_with_size(3)),
+            ),
+            (
+                PathBuf::from("photos/gone.txt"),
+                (XorName::from_content(b"gone"), Metadata::new_with_size(4)),
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let uploaded = std::sync::Mutex::new(vec![]);
+        let (files, diff) = sync_files(&dir, &previous, |path| {
+            uploaded.lock().expect("lock").push(path.clone());
+            async move { Ok(XorName::from_content(path.to_string_lossy().as_bytes())) }
+        })
+        .await?;
+
+        assert_eq!(diff.added, vec![PathBuf::from("photos/new.txt")]);
+        assert_eq!(diff.modified, vec![PathBuf::from("photos/changed.txt")]);
+        assert_eq!(diff.removed, vec![PathBuf::from("photos/gone.txt")]);
+        assert_eq!(diff.unchanged, vec![PathBuf::from("photos/kept.txt")]);
+        assert_eq!(uploaded.lock().expect("lock").len(), 2);
+        assert_eq!(files.len(), 3);
+        assert!(files.contains(&(PathBuf::from("photos/kept.txt"), kept_addr, kept_metadata)));
+        Ok(())
+    }
+}
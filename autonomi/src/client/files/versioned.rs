@@ -0,0 +1,215 @@
+// Copyright 2024 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use ant_evm::EvmWallet;
+use ant_protocol::storage::ChunkAddress;
+use bls::{PublicKey, SecretKey};
+use xor_name::XorName;
+
+use super::archive_public::ArchiveAddr;
+use crate::client::graph::{GraphEntry, GraphEntryAddress, GraphError};
+use crate::client::pointer::{Pointer, PointerAddress, PointerError, PointerTarget};
+use crate::Client;
+
+/// Errors that can occur when publishing or reading a versioned archive.
+#[derive(Debug, thiserror::Error)]
+pub enum VersionedArchiveError {
+    #[error("Pointer error: {0}")]
+    Pointer(#[from] PointerError),
+    #[error("Graph error: {0}")]
+    Graph(#[from] GraphError),
+    #[error("The pointer does not point to an archive")]
+    NotAnArchive,
+    #[error("Version {0} of the archive is missing from the network")]
+    MissingVersion(u32),
+    #[error("The history of the archive is broken at version {0}")]
+    BrokenHistory(u32),
+}
+
+/// A named archive that can be updated while keeping a stable address.
+///
+/// The archive is owned by a key derived from the user's key and the name. That key owns a [`Pointer`]
+/// targeting the latest [`ArchiveAddr`], its counter being the latest version number. Every version is also
+/// recorded as a [`GraphEntry`] whose content is the archive address and whose parent is the previous version,
+/// so that older versions can still be listed. The graph entry of version `n` is owned by the key derived
+/// from the pointer owner with index `n`, which lets anyone walk the history from the pointer.
+#[derive(Debug, Clone)]
+pub struct VersionedArchive {
+    key: SecretKey,
+}
+
+/// A version of a [`VersionedArchive`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArchiveVersion {
+    /// The version number, starting at 0.
+    pub version: u32,
+    /// The address of the archive of this version.
+    pub archive: ArchiveAddr,
+    /// The graph entry recording this version.
+    pub entry: GraphEntryAddress,
+}
+
+impl VersionedArchive {
+    /// The versioned archive with the given name, owned by `owner`.
+    pub fn new(owner: &SecretKey, name: &str) -> Self {
+        let index = XorName::from_content(name.as_bytes());
+        Self {
+            key: owner.derive_child(&index.0),
+        }
+    }
+
+    /// The versioned archive owned by the given key.
+    pub fn from_secret_key(key: SecretKey) -> Self {
+        Self { key }
+    }
+
+    /// The stable address of the versioned archive.
+    pub fn address(&self) -> PointerAddress {
+        PointerAddress::from_owner(self.key.public_key())
+    }
+
+    fn version_key(&self, version: u32) -> SecretKey {
+        self.key.derive_child(&version.to_be_bytes())
+    }
+}
+
+fn version_owner(pointer_owner: &PublicKey, version: u32) -> PublicKey {
+    pointer_owner.derive_child(&version.to_be_bytes())
+}
+
+impl Client {
+    /// Publish `archive` as the new version of a versioned archive.
+    ///
+    /// The history is extended with a new [`GraphEntry`] before the pointer is moved to the archive.
+    pub async fn versioned_archive_publish(
+        &self,
+        versioned: &VersionedArchive,
+        archive: ArchiveAddr,
+        wallet: &EvmWallet,
+    ) -> Result<ArchiveVersion, VersionedArchiveError> {
+        let address = versioned.address();
+        let version = match self.pointer_get(address).await {
+            Ok(pointer) => pointer.count() + 1,
+            // the pointer was not found, this is the first version
+            Err(PointerError::PointerNotFound(_)) => 0,
+            Err(err) => return Err(err.into()),
+        };
+        info!("Publishing archive {archive:?} as version {version} of {address:?}");
+
+        let key = versioned.version_key(version);
+        let parents = match version.checked_sub(1) {
+            Some(previous) => vec![versioned.version_key(previous).public_key()],
+            None => vec![],
+        };
+        let entry = GraphEntry::new(key.public_key(), parents, archive.0, None, &key);
+        let entry_address = entry.address();
+        self.transaction_put(entry, wallet).await?;
+
+        let target = PointerTarget::ChunkAddress(ChunkAddress::new(archive));
+        if version == 0 {
+            let pointer = Pointer::new(versioned.key.public_key(), version, target, &versioned.key);
+            self.pointer_put(pointer, wallet).await?;
+        } else {
+            // moving an existing pointer is free
+            self.pointer_update(&versioned.key, target).await?;
+        }
+
+        Ok(ArchiveVersion {
+            version,
+            archive,
+            entry: entry_address,
+        })
+    }
+
+    /// Get the latest version of a versioned archive.
+    pub async fn versioned_archive_get(
+        &self,
+        address: PointerAddress,
+    ) -> Result<ArchiveVersion, VersionedArchiveError> {
+        let pointer = self.pointer_get(address).await?;
+        let PointerTarget::ChunkAddress(archive) = pointer.target() else {
+            return Err(VersionedArchiveError::NotAnArchive);
+        };
+        let entry_owner = version_owner(pointer.owner(), pointer.count());
+        Ok(ArchiveVersion {
+            version: pointer.count(),
+            archive: *archive.xorname(),
+            entry: GraphEntryAddress::from_owner(entry_owner),
+        })
+    }
+
+    /// List all the versions of a versioned archive, latest first.
+    pub async fn versioned_archive_history(
+        &self,
+        address: PointerAddress,
+    ) -> Result<Vec<ArchiveVersion>, VersionedArchiveError> {
+        let pointer = self.pointer_get(address).await?;
+        let mut versions = vec![];
+        let mut version = pointer.count();
+
+        loop {
+            let entry_owner = version_owner(pointer.owner(), version);
+            let entry_address = GraphEntryAddress::from_owner(entry_owner);
+            let entry = self
+                .transaction_get(entry_address)
+                .await
+                .map_err(|err| {
+                    error!("Failed to fetch version {version} of {address:?}: {err}");
+                    VersionedArchiveError::MissingVersion(version)
+                })?
+                .into_iter()
+                .find(|entry| entry.owner == entry_owner && entry.verify())
+                .ok_or(VersionedArchiveError::MissingVersion(version))?;
+
+            versions.push(ArchiveVersion {
+                version,
+                archive: XorName(entry.content),
+                entry: entry_address,
+            });
+
+            match (entry.parents.as_slice(), version.checked_sub(1)) {
+                ([], None) => break,
+                ([parent], Some(previous))
+                    if *parent == version_owner(pointer.owner(), previous) =>
+                {
+                    version = previous;
+                }
+                _ => return Err(VersionedArchiveError::BrokenHistory(version)),
+            }
+        }
+
+        Ok(versions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_owners_are_derived_from_the_pointer_owner() {
+        let user_key = SecretKey::random();
+        let versioned = VersionedArchive::new(&user_key, "site");
+        assert_eq!(
+            versioned.address(),
+            VersionedArchive::new(&user_key, "site").address()
+        );
+        assert_ne!(
+            versioned.address(),
+            VersionedArchive::new(&user_key, "blog").address()
+        );
+
+        let pointer_owner = versioned.key.public_key();
+        for version in [0, 1, 42] {
+            assert_eq!(
+                version_owner(&pointer_owner, version),
+                versioned.version_key(version).public_key()
+            );
+        }
+    }
+}
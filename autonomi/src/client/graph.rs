@@ -6,6 +6,10 @@
 // KIND, either express or implied. Please review the Licences for the specific language governing
 // permissions and limitations relating to use of the SAFE Network Software.
 
+mod walk;
+
+pub use walk::{EntryGraph, GraphNode, WalkDirection, WalkLimits, DEFAULT_MAX_WALKED_ENTRIES};
+
 use crate::client::data::PayError;
 use crate::client::Client;
 use crate::client::ClientEvent;
@@ -14,8 +18,8 @@ use crate::client::UploadSummary;
 use ant_evm::Amount;
 use ant_evm::AttoTokens;
 pub use ant_protocol::storage::GraphEntry;
-use ant_protocol::storage::GraphEntryAddress;
-pub use bls::SecretKey;
+pub use ant_protocol::storage::GraphEntryAddress;
+pub use bls::{PublicKey, SecretKey};
 
 use ant_evm::{EvmWallet, EvmWalletError};
 use ant_networking::{GetRecordCfg, NetworkError, PutRecordCfg, VerificationKind};
@@ -68,7 +72,8 @@ impl Client {
         // pay for the transaction
         let xor_name = address.xorname();
         debug!("Paying for transaction at address: {address:?}");
-        let payment_proofs = self
+        let started = ant_networking::time::Instant::now();
+        let (payment_proofs, transactions) = self
             .pay(std::iter::once(*xor_name), wallet)
             .await
             .inspect_err(|err| {
@@ -76,8 +81,8 @@ impl Client {
             })?;
 
         // make sure the transaction was paid for
-        let (proof, price) = match payment_proofs.get(xor_name) {
-            Some((proof, price)) => (proof, price),
+        let proof = match payment_proofs.get(xor_name) {
+            Some((proof, _price)) => proof,
             None => {
                 // transaction was skipped, meaning it was already paid for
                 error!("Transaction at address: {address:?} was already paid for");
@@ -110,6 +115,7 @@ impl Client {
         };
 
         // put the record to the network
+        let record_size = record.value.len() as u64;
         debug!("Storing transaction at address {address:?} to the network");
         self.network
             .put_record(record, &put_cfg)
@@ -119,15 +125,15 @@ impl Client {
             })?;
 
         // send client event
-        if let Some(channel) = self.client_event_sender.as_ref() {
-            let summary = UploadSummary {
-                record_count: 1,
-                tokens_spent: price.as_atto(),
-            };
-            if let Err(err) = channel.send(ClientEvent::UploadComplete(summary)).await {
-                error!("Failed to send client event: {err}");
-            }
-        }
+        let mut summary = UploadSummary {
+            record_count: 1,
+            bytes_uploaded: record_size,
+            ..Default::default()
+        };
+        summary.add_payments(&payment_proofs, transactions);
+        summary.elapsed = started.elapsed();
+        self.send_client_event(ClientEvent::UploadComplete(summary))
+            .await;
 
         Ok(())
     }
@@ -0,0 +1,425 @@
+// Copyright 2024 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+use ant_networking::{GetRecordError, NetworkError};
+
+use super::{GraphEntry, GraphEntryAddress, GraphError};
+use crate::client::data::CHUNK_DOWNLOAD_BATCH_SIZE;
+use crate::client::utils::process_tasks_with_max_concurrency;
+use crate::Client;
+
+/// The direction a graph is walked in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WalkDirection {
+    /// From the entries to their outputs.
+    Forward,
+    /// From the entries to their parents.
+    Backward,
+}
+
+/// Number of entries a walk fetches at most by default, see [`WalkLimits`].
+pub const DEFAULT_MAX_WALKED_ENTRIES: usize = 10_000;
+
+/// Bounds of a graph walk, the entries beyond them are listed as [`EntryGraph::unexplored`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WalkLimits {
+    /// Number of steps from the start beyond which entries are not fetched.
+    pub max_depth: usize,
+    /// Number of addresses fetched beyond which the walk stops.
+    pub max_entries: usize,
+}
+
+impl Default for WalkLimits {
+    fn default() -> Self {
+        Self {
+            max_depth: usize::MAX,
+            max_entries: DEFAULT_MAX_WALKED_ENTRIES,
+        }
+    }
+}
+
+/// The entries found at one address of a walked graph.
+#[derive(Debug, Clone)]
+pub struct GraphNode {
+    pub address: GraphEntryAddress,
+    /// Number of steps from the start of the walk.
+    pub depth: usize,
+    /// The valid entries stored at the address, more than one means the owner forked the graph there.
+    pub entries: Vec<GraphEntry>,
+}
+
+/// The part of a graph reached by walking it from an entry, see [`Client::graph_walk_forward`].
+///
+/// Edges always go in the direction of the walk: from an entry to its outputs when walking forward, from an entry
+/// to its parents when walking backward.
+#[derive(Debug, Clone)]
+pub struct EntryGraph {
+    start: GraphEntryAddress,
+    direction: WalkDirection,
+    /// The nodes in the order they were reached, the depth never decreases.
+    nodes: Vec<GraphNode>,
+    edges: Vec<(GraphEntryAddress, GraphEntryAddress)>,
+    edge_set: HashSet<(GraphEntryAddress, GraphEntryAddress)>,
+    missing: Vec<GraphEntryAddress>,
+    unexplored: Vec<GraphEntryAddress>,
+}
+
+impl EntryGraph {
+    fn new(start: GraphEntryAddress, direction: WalkDirection) -> Self {
+        Self {
+            start,
+            direction,
+            nodes: vec![],
+            edges: vec![],
+            edge_set: HashSet::new(),
+            missing: vec![],
+            unexplored: vec![],
+        }
+    }
+
+    pub fn start(&self) -> GraphEntryAddress {
+        self.start
+    }
+
+    pub fn direction(&self) -> WalkDirection {
+        self.direction
+    }
+
+    /// Iterate over the nodes in the order of the walk, closest to the start first.
+    pub fn iter(&self) -> impl Iterator<Item = &GraphNode> {
+        self.nodes.iter()
+    }
+
+    pub fn node(&self, address: &GraphEntryAddress) -> Option<&GraphNode> {
+        self.nodes.iter().find(|node| &node.address == address)
+    }
+
+    pub fn edges(&self) -> &[(GraphEntryAddress, GraphEntryAddress)] {
+        &self.edges
+    }
+
+    /// Addresses referenced by the walked entries with no entry stored at them yet.
+    pub fn missing(&self) -> &[GraphEntryAddress] {
+        &self.missing
+    }
+
+    /// Addresses reached by the walk but not fetched because of its [`WalkLimits`].
+    pub fn unexplored(&self) -> &[GraphEntryAddress] {
+        &self.unexplored
+    }
+
+    /// Whether the walk stopped at its [`WalkLimits`] before reaching the end of the graph.
+    pub fn is_truncated(&self) -> bool {
+        !self.unexplored.is_empty()
+    }
+
+    /// Addresses holding more than one entry.
+    pub fn forks(&self) -> Vec<GraphEntryAddress> {
+        self.nodes
+            .iter()
+            .filter(|node| node.entries.len() > 1)
+            .map(|node| node.address)
+            .collect()
+    }
+
+    /// Edges closing a cycle, a valid graph has none.
+    pub fn cycles(&self) -> Vec<(GraphEntryAddress, GraphEntryAddress)> {
+        let mut adjacency: HashMap<GraphEntryAddress, Vec<GraphEntryAddress>> = HashMap::new();
+        for (from, to) in &self.edges {
+            adjacency.entry(*from).or_default().push(*to);
+        }
+
+        // depth first search from the start, an edge to a node still on the path closes a cycle
+        let mut back_edges = vec![];
+        let mut on_path = HashSet::from([self.start]);
+        let mut done = HashSet::new();
+        let mut stack = vec![(self.start, 0)];
+        while let Some((address, next_child)) = stack.pop() {
+            let children = adjacency.get(&address).map(Vec::as_slice).unwrap_or(&[]);
+            let Some(child) = children.get(next_child) else {
+                on_path.remove(&address);
+                done.insert(address);
+                continue;
+            };
+            stack.push((address, next_child + 1));
+            if on_path.contains(child) {
+                back_edges.push((address, *child));
+            } else if !done.contains(child) {
+                on_path.insert(*child);
+                stack.push((*child, 0));
+            }
+        }
+        back_edges
+    }
+
+    /// Render the graph in the DOT format of Graphviz, for debugging.
+    ///
+    /// Forks and the edges closing cycles are drawn in red, missing entries dashed.
+    pub fn to_dot(&self) -> String {
+        let cycles: HashSet<_> = self.cycles().into_iter().collect();
+        let mut dot = String::from("digraph entries {\n");
+        for node in &self.nodes {
+            let hex = node.address.to_hex();
+            let mut label = hex[..6].to_string();
+            for entry in &node.entries {
+                let _ = write!(label, "\\ncontent {}", &hex::encode(entry.content)[..6]);
+            }
+            let color = if node.entries.len() > 1 {
+                ", color=red"
+            } else {
+                ""
+            };
+            let _ = writeln!(dot, "  \"{hex}\" [label=\"{label}\"{color}];");
+        }
+        for address in &self.missing {
+            let hex = address.to_hex();
+            let _ = writeln!(
+                dot,
+                "  \"{hex}\" [label=\"{}\\nmissing\", style=dashed];",
+                &hex[..6]
+            );
+        }
+        for edge @ (from, to) in &self.edges {
+            let color = if cycles.contains(edge) {
+                " [color=red]"
+            } else {
+                ""
+            };
+            let _ = writeln!(
+                dot,
+                "  \"{}\" -> \"{}\"{color};",
+                from.to_hex(),
+                to.to_hex()
+            );
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Add the entries found at an address, returns the addresses they lead to.
+    fn insert(
+        &mut self,
+        address: GraphEntryAddress,
+        depth: usize,
+        entries: Vec<GraphEntry>,
+    ) -> Vec<GraphEntryAddress> {
+        let mut next = vec![];
+        for entry in &entries {
+            let keys: Vec<_> = match self.direction {
+                WalkDirection::Forward => entry
+                    .outputs
+                    .iter()
+                    .flatten()
+                    .map(|(key, _)| *key)
+                    .collect(),
+                WalkDirection::Backward => entry.parents.clone(),
+            };
+            for key in keys {
+                let to = GraphEntryAddress::from_owner(key);
+                if self.edge_set.insert((address, to)) {
+                    self.edges.push((address, to));
+                    next.push(to);
+                }
+            }
+        }
+        self.nodes.push(GraphNode {
+            address,
+            depth,
+            entries,
+        });
+        next
+    }
+}
+
+impl Client {
+    /// Walk a graph from the entry at `start` to its outputs, their outputs and so on.
+    ///
+    /// The entries of each step are fetched concurrently. The walk stops at entries without outputs and at outputs
+    /// not stored yet, which are listed as [`EntryGraph::missing`]. Forks and cycles do not stop the walk, they are
+    /// reported by [`EntryGraph::forks`] and [`EntryGraph::cycles`].
+    ///
+    /// At most [`DEFAULT_MAX_WALKED_ENTRIES`] entries are fetched, see [`Client::graph_walk`] for other limits.
+    pub async fn graph_walk_forward(
+        &self,
+        start: GraphEntryAddress,
+    ) -> Result<EntryGraph, GraphError> {
+        self.graph_walk(start, WalkDirection::Forward, WalkLimits::default())
+            .await
+    }
+
+    /// Walk a graph from the entry at `start` to its parents, their parents and so on.
+    ///
+    /// Works like [`Client::graph_walk_forward`], in the other direction.
+    pub async fn graph_walk_backward(
+        &self,
+        start: GraphEntryAddress,
+    ) -> Result<EntryGraph, GraphError> {
+        self.graph_walk(start, WalkDirection::Backward, WalkLimits::default())
+            .await
+    }
+
+    /// Walk a graph from the entry at `start` in the given direction, within the given limits.
+    ///
+    /// The walk goes through the graph one depth at a time, the addresses it reaches beyond the limits are not
+    /// fetched and are listed as [`EntryGraph::unexplored`].
+    pub async fn graph_walk(
+        &self,
+        start: GraphEntryAddress,
+        direction: WalkDirection,
+        limits: WalkLimits,
+    ) -> Result<EntryGraph, GraphError> {
+        debug!("Walking the graph {direction:?} from {start:?} within {limits:?}");
+        let mut graph = EntryGraph::new(start, direction);
+        let mut visited = HashSet::from([start]);
+        let mut frontier = vec![start];
+        let mut fetched = 0;
+        let mut depth = 0;
+
+        while !frontier.is_empty() {
+            let allowed = if depth > limits.max_depth {
+                0
+            } else {
+                limits.max_entries.saturating_sub(fetched)
+            };
+            if frontier.len() > allowed {
+                graph.unexplored = frontier.split_off(allowed);
+                debug!(
+                    "Walk limits reached at depth {depth}, leaving {} addresses unexplored",
+                    graph.unexplored.len()
+                );
+            }
+            fetched += frontier.len();
+
+            let tasks = frontier
+                .iter()
+                .map(|address| async move { (*address, self.transaction_get(*address).await) });
+            let results =
+                process_tasks_with_max_concurrency(tasks, *CHUNK_DOWNLOAD_BATCH_SIZE).await;
+
+            let mut next = vec![];
+            for (address, result) in results {
+                let entries = match result {
+                    Ok(entries) => entries,
+                    Err(GraphError::Network(NetworkError::GetRecordError(
+                        GetRecordError::RecordNotFound,
+                    ))) if address != start => {
+                        graph.missing.push(address);
+                        continue;
+                    }
+                    Err(err) => {
+                        error!("Failed to get the graph entry at {address:?}: {err}");
+                        return Err(err);
+                    }
+                };
+                let entries: Vec<_> = entries
+                    .into_iter()
+                    .filter(|entry| entry.verify() && entry.address() == address)
+                    .collect();
+                if entries.is_empty() {
+                    error!("No valid graph entry at {address:?}");
+                    return Err(GraphError::FailedVerification);
+                }
+                next.extend(
+                    graph
+                        .insert(address, depth, entries)
+                        .into_iter()
+                        .filter(|to| visited.insert(*to)),
+                );
+            }
+            frontier = next;
+            depth += 1;
+            if !graph.unexplored.is_empty() {
+                graph.unexplored.append(&mut frontier);
+            }
+        }
+
+        debug!(
+            "Walked {} entries {direction:?} from {start:?}",
+            graph.nodes.len()
+        );
+        Ok(graph)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bls::SecretKey;
+
+    fn entry(key: &SecretKey, parents: &[&SecretKey], outputs: &[&SecretKey]) -> GraphEntry {
+        GraphEntry::new(
+            key.public_key(),
+            parents.iter().map(|parent| parent.public_key()).collect(),
+            [0; 32],
+            Some(
+                outputs
+                    .iter()
+                    .map(|output| (output.public_key(), [0; 32]))
+                    .collect(),
+            ),
+            key,
+        )
+    }
+
+    fn address(key: &SecretKey) -> GraphEntryAddress {
+        GraphEntryAddress::from_owner(key.public_key())
+    }
+
+    #[test]
+    fn diamonds_are_not_cycles_but_loops_are() {
+        let [a, b, c, d] = [(); 4].map(|_| SecretKey::random());
+
+        // a -> b, a -> c, b -> d, c -> d
+        let mut graph = EntryGraph::new(address(&a), WalkDirection::Forward);
+        let _ = graph.insert(address(&a), 0, vec![entry(&a, &[], &[&b, &c])]);
+        let _ = graph.insert(address(&b), 1, vec![entry(&b, &[&a], &[&d])]);
+        let _ = graph.insert(address(&c), 1, vec![entry(&c, &[&a], &[&d])]);
+        let next = graph.insert(address(&d), 2, vec![entry(&d, &[&b, &c], &[])]);
+        assert!(next.is_empty());
+        assert_eq!(graph.edges().len(), 4);
+        assert!(graph.cycles().is_empty());
+        assert!(graph.forks().is_empty());
+
+        // d -> a closes a loop, and a second entry at d forks the graph
+        let mut graph = EntryGraph::new(address(&a), WalkDirection::Forward);
+        let _ = graph.insert(address(&a), 0, vec![entry(&a, &[], &[&b])]);
+        let _ = graph.insert(address(&b), 1, vec![entry(&b, &[&a], &[&d])]);
+        let _ = graph.insert(
+            address(&d),
+            2,
+            vec![entry(&d, &[&b], &[&a]), entry(&d, &[&b], &[])],
+        );
+        assert_eq!(graph.cycles(), vec![(address(&d), address(&a))]);
+        assert_eq!(graph.forks(), vec![address(&d)]);
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph entries {"));
+        assert!(dot.contains(&format!(
+            "\"{}\" -> \"{}\" [color=red];",
+            address(&d).to_hex(),
+            address(&a).to_hex()
+        )));
+    }
+
+    #[test]
+    fn backward_walks_follow_parents() {
+        let [a, b] = [(); 2].map(|_| SecretKey::random());
+        let mut graph = EntryGraph::new(address(&b), WalkDirection::Backward);
+        // a fork at b with the same parent leads to a single edge
+        let next = graph.insert(
+            address(&b),
+            0,
+            vec![entry(&b, &[&a], &[]), entry(&b, &[&a], &[&a])],
+        );
+        assert_eq!(next, vec![address(&a)]);
+        assert_eq!(graph.edges(), &[(address(&b), address(&a))]);
+    }
+}
@@ -10,13 +10,16 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
 pub mod address;
+pub mod cache;
 pub mod payment;
 pub mod quote;
+pub mod receipts;
 
 pub mod data;
 pub mod files;
 pub mod graph;
 pub mod pointer;
+pub mod scratchpad;
 
 #[cfg(feature = "external-signer")]
 #[cfg_attr(docsrs, doc(cfg(feature = "external-signer")))]
@@ -34,12 +37,25 @@ mod utils;
 
 use ant_bootstrap::{BootstrapCacheConfig, BootstrapCacheStore, PeersArgs};
 pub use ant_evm::Amount;
-use ant_evm::EvmNetwork;
-use ant_networking::{interval, multiaddr_is_global, Network, NetworkBuilder, NetworkEvent};
+use ant_evm::{AttoTokens, EvmNetwork, ProofOfPayment, TxHash};
+use ant_networking::{
+    interval, multiaddr_is_global, Network, NetworkBuilder, NetworkEvent, TransportProtocol,
+};
 use ant_protocol::version::IDENTIFY_PROTOCOL_STR;
+use cache::ChunkCache;
+pub use cache::ChunkCacheConfig;
 use libp2p::{identity::Keypair, Multiaddr};
-use std::{collections::HashSet, sync::Arc, time::Duration};
+use payment::PaymentTransactions;
+use quote::QuoteSelectionPolicy;
+use receipts::ReceiptStore;
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::Arc,
+    time::Duration,
+};
 use tokio::sync::mpsc;
+use xor_name::XorName;
 
 /// Time before considering the connection timed out.
 pub const CONNECT_TIMEOUT_SECS: u64 = 10;
@@ -68,6 +84,9 @@ pub struct Client {
     pub(crate) network: Network,
     pub(crate) client_event_sender: Arc<Option<mpsc::Sender<ClientEvent>>>,
     pub(crate) evm_network: EvmNetwork,
+    pub(crate) chunk_cache: Option<Arc<ChunkCache>>,
+    pub(crate) quote_selection_policy: QuoteSelectionPolicy,
+    pub(crate) receipt_store: Option<Arc<ReceiptStore>>,
 }
 
 /// Configuration for [`Client::init_with_config`].
@@ -82,6 +101,22 @@ pub struct ClientConfig {
     ///
     /// If not provided, the client will use the default bootstrap peers.
     pub peers: Option<Vec<Multiaddr>>,
+
+    /// Cache the chunks fetched from the network on disk.
+    ///
+    /// Chunks are immutable and self-validating, so cached chunks are returned without querying the network.
+    /// If not provided, no chunks are cached.
+    pub chunk_cache: Option<ChunkCacheConfig>,
+
+    /// How the quotes to pay are chosen when uploading.
+    ///
+    /// Can be overridden per request with [`Client::get_store_quotes_with_policy`].
+    pub quote_selection_policy: QuoteSelectionPolicy,
+
+    /// Save the receipt of every payment made by the client in this directory.
+    ///
+    /// Expired payments are pruned from it when the client starts. If not provided, receipts are not saved.
+    pub receipts_dir: Option<PathBuf>,
 }
 
 impl Default for ClientConfig {
@@ -92,6 +127,25 @@ impl Default for ClientConfig {
             #[cfg(not(feature = "local"))]
             local: false,
             peers: None,
+            chunk_cache: None,
+            quote_selection_policy: Default::default(),
+            receipts_dir: None,
+        }
+    }
+}
+
+impl ClientConfig {
+    /// Configuration bootstrapping from a list of peers.
+    ///
+    /// If any of the provided peers is a global address, the client will not be local.
+    pub fn from_peers(peers: Vec<Multiaddr>) -> Self {
+        // Any global address makes the client non-local
+        let local = !peers.iter().any(multiaddr_is_global);
+
+        Self {
+            local,
+            peers: Some(peers),
+            ..Default::default()
         }
     }
 }
@@ -145,14 +199,7 @@ impl Client {
     /// # }
     /// ```
     pub async fn init_with_peers(peers: Vec<Multiaddr>) -> Result<Self, ConnectError> {
-        // Any global address makes the client non-local
-        let local = !peers.iter().any(multiaddr_is_global);
-
-        Self::init_with_config(ClientConfig {
-            local,
-            peers: Some(peers),
-        })
-        .await
+        Self::init_with_config(ClientConfig::from_peers(peers)).await
     }
 
     /// Initialize the client with the given configuration.
@@ -199,10 +246,32 @@ impl Client {
         receiver.await.expect("sender should not close")?;
         debug!("Enough peers were added to our routing table, initialization complete");
 
+        let chunk_cache = match config.chunk_cache {
+            Some(cache_config) => ChunkCache::open(cache_config)
+                .await
+                .inspect_err(|err| {
+                    warn!("Failed to open the chunk cache, continuing without it: {err}")
+                })
+                .ok()
+                .map(Arc::new),
+            None => None,
+        };
+
+        let receipt_store = config.receipts_dir.map(|dir| {
+            let store = ReceiptStore::new(dir);
+            if let Err(err) = store.prune_expired() {
+                warn!("Failed to prune the expired payment receipts: {err}");
+            }
+            Arc::new(store)
+        });
+
         Ok(Self {
             network,
             client_event_sender: Arc::new(None),
             evm_network: Default::default(),
+            chunk_cache,
+            quote_selection_policy: config.quote_selection_policy,
+            receipt_store,
         })
     }
 
@@ -258,6 +327,9 @@ impl Client {
             network,
             client_event_sender: Arc::new(None),
             evm_network: Default::default(),
+            chunk_cache: None,
+            quote_selection_policy: Default::default(),
+            receipt_store: None,
         })
     }
 
@@ -274,10 +346,48 @@ impl Client {
     pub fn set_evm_network(&mut self, evm_network: EvmNetwork) {
         self.evm_network = evm_network;
     }
+
+    /// Send an event to the receiver returned by [`Client::enable_client_events`], if any.
+    pub(crate) async fn send_client_event(&self, event: ClientEvent) {
+        if let Some(channel) = self.client_event_sender.as_ref() {
+            if let Err(err) = channel.send(event).await {
+                error!("Failed to send client event: {err:?}");
+            }
+        }
+    }
+
+    /// Send a progress event without waiting, the event is dropped when the receiver is not keeping up.
+    ///
+    /// Uploads must never wait on a slow receiver, only [`ClientEvent::UploadComplete`] is worth waiting for.
+    pub(crate) fn try_send_client_event(&self, event: ClientEvent) {
+        if let Some(channel) = self.client_event_sender.as_ref() {
+            match channel.try_send(event) {
+                Ok(()) => {}
+                Err(mpsc::error::TrySendError::Full(event)) => {
+                    trace!("Client event channel is full, dropping {event:?}");
+                }
+                Err(err) => error!("Failed to send client event: {err:?}"),
+            }
+        }
+    }
+
+    /// Set how the quotes to pay are chosen when uploading.
+    pub fn set_quote_selection_policy(&mut self, policy: QuoteSelectionPolicy) {
+        self.quote_selection_policy = policy;
+    }
+
+    /// The store the receipts of the payments made by the client are saved to, if any.
+    ///
+    /// See [`ClientConfig::receipts_dir`].
+    pub fn receipt_store(&self) -> Option<&ReceiptStore> {
+        self.receipt_store.as_deref()
+    }
 }
 
 fn build_client_and_run_swarm(local: bool) -> (Network, mpsc::Receiver<NetworkEvent>) {
     let mut network_builder = NetworkBuilder::new(Keypair::generate_ed25519(), local);
+    // The client does not listen, enabling all the transports lets it reach nodes whatever they listen on.
+    network_builder.transports(TransportProtocol::ALL);
 
     if let Ok(mut config) = BootstrapCacheConfig::default_config() {
         if local {
@@ -361,12 +471,68 @@ async fn handle_event_receiver(
 /// Events that can be broadcasted by the client.
 #[derive(Debug, Clone)]
 pub enum ClientEvent {
+    /// The quotes to store a chunk are about to be paid, for the given total price.
+    ChunkQuoted {
+        address: XorName,
+        price: Amount,
+    },
+    /// A chunk was paid for, in the given transaction.
+    ///
+    /// The transaction is `None` when none of the quotes of the chunk had to be paid.
+    ChunkPaid {
+        address: XorName,
+        tx_hash: Option<TxHash>,
+    },
+    /// A chunk was stored on the nodes it was paid to, which proved they store it.
+    ChunkVerified {
+        address: XorName,
+    },
     UploadComplete(UploadSummary),
 }
 
 /// Summary of an upload operation.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct UploadSummary {
     pub record_count: usize,
     pub tokens_spent: Amount,
+    /// Gas paid for the payment transactions, in wei.
+    pub gas_spent: Amount,
+    /// Hashes of the payment transactions.
+    pub tx_hashes: Vec<TxHash>,
+    /// Price paid for each chunk.
+    pub chunk_prices: HashMap<XorName, Amount>,
+    /// Number of chunk uploads retried after a failure.
+    pub retries: usize,
+    /// Size of the uploaded records, in bytes.
+    pub bytes_uploaded: u64,
+    /// Time taken by the upload.
+    pub elapsed: Duration,
+}
+
+impl UploadSummary {
+    /// Add the payments made for some chunks, along with the transactions that paid them.
+    pub(crate) fn add_payments<'a>(
+        &mut self,
+        payments: impl IntoIterator<Item = (&'a XorName, &'a (ProofOfPayment, AttoTokens))>,
+        transactions: PaymentTransactions,
+    ) {
+        for (xor_name, (_, price)) in payments {
+            self.tokens_spent += price.as_atto();
+            let _ = self.chunk_prices.insert(*xor_name, price.as_atto());
+        }
+        self.gas_spent += transactions.gas_spent;
+        self.tx_hashes.extend(transactions.tx_hashes);
+    }
+
+    /// Combine the summary of another upload into this one.
+    pub fn merge(&mut self, other: UploadSummary) {
+        self.record_count += other.record_count;
+        self.tokens_spent += other.tokens_spent;
+        self.gas_spent += other.gas_spent;
+        self.tx_hashes.extend(other.tx_hashes);
+        self.chunk_prices.extend(other.chunk_prices);
+        self.retries += other.retries;
+        self.bytes_uploaded += other.bytes_uploaded;
+        self.elapsed += other.elapsed;
+    }
 }
@@ -1,7 +1,7 @@
 use crate::client::data::PayError;
 use crate::client::quote::StoreQuote;
 use crate::Client;
-use ant_evm::{AttoTokens, EncodedPeerId, EvmWallet, ProofOfPayment};
+use ant_evm::{Amount, AttoTokens, EncodedPeerId, EvmWallet, ProofOfPayment, TxHash};
 use std::collections::HashMap;
 use xor_name::XorName;
 
@@ -35,6 +35,14 @@ pub fn receipt_from_store_quotes(quotes: StoreQuote) -> Receipt {
     receipt
 }
 
+/// The transactions sent to make a payment.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct PaymentTransactions {
+    pub(crate) tx_hashes: Vec<TxHash>,
+    /// Gas paid for the transactions, in wei.
+    pub(crate) gas_spent: Amount,
+}
+
 /// Payment options for data payments.
 #[derive(Clone)]
 pub enum PaymentOption {
@@ -65,13 +73,11 @@ impl Client {
         &self,
         content_addrs: impl Iterator<Item = XorName> + Clone,
         payment_option: PaymentOption,
-    ) -> Result<Receipt, PayError> {
+    ) -> Result<(Receipt, PaymentTransactions), PayError> {
         match payment_option {
-            PaymentOption::Wallet(wallet) => {
-                let receipt = self.pay(content_addrs, &wallet).await?;
-                Ok(receipt)
-            }
-            PaymentOption::Receipt(receipt) => Ok(receipt),
+            PaymentOption::Wallet(wallet) => self.pay(content_addrs, &wallet).await,
+            // paid beforehand, no transaction is sent
+            PaymentOption::Receipt(receipt) => Ok((receipt, PaymentTransactions::default())),
         }
     }
 }
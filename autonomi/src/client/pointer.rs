@@ -1,11 +1,12 @@
 use crate::client::data::PayError;
 use crate::client::Client;
-use tracing::{debug, error, trace};
+use tracing::{debug, error, trace, warn};
 
 use ant_evm::{Amount, AttoTokens, EvmWallet, EvmWalletError};
-use ant_networking::{GetRecordCfg, NetworkError, PutRecordCfg, VerificationKind};
+use ant_networking::{GetRecordCfg, GetRecordError, NetworkError, PutRecordCfg, VerificationKind};
+pub use ant_protocol::storage::{Pointer, PointerAddress, PointerTarget};
 use ant_protocol::{
-    storage::{try_serialize_record, Pointer, PointerAddress, RecordKind, RetryStrategy},
+    storage::{try_deserialize_record, try_serialize_record, RecordKind, RetryStrategy},
     NetworkAddress,
 };
 use bls::SecretKey;
@@ -31,22 +32,96 @@ pub enum PointerError {
     InvalidQuote,
     #[error("Pointer already exists at this address: {0:?}")]
     PointerAlreadyExists(PointerAddress),
+    #[error("Pointer not found at this address: {0:?}")]
+    PointerNotFound(PointerAddress),
 }
 
 impl Client {
     /// Get a pointer from the network
+    ///
+    /// When the nodes hold different versions of the pointer, the validly signed one with the highest counter is
+    /// returned.
     pub async fn pointer_get(&self, address: PointerAddress) -> Result<Pointer, PointerError> {
         let key = NetworkAddress::from_pointer_address(address).to_record_key();
-        let record = self.network.get_local_record(&key).await?;
+        let get_cfg = GetRecordCfg {
+            get_quorum: Quorum::Majority,
+            retry_strategy: Some(RetryStrategy::Quick),
+            target_record: None,
+            expected_holders: Default::default(),
+            is_register: false,
+        };
 
-        match record {
-            Some(record) => {
-                let (_, pointer): (Vec<u8>, Pointer) = rmp_serde::from_slice(&record.value)
-                    .map_err(|_| PointerError::Serialization)?;
-                Ok(pointer)
+        let pointers = match self.network.get_record_from_network(key, &get_cfg).await {
+            Ok(record) => vec![try_deserialize_record::<Pointer>(&record)
+                .map_err(|_| PointerError::Serialization)?],
+            Err(NetworkError::GetRecordError(GetRecordError::SplitRecord { result_map })) => {
+                debug!("Got multiple versions of the pointer at {address:?}");
+                result_map
+                    .values()
+                    .filter_map(|(record, _)| try_deserialize_record::<Pointer>(record).ok())
+                    .collect()
             }
-            None => Err(PointerError::Corrupt),
-        }
+            Err(NetworkError::GetRecordError(GetRecordError::RecordNotFound)) => {
+                return Err(PointerError::PointerNotFound(address));
+            }
+            Err(err) => {
+                error!("Failed to get pointer at {address:?} from the network: {err}");
+                return Err(err.into());
+            }
+        };
+
+        latest_pointer(pointers, address).ok_or(PointerError::Corrupt)
+    }
+
+    /// Point an existing pointer to a new target.
+    ///
+    /// The counter of the pointer is bumped and the pointer signed again with `key`, its owner's key.
+    /// Updates are free, the nodes only accept them for a pointer they already store and with a higher counter.
+    pub async fn pointer_update(
+        &self,
+        key: &SecretKey,
+        target: PointerTarget,
+    ) -> Result<Pointer, PointerError> {
+        let address = PointerAddress::from_owner(key.public_key());
+        let current = self.pointer_get(address).await?;
+        let pointer = Pointer::new(key.public_key(), current.count() + 1, target, key);
+
+        let record = Record {
+            key: NetworkAddress::from_pointer_address(address).to_record_key(),
+            value: try_serialize_record(&pointer, RecordKind::Pointer)
+                .map_err(|_| PointerError::Serialization)?
+                .to_vec(),
+            publisher: None,
+            expires: None,
+        };
+
+        // nodes still holding the previous version make reads split, which is expected until they are updated
+        let get_cfg = GetRecordCfg {
+            get_quorum: Quorum::Majority,
+            retry_strategy: None,
+            target_record: None,
+            expected_holders: Default::default(),
+            is_register: false,
+        };
+        let put_cfg = PutRecordCfg {
+            put_quorum: Quorum::Majority,
+            retry_strategy: Some(RetryStrategy::Balanced),
+            use_put_record_to: None,
+            verification: Some((VerificationKind::Crdt, get_cfg)),
+        };
+
+        debug!(
+            "Updating pointer at address {address:?} to counter {}",
+            pointer.count()
+        );
+        self.network
+            .put_record(record, &put_cfg)
+            .await
+            .inspect_err(|err| {
+                error!("Failed to put record - pointer update {address:?} to the network: {err}")
+            })?;
+
+        Ok(pointer)
     }
 
     /// Store a pointer on the network
@@ -60,7 +135,7 @@ impl Client {
         // pay for the pointer storage
         let xor_name = *address.xorname();
         debug!("Paying for pointer at address: {address:?}");
-        let payment_proofs = self
+        let (payment_proofs, _) = self
             .pay(std::iter::once(xor_name), wallet)
             .await
             .inspect_err(|err| {
@@ -133,3 +208,45 @@ impl Client {
         Ok(total_cost)
     }
 }
+
+/// The validly signed pointer of `address` with the highest counter.
+fn latest_pointer(pointers: Vec<Pointer>, address: PointerAddress) -> Option<Pointer> {
+    pointers
+        .into_iter()
+        .filter(|pointer| {
+            let valid = pointer.verify() && pointer.network_address() == address;
+            if !valid {
+                warn!("Ignoring invalid pointer fetched for {address:?}");
+            }
+            valid
+        })
+        .max_by_key(|pointer| pointer.count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ant_protocol::storage::ChunkAddress;
+    use xor_name::XorName;
+
+    #[test]
+    fn latest_valid_pointer_is_chosen() {
+        let key = SecretKey::random();
+        let address = PointerAddress::from_owner(key.public_key());
+        let target = |n: u8| PointerTarget::ChunkAddress(ChunkAddress::new(XorName([n; 32])));
+
+        let old = Pointer::new(key.public_key(), 1, target(1), &key);
+        let latest = Pointer::new(key.public_key(), 2, target(2), &key);
+        // a higher counter signed by someone else must be ignored
+        let forged = Pointer::new(key.public_key(), 3, target(3), &SecretKey::random());
+
+        assert_eq!(
+            latest_pointer(vec![old.clone(), forged, latest.clone()], address),
+            Some(latest)
+        );
+        let other_key = SecretKey::random();
+        let other = Pointer::new(other_key.public_key(), 4, target(4), &other_key);
+        assert_eq!(latest_pointer(vec![other, old.clone()], address), Some(old));
+        assert_eq!(latest_pointer(vec![], address), None);
+    }
+}
@@ -13,9 +13,97 @@ use ant_evm::{Amount, EvmNetwork, PaymentQuote, QuotePayment, QuotingMetrics};
 use ant_networking::{Network, NetworkError};
 use ant_protocol::{storage::ChunkAddress, NetworkAddress, CLOSE_GROUP_SIZE};
 use libp2p::PeerId;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use xor_name::XorName;
 
+/// The number of quotes needed to pay for an address.
+const MINIMUM_QUOTES_TO_PAY: usize = 5;
+
+/// How the quotes to pay for an address are chosen among the ones of its close nodes.
+///
+/// Set for all the payments of a client with [`crate::ClientConfig`] or [`Client::set_quote_selection_policy`],
+/// or for a single request with [`Client::get_store_quotes_with_policy`].
+///
+/// Whatever the policy, the proof of payment of an address always holds 5 quotes, the policy only decides
+/// which nodes they come from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuoteSelectionPolicy {
+    candidates_per_address: usize,
+    prefer_long_live_time: bool,
+    exclude_bad_nodes: bool,
+    max_price_per_chunk: Option<Amount>,
+}
+
+impl Default for QuoteSelectionPolicy {
+    fn default() -> Self {
+        Self {
+            candidates_per_address: MINIMUM_QUOTES_TO_PAY,
+            prefer_long_live_time: false,
+            exclude_bad_nodes: false,
+            max_price_per_chunk: None,
+        }
+    }
+}
+
+impl QuoteSelectionPolicy {
+    /// Choose the quotes among the `candidates` cheapest ones of each address.
+    ///
+    /// Only matters along with [`QuoteSelectionPolicy::with_prefer_long_live_time`], the cheapest quotes being
+    /// picked otherwise. Raised to 5 when lower.
+    pub fn with_candidates_per_address(mut self, candidates: usize) -> Self {
+        self.candidates_per_address = candidates.max(MINIMUM_QUOTES_TO_PAY);
+        self
+    }
+
+    /// Pick the quotes of the nodes that have been running the longest among the candidates rather than the
+    /// cheapest ones.
+    pub fn with_prefer_long_live_time(mut self, prefer_long_live_time: bool) -> Self {
+        self.prefer_long_live_time = prefer_long_live_time;
+        self
+    }
+
+    /// Ignore the quotes of the nodes this client has flagged as bad.
+    pub fn with_exclude_bad_nodes(mut self, exclude_bad_nodes: bool) -> Self {
+        self.exclude_bad_nodes = exclude_bad_nodes;
+        self
+    }
+
+    /// Fail with [`CostError::PriceAboveCap`] instead of paying more than `cap` to store a chunk.
+    pub fn with_max_price_per_chunk(mut self, cap: Amount) -> Self {
+        self.max_price_per_chunk = Some(cap);
+        self
+    }
+
+    /// Pick the `MINIMUM_QUOTES_TO_PAY` quotes to pay among the priced quotes of an address.
+    fn select(&self, mut prices: Vec<(PeerId, PaymentQuote, Amount)>) -> QuoteForAddress {
+        prices.sort_by_key(|(_, _, price)| *price);
+        prices.truncate(self.candidates_per_address);
+        if self.prefer_long_live_time {
+            prices.sort_by(|(_, quote_a, price_a), (_, quote_b, price_b)| {
+                quote_b
+                    .quoting_metrics
+                    .live_time
+                    .cmp(&quote_a.quoting_metrics.live_time)
+                    .then(price_a.cmp(price_b))
+            });
+        }
+        prices.truncate(MINIMUM_QUOTES_TO_PAY);
+
+        // don't pay for the cheapest 2 quotes but include them
+        prices.sort_by_key(|(_, _, price)| *price);
+        for (_, _, price) in prices.iter_mut().take(2) {
+            *price = Amount::ZERO;
+        }
+
+        QuoteForAddress(prices)
+    }
+
+    /// The cap of the policy if the quotes chosen for an address are priced above it.
+    fn exceeded_cap(&self, quote: &QuoteForAddress) -> Option<Amount> {
+        self.max_price_per_chunk.filter(|cap| quote.price() > *cap)
+    }
+}
+
 /// A quote for a single address
 pub struct QuoteForAddress(pub(crate) Vec<(PeerId, PaymentQuote, Amount)>);
 
@@ -53,9 +141,20 @@ impl StoreQuote {
 }
 
 impl Client {
+    /// Get the quotes to pay for storing the content addresses, chosen by the quote selection policy of the client.
     pub async fn get_store_quotes(
         &self,
         content_addrs: impl Iterator<Item = XorName>,
+    ) -> Result<StoreQuote, CostError> {
+        self.get_store_quotes_with_policy(content_addrs, &self.quote_selection_policy)
+            .await
+    }
+
+    /// Get the quotes to pay for storing the content addresses, chosen by the given policy.
+    pub async fn get_store_quotes_with_policy(
+        &self,
+        content_addrs: impl Iterator<Item = XorName>,
+        policy: &QuoteSelectionPolicy,
     ) -> Result<StoreQuote, CostError> {
         // get all quotes from nodes
         let futures: Vec<_> = content_addrs
@@ -69,13 +168,18 @@ impl Client {
 
         let mut rate_limiter = RateLimiter::new();
 
-        for (content_addr, raw_quotes) in raw_quotes_per_addr {
+        for (content_addr, mut raw_quotes) in raw_quotes_per_addr {
             // FIXME: find better way to deal with paid content addrs and feedback to the user
             // assume that content addr is already paid for and uploaded
             if raw_quotes.is_empty() {
                 continue;
             }
 
+            if policy.exclude_bad_nodes {
+                let bad_nodes = bad_nodes(&self.network, &raw_quotes).await;
+                raw_quotes = exclude_bad_nodes(raw_quotes, &bad_nodes);
+            }
+
             // ask smart contract for the market price
             let quoting_metrics: Vec<QuotingMetrics> = raw_quotes
                 .clone()
@@ -90,52 +194,66 @@ impl Client {
             )
             .await?;
 
-            let mut prices: Vec<(PeerId, PaymentQuote, Amount)> = all_prices
+            let prices: Vec<(PeerId, PaymentQuote, Amount)> = all_prices
                 .into_iter()
                 .zip(raw_quotes.into_iter())
                 .map(|(price, (peer, quote))| (peer, quote, price))
                 .collect();
 
-            // sort by price
-            prices.sort_by(|(_, _, price_a), (_, _, price_b)| price_a.cmp(price_b));
-
-            // we need at least 5 valid quotes to pay for the data
-            const MINIMUM_QUOTES_TO_PAY: usize = 5;
-            match &prices[..] {
-                [first, second, third, fourth, fifth, ..] => {
-                    let (p1, q1, _) = first;
-                    let (p2, q2, _) = second;
-
-                    // don't pay for the cheapest 2 quotes but include them
-                    let first = (*p1, q1.clone(), Amount::ZERO);
-                    let second = (*p2, q2.clone(), Amount::ZERO);
-
-                    // pay for the rest
-                    quotes_to_pay_per_addr.insert(
-                        content_addr,
-                        QuoteForAddress(vec![
-                            first,
-                            second,
-                            third.clone(),
-                            fourth.clone(),
-                            fifth.clone(),
-                        ]),
-                    );
-                }
-                _ => {
-                    return Err(CostError::NotEnoughNodeQuotes(
-                        content_addr,
-                        prices.len(),
-                        MINIMUM_QUOTES_TO_PAY,
-                    ));
-                }
+            if prices.len() < MINIMUM_QUOTES_TO_PAY {
+                return Err(CostError::NotEnoughNodeQuotes(
+                    content_addr,
+                    prices.len(),
+                    MINIMUM_QUOTES_TO_PAY,
+                ));
+            }
+
+            let quote = policy.select(prices);
+            if let Some(cap) = policy.exceeded_cap(&quote) {
+                return Err(CostError::PriceAboveCap(content_addr, quote.price(), cap));
             }
+            quotes_to_pay_per_addr.insert(content_addr, quote);
         }
 
         Ok(StoreQuote(quotes_to_pay_per_addr))
     }
 }
 
+/// The peers among the quoting ones that are flagged as bad by this client.
+async fn bad_nodes(network: &Network, quotes: &[(PeerId, PaymentQuote)]) -> HashSet<PeerId> {
+    let mut bad_nodes = HashSet::new();
+    for (peer, _) in quotes {
+        match network
+            .is_peer_shunned(NetworkAddress::from_peer(*peer))
+            .await
+        {
+            Ok(true) => {
+                let _ = bad_nodes.insert(*peer);
+            }
+            Ok(false) => {}
+            Err(err) => warn!("Could not check whether {peer:?} is a bad node: {err:?}"),
+        }
+    }
+    bad_nodes
+}
+
+/// Drop the quotes of the bad nodes.
+fn exclude_bad_nodes(
+    quotes: Vec<(PeerId, PaymentQuote)>,
+    bad_nodes: &HashSet<PeerId>,
+) -> Vec<(PeerId, PaymentQuote)> {
+    quotes
+        .into_iter()
+        .filter(|(peer, _)| {
+            let bad = bad_nodes.contains(peer);
+            if bad {
+                info!("Ignoring the quote of bad node {peer:?}");
+            }
+            !bad
+        })
+        .collect()
+}
+
 /// Fetch a store quote for a content address.
 async fn fetch_store_quote(
     network: &Network,
@@ -218,3 +336,109 @@ async fn get_market_price_with_rate_limiter_and_retries(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Priced quotes from nodes with the given prices and live times.
+    fn priced_quotes(prices_and_live_times: &[(u64, u64)]) -> Vec<(PeerId, PaymentQuote, Amount)> {
+        prices_and_live_times
+            .iter()
+            .map(|(price, live_time)| {
+                let mut quote = PaymentQuote::zero();
+                quote.quoting_metrics.live_time = *live_time;
+                (PeerId::random(), quote, Amount::from(*price))
+            })
+            .collect()
+    }
+
+    /// Prices and live times of 8 nodes, the live times being unrelated to the prices.
+    const NODES: [(u64, u64); 8] = [
+        (50, 4),
+        (10, 1),
+        (80, 200),
+        (30, 2),
+        (60, 100),
+        (20, 50),
+        (70, 5),
+        (40, 3),
+    ];
+
+    fn paid_prices(quote: &QuoteForAddress) -> Vec<Amount> {
+        quote.0.iter().map(|(_, _, price)| *price).collect()
+    }
+
+    #[test]
+    fn quotes_are_selected_by_policy() {
+        // the 5 cheapest quotes, the two cheapest ones not being paid
+        let quote = QuoteSelectionPolicy::default().select(priced_quotes(&NODES));
+        assert_eq!(
+            paid_prices(&quote),
+            [0, 0, 30, 40, 50].map(Amount::from).to_vec()
+        );
+
+        // the longest running nodes among the 6 cheapest: the node quoting 60 is chosen over the cheaper node
+        // quoting 10, which has been running for the shortest time
+        let policy = QuoteSelectionPolicy::default()
+            .with_candidates_per_address(6)
+            .with_prefer_long_live_time(true);
+        let quote = policy.select(priced_quotes(&NODES));
+        assert_eq!(
+            paid_prices(&quote),
+            [0, 0, 40, 50, 60].map(Amount::from).to_vec()
+        );
+
+        // the proof always holds the same number of quotes
+        let policy = QuoteSelectionPolicy::default().with_candidates_per_address(8);
+        assert_eq!(
+            policy.select(priced_quotes(&NODES[..6])).0.len(),
+            MINIMUM_QUOTES_TO_PAY
+        );
+        assert_eq!(
+            policy.select(priced_quotes(&NODES)).0.len(),
+            MINIMUM_QUOTES_TO_PAY
+        );
+
+        let policy = QuoteSelectionPolicy::default().with_candidates_per_address(2);
+        assert_eq!(policy.candidates_per_address, MINIMUM_QUOTES_TO_PAY);
+    }
+
+    #[test]
+    fn price_above_the_cap_is_refused() {
+        // 30 + 40 + 50 are paid
+        let quote = QuoteSelectionPolicy::default().select(priced_quotes(&NODES));
+        assert_eq!(quote.price(), Amount::from(120));
+
+        assert_eq!(QuoteSelectionPolicy::default().exceeded_cap(&quote), None);
+        let policy = QuoteSelectionPolicy::default().with_max_price_per_chunk(Amount::from(120));
+        assert_eq!(policy.exceeded_cap(&quote), None);
+        let policy = QuoteSelectionPolicy::default().with_max_price_per_chunk(Amount::from(119));
+        assert_eq!(policy.exceeded_cap(&quote), Some(Amount::from(119)));
+    }
+
+    #[test]
+    fn bad_nodes_are_excluded() {
+        let quotes: Vec<(PeerId, PaymentQuote)> = priced_quotes(&NODES)
+            .into_iter()
+            .map(|(peer, quote, _)| (peer, quote))
+            .collect();
+        // the two longest running nodes are bad
+        let bad_nodes: HashSet<PeerId> = quotes
+            .iter()
+            .filter(|(_, quote)| quote.quoting_metrics.live_time >= 100)
+            .map(|(peer, _)| *peer)
+            .collect();
+        assert_eq!(bad_nodes.len(), 2);
+
+        let good_quotes = exclude_bad_nodes(quotes.clone(), &bad_nodes);
+        assert_eq!(good_quotes.len(), NODES.len() - 2);
+        assert!(good_quotes
+            .iter()
+            .all(|(peer, _)| !bad_nodes.contains(peer)));
+        assert_eq!(
+            exclude_bad_nodes(quotes, &HashSet::new()).len(),
+            NODES.len()
+        );
+    }
+}
@@ -0,0 +1,270 @@
+// Copyright 2024 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use std::{
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use ant_evm::AttoTokens;
+use serde::{Deserialize, Serialize};
+use xor_name::XorName;
+
+use crate::client::payment::{PaymentOption, Receipt};
+
+/// Extension of the receipt files within a [`ReceiptStore`].
+const RECEIPT_FILE_EXTENSION: &str = "receipt";
+
+/// Errors that can occur while reading or writing a [`ReceiptStore`].
+#[derive(Debug, thiserror::Error)]
+pub enum ReceiptError {
+    #[error("IO failure")]
+    IoError(#[from] std::io::Error),
+    #[error("Failed to serialize receipt")]
+    Serialization(#[from] rmp_serde::encode::Error),
+    #[error("Failed to deserialize receipt")]
+    Deserialization(#[from] rmp_serde::decode::Error),
+    #[error("No receipt found with id {0}")]
+    NotFound(String),
+}
+
+/// A payment receipt persisted by a [`ReceiptStore`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StoredReceipt {
+    /// Identifier of the receipt within its store.
+    pub id: String,
+    /// When the payment was made.
+    pub paid_at: SystemTime,
+    /// The proofs of payment and the amounts paid for each chunk.
+    pub receipt: Receipt,
+}
+
+impl StoredReceipt {
+    /// The total amount paid for the chunks of the receipt.
+    pub fn total_paid(&self) -> AttoTokens {
+        AttoTokens::from_atto(
+            self.receipt
+                .values()
+                .map(|(_, price)| price.as_atto())
+                .sum(),
+        )
+    }
+
+    /// Whether every payment of the receipt has expired and can no longer be used to store data.
+    pub fn has_expired(&self) -> bool {
+        self.receipt.values().all(|(proof, _)| proof.has_expired())
+    }
+}
+
+/// Only the payments that have not expired yet are kept, expired ones would be rejected by the nodes.
+impl From<StoredReceipt> for PaymentOption {
+    fn from(value: StoredReceipt) -> Self {
+        PaymentOption::Receipt(
+            value
+                .receipt
+                .into_iter()
+                .filter(|(_, (proof, _))| !proof.has_expired())
+                .collect(),
+        )
+    }
+}
+
+/// On-disk store of the receipts of the payments made by the client.
+///
+/// Each receipt is written to its own file as soon as it is paid, so payments are never lost even if the upload
+/// using them fails. A stored receipt can be used again through [`PaymentOption`] until its quotes expire, after
+/// which [`ReceiptStore::prune_expired`] removes it.
+#[derive(Debug, Clone)]
+pub struct ReceiptStore {
+    dir: PathBuf,
+}
+
+impl ReceiptStore {
+    /// Store receipts in `dir`, which is created on the first save.
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// The directory the receipts are stored in.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Persist a new receipt, returns its stored form.
+    pub fn save(&self, receipt: &Receipt) -> Result<StoredReceipt, ReceiptError> {
+        let paid_at = SystemTime::now();
+        // Ids sort chronologically, the digest tells apart the receipts paid within the same second.
+        let digest = XorName::from_content(&rmp_serde::to_vec(receipt)?);
+        let secs = paid_at
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        let stored = StoredReceipt {
+            id: format!("{secs}-{}", hex::encode(&digest.0[..4])),
+            paid_at,
+            receipt: receipt.clone(),
+        };
+        self.write(&stored)?;
+        debug!(
+            "Saved receipt {} for {} chunks to {:?}",
+            stored.id,
+            receipt.len(),
+            self.dir
+        );
+        Ok(stored)
+    }
+
+    /// Load the receipt with the given id.
+    pub fn load(&self, id: &str) -> Result<StoredReceipt, ReceiptError> {
+        match std::fs::read(self.path_of(id)) {
+            Ok(bytes) => Ok(rmp_serde::from_slice(&bytes)?),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                Err(ReceiptError::NotFound(id.to_string()))
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// All the stored receipts, oldest first.
+    ///
+    /// Files that cannot be read as receipts are skipped.
+    pub fn list(&self) -> Result<Vec<StoredReceipt>, ReceiptError> {
+        let mut receipts = vec![];
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(receipts),
+            Err(err) => return Err(err.into()),
+        };
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(RECEIPT_FILE_EXTENSION) {
+                continue;
+            }
+            let receipt = std::fs::read(&path)
+                .map_err(ReceiptError::from)
+                .and_then(|bytes| Ok(rmp_serde::from_slice::<StoredReceipt>(&bytes)?));
+            match receipt {
+                Ok(receipt) => receipts.push(receipt),
+                Err(err) => warn!("Skipping unreadable receipt file {path:?}: {err}"),
+            }
+        }
+        receipts.sort_by(|a, b| a.paid_at.cmp(&b.paid_at).then_with(|| a.id.cmp(&b.id)));
+        Ok(receipts)
+    }
+
+    /// Remove the expired payments from the stored receipts, and the receipts left without any payment.
+    ///
+    /// Returns the number of chunk payments removed.
+    pub fn prune_expired(&self) -> Result<usize, ReceiptError> {
+        let mut pruned = 0;
+        for mut stored in self.list()? {
+            let before = stored.receipt.len();
+            stored.receipt.retain(|_, (proof, _)| !proof.has_expired());
+            let expired = before - stored.receipt.len();
+            if expired == 0 {
+                continue;
+            }
+            pruned += expired;
+            if stored.receipt.is_empty() {
+                debug!("Removing expired receipt {}", stored.id);
+                std::fs::remove_file(self.path_of(&stored.id))?;
+            } else {
+                self.write(&stored)?;
+            }
+        }
+        if pruned > 0 {
+            info!(
+                "Pruned {pruned} expired payments from the receipts in {:?}",
+                self.dir
+            );
+        }
+        Ok(pruned)
+    }
+
+    // Write to a temporary file first, so that a crash mid-write never leaves a corrupt receipt behind.
+    fn write(&self, stored: &StoredReceipt) -> Result<(), ReceiptError> {
+        std::fs::create_dir_all(&self.dir)?;
+        let bytes = rmp_serde::to_vec(stored)?;
+        let path = self.path_of(&stored.id);
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, bytes)?;
+        std::fs::rename(&tmp_path, &path)?;
+        Ok(())
+    }
+
+    fn path_of(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{id}.{RECEIPT_FILE_EXTENSION}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ant_evm::{EncodedPeerId, PaymentQuote, ProofOfPayment, QUOTE_EXPIRATION_SECS};
+    use libp2p::PeerId;
+    use std::time::Duration;
+
+    fn proof_for(xor_name: XorName, timestamp: SystemTime) -> ProofOfPayment {
+        let mut quote = PaymentQuote::test_dummy(xor_name);
+        quote.timestamp = timestamp;
+        ProofOfPayment {
+            peer_quotes: vec![(EncodedPeerId::from(PeerId::random()), quote)],
+        }
+    }
+
+    #[test]
+    fn receipts_are_persisted_and_pruned_once_expired() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = tempfile::tempdir()?;
+        let store = ReceiptStore::new(dir.path().to_path_buf());
+
+        let fresh = XorName::random(&mut rand::thread_rng());
+        let expired = XorName::random(&mut rand::thread_rng());
+        let long_ago = SystemTime::now() - Duration::from_secs(2 * QUOTE_EXPIRATION_SECS);
+        let receipt: Receipt = [
+            (
+                fresh,
+                (proof_for(fresh, SystemTime::now()), AttoTokens::from_u64(1)),
+            ),
+            (
+                expired,
+                (proof_for(expired, long_ago), AttoTokens::from_u64(2)),
+            ),
+        ]
+        .into_iter()
+        .collect();
+        let only_expired: Receipt = [(
+            expired,
+            (proof_for(expired, long_ago), AttoTokens::from_u64(3)),
+        )]
+        .into_iter()
+        .collect();
+
+        let stored = store.save(&receipt)?;
+        let stale = store.save(&only_expired)?;
+        assert_eq!(store.load(&stored.id)?, stored);
+        assert_eq!(stored.total_paid(), AttoTokens::from_u64(3));
+        assert!(stale.has_expired());
+        assert_eq!(store.list()?.len(), 2);
+
+        let PaymentOption::Receipt(usable) = PaymentOption::from(stored.clone()) else {
+            panic!("a stored receipt should convert to a receipt payment option");
+        };
+        assert_eq!(usable.keys().collect::<Vec<_>>(), vec![&fresh]);
+
+        assert_eq!(store.prune_expired()?, 2);
+        let remaining = store.list()?;
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, stored.id);
+        assert_eq!(remaining[0].receipt, usable);
+        assert!(matches!(
+            store.load(&stale.id),
+            Err(ReceiptError::NotFound(_))
+        ));
+        Ok(())
+    }
+}
@@ -316,14 +316,15 @@ impl Client {
 
         let reg_xor = address.xorname();
         debug!("Paying for register at address: {address}");
-        let payment_proofs = self
+        let started = ant_networking::time::Instant::now();
+        let (payment_proofs, transactions) = self
             .pay(std::iter::once(reg_xor), wallet)
             .await
             .inspect_err(|err| {
                 error!("Failed to pay for register at address: {address} : {err}")
             })?;
-        let (proof, price) = if let Some((proof, price)) = payment_proofs.get(&reg_xor) {
-            (proof, price)
+        let proof = if let Some((proof, _price)) = payment_proofs.get(&reg_xor) {
+            proof
         } else {
             // register was skipped, meaning it was already paid for
             error!("Register at address: {address} was already paid for");
@@ -360,6 +361,7 @@ impl Client {
             verification: Some((VerificationKind::Crdt, get_cfg)),
         };
 
+        let record_size = record.value.len() as u64;
         debug!("Storing register at address {address} to the network");
         self.network
             .put_record(record, &put_cfg)
@@ -368,15 +370,15 @@ impl Client {
                 error!("Failed to put record - register {address} to the network: {err}")
             })?;
 
-        if let Some(channel) = self.client_event_sender.as_ref() {
-            let summary = UploadSummary {
-                record_count: 1,
-                tokens_spent: price.as_atto(),
-            };
-            if let Err(err) = channel.send(ClientEvent::UploadComplete(summary)).await {
-                error!("Failed to send client event: {err}");
-            }
-        }
+        let mut summary = UploadSummary {
+            record_count: 1,
+            bytes_uploaded: record_size,
+            ..Default::default()
+        };
+        summary.add_payments(&payment_proofs, transactions);
+        summary.elapsed = started.elapsed();
+        self.send_client_event(ClientEvent::UploadComplete(summary))
+            .await;
 
         Ok(register)
     }
@@ -0,0 +1,632 @@
+// Copyright 2024 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under The General Public License (GPL), version 3.
+// Unless required by applicable law or agreed to in writing, the SAFE Network Software distributed
+// under the GPL Licence is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied. Please review the Licences for the specific language governing
+// permissions and limitations relating to use of the SAFE Network Software.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::client::data::PayError;
+use crate::client::payment::PaymentOption;
+use crate::client::Client;
+
+use ant_evm::{Amount, AttoTokens};
+use ant_networking::{GetRecordCfg, GetRecordError, NetworkError, PutRecordCfg, VerificationKind};
+pub use ant_protocol::storage::{Scratchpad, ScratchpadAddress};
+use ant_protocol::{
+    storage::{try_deserialize_record, try_serialize_record, RecordKind, RetryStrategy},
+    Bytes, NetworkAddress,
+};
+use bls::{PublicKey, SecretKey};
+use libp2p::kad::{Quorum, Record};
+use serde::{de::DeserializeOwned, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::data::CostError;
+
+/// Identifies the type of the content of a scratchpad, so that apps can tell their scratchpads apart.
+pub type DataEncoding = u64;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ScratchpadError {
+    #[error("Cost error: {0}")]
+    Cost(#[from] CostError),
+    #[error("Network error")]
+    Network(#[from] NetworkError),
+    #[error("Protocol: {0}")]
+    Protocol(#[from] ant_protocol::Error),
+    #[error("Payment failure occurred during scratchpad creation.")]
+    Pay(#[from] PayError),
+    #[error("Serialization error")]
+    Serialization,
+    #[error("Content error: {0}")]
+    Content(#[from] ContentError),
+    #[error("Scratchpad could not be verified (corrupt)")]
+    Corrupt,
+    #[error("Scratchpad already exists at this address: {0:?}")]
+    ScratchpadAlreadyExists(ScratchpadAddress),
+    #[error("Scratchpad not found at this address: {0:?}")]
+    ScratchpadNotFound(ScratchpadAddress),
+    #[error("Scratchpad at {0:?} kept being updated concurrently, giving up")]
+    UpdateConflict(ScratchpadAddress),
+    #[error("Update of the scratchpad at {0:?} could not be confirmed by all its holders")]
+    UpdateUnconfirmed(ScratchpadAddress),
+}
+
+/// Errors that can occur when reading or writing the typed content of a scratchpad.
+#[derive(Debug, thiserror::Error)]
+pub enum ContentError {
+    #[error("Scratchpad holds data encoded as {found}, expected {expected}")]
+    DataEncodingMismatch {
+        expected: DataEncoding,
+        found: DataEncoding,
+    },
+    #[error("Failed to decrypt the scratchpad content")]
+    Decryption,
+    #[error("Failed to serialize the scratchpad content: {0}")]
+    Serialization(#[from] rmp_serde::encode::Error),
+    #[error("Failed to deserialize the scratchpad content: {0}")]
+    Deserialization(#[from] rmp_serde::decode::Error),
+}
+
+/// The data encoding of the given app or type name.
+///
+/// The encoding is the start of the SHA-256 digest of the name, so it never changes across builds and platforms.
+pub fn data_encoding_from_name(name: &str) -> DataEncoding {
+    let digest = Sha256::digest(name.as_bytes());
+    let mut encoding = [0; 8];
+    encoding.copy_from_slice(&digest[..8]);
+    DataEncoding::from_be_bytes(encoding)
+}
+
+/// A type stored in scratchpads, serialized with MessagePack.
+pub trait ScratchpadContent: Serialize + DeserializeOwned {
+    /// Name the data encoding of the type is derived from, it should be unique to the app and the type.
+    const ENCODING_NAME: &'static str;
+
+    /// The data encoding of the scratchpads holding this type.
+    fn data_encoding() -> DataEncoding {
+        data_encoding_from_name(Self::ENCODING_NAME)
+    }
+}
+
+/// Names of the known data encodings, to describe the content of scratchpads.
+#[derive(Debug, Clone, Default)]
+pub struct DataEncodingRegistry {
+    names: HashMap<DataEncoding, String>,
+}
+
+impl DataEncodingRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the data encoding of a name, returns the encoding.
+    pub fn register(&mut self, name: &str) -> DataEncoding {
+        let encoding = data_encoding_from_name(name);
+        let _ = self.names.insert(encoding, name.to_string());
+        encoding
+    }
+
+    /// Register the data encoding of a type, returns the encoding.
+    pub fn register_type<T: ScratchpadContent>(&mut self) -> DataEncoding {
+        self.register(T::ENCODING_NAME)
+    }
+
+    /// The name of a registered data encoding.
+    pub fn name(&self, encoding: DataEncoding) -> Option<&str> {
+        self.names.get(&encoding).map(String::as_str)
+    }
+}
+
+impl Client {
+    /// Get the latest version of a scratchpad from the network.
+    ///
+    /// When the nodes hold different versions, the valid one with the highest counter is returned. Its data is still
+    /// encrypted, see [`Scratchpad::decrypt_data`].
+    pub async fn scratchpad_get(
+        &self,
+        address: &ScratchpadAddress,
+    ) -> Result<Scratchpad, ScratchpadError> {
+        let pads = self.scratchpad_versions(address, Quorum::Majority).await?;
+        latest_scratchpad(pads, address).ok_or(ScratchpadError::Corrupt)
+    }
+
+    /// The versions of a scratchpad held by the nodes answering the query.
+    ///
+    /// With [`Quorum::All`], a version held by any of the nodes is returned, even if the others agree on another.
+    async fn scratchpad_versions(
+        &self,
+        address: &ScratchpadAddress,
+        quorum: Quorum,
+    ) -> Result<Vec<Scratchpad>, ScratchpadError> {
+        let network_address = NetworkAddress::from_scratchpad_address(*address);
+        let key = network_address.to_record_key();
+        let get_cfg = GetRecordCfg {
+            get_quorum: quorum,
+            retry_strategy: None,
+            target_record: None,
+            expected_holders: HashSet::new(),
+            is_register: false,
+        };
+
+        let pads = match self.network.get_record_from_network(key, &get_cfg).await {
+            Ok(record)
+            | Err(NetworkError::GetRecordError(GetRecordError::NotEnoughCopies {
+                record, ..
+            })) => vec![try_deserialize_record::<Scratchpad>(&record)
+                .map_err(|_| ScratchpadError::Serialization)?],
+            Err(NetworkError::GetRecordError(GetRecordError::SplitRecord { result_map })) => {
+                debug!("Got multiple versions of the scratchpad at {address:?}");
+                result_map
+                    .values()
+                    .filter_map(|(record, _)| try_deserialize_record::<Scratchpad>(record).ok())
+                    .collect()
+            }
+            Err(NetworkError::GetRecordError(GetRecordError::RecordNotFound)) => {
+                return Err(ScratchpadError::ScratchpadNotFound(*address));
+            }
+            Err(err) => {
+                warn!("Failed to fetch scratchpad {network_address:?} from network: {err}");
+                return Err(err.into());
+            }
+        };
+        Ok(pads)
+    }
+
+    /// Get and decrypt the content of the scratchpad owned by `key`, as the given type.
+    pub async fn scratchpad_get_typed<T: ScratchpadContent>(
+        &self,
+        key: &SecretKey,
+    ) -> Result<T, ScratchpadError> {
+        let pad = self
+            .scratchpad_get(&ScratchpadAddress::new(key.public_key()))
+            .await?;
+        Ok(decode_content(&pad, key)?)
+    }
+
+    /// Create the scratchpad owned by `key` with the given content, paying for it.
+    ///
+    /// Returns the scratchpad and the amount paid.
+    pub async fn scratchpad_create(
+        &self,
+        key: &SecretKey,
+        data_encoding: DataEncoding,
+        data: Bytes,
+        payment_option: PaymentOption,
+    ) -> Result<(Scratchpad, AttoTokens), ScratchpadError> {
+        let address = ScratchpadAddress::new(key.public_key());
+        match self.scratchpad_get(&address).await {
+            Ok(_) => return Err(ScratchpadError::ScratchpadAlreadyExists(address)),
+            Err(ScratchpadError::ScratchpadNotFound(_)) => {}
+            Err(err) => return Err(err),
+        }
+
+        let mut pad = Scratchpad::new(key.public_key(), data_encoding);
+        let _ = pad.update_and_sign(data, key);
+
+        let (receipt, _) = self
+            .pay_for_content_addrs(std::iter::once(pad.xorname()), payment_option)
+            .await
+            .inspect_err(|err| {
+                error!("Failed to pay for new scratchpad at addr: {address:?} : {err}")
+            })?;
+        let (proof, price) = match receipt.values().next() {
+            Some(proof) => proof,
+            None => return Err(ScratchpadError::ScratchpadAlreadyExists(address)),
+        };
+
+        let record = Record {
+            key: pad.network_address().to_record_key(),
+            value: try_serialize_record(&(proof, &pad), RecordKind::ScratchpadWithPayment)
+                .map_err(|_| ScratchpadError::Serialization)?
+                .to_vec(),
+            publisher: None,
+            expires: None,
+        };
+        debug!("Creating scratchpad at {address:?}");
+        self.network
+            .put_record(record, &scratchpad_put_cfg())
+            .await
+            .inspect_err(|err| {
+                error!("Failed to put scratchpad {address:?} to the network with err: {err:?}")
+            })?;
+
+        Ok((pad, *price))
+    }
+
+    /// Create the scratchpad owned by `key` holding the given typed content, paying for it.
+    pub async fn scratchpad_create_typed<T: ScratchpadContent>(
+        &self,
+        key: &SecretKey,
+        content: &T,
+        payment_option: PaymentOption,
+    ) -> Result<(Scratchpad, AttoTokens), ScratchpadError> {
+        let data = Bytes::from(rmp_serde::to_vec(content).map_err(ContentError::from)?);
+        self.scratchpad_create(key, T::data_encoding(), data, payment_option)
+            .await
+    }
+
+    /// Update the content of the scratchpad owned by `key`, for free.
+    ///
+    /// `update` is given the current decrypted content and returns the new one. If another writer updates the
+    /// scratchpad at the same time, the latest version is fetched again and `update` applied to it, so that no
+    /// update is silently lost. Gives up with [`ScratchpadError::UpdateConflict`] if the conflicts persist.
+    pub async fn scratchpad_update<F>(
+        &self,
+        key: &SecretKey,
+        mut update: F,
+    ) -> Result<Scratchpad, ScratchpadError>
+    where
+        F: FnMut(Bytes) -> Bytes,
+    {
+        self.scratchpad_read_modify_write(key, None, |data| Ok(update(data)))
+            .await
+    }
+
+    /// Update the typed content of the scratchpad owned by `key`, for free.
+    ///
+    /// Works like [`Client::scratchpad_update`].
+    pub async fn scratchpad_update_typed<T, F>(
+        &self,
+        key: &SecretKey,
+        mut update: F,
+    ) -> Result<Scratchpad, ScratchpadError>
+    where
+        T: ScratchpadContent,
+        F: FnMut(T) -> T,
+    {
+        self.scratchpad_read_modify_write(key, Some(T::data_encoding()), |data| {
+            let content = update(rmp_serde::from_slice(&data)?);
+            Ok(Bytes::from(rmp_serde::to_vec(&content)?))
+        })
+        .await
+    }
+
+    /// Get the cost of creating the scratchpad of `owner`.
+    pub async fn scratchpad_cost(&self, owner: &PublicKey) -> Result<AttoTokens, ScratchpadError> {
+        trace!("Getting cost for scratchpad of {owner:?}");
+        let xor = ScratchpadAddress::new(*owner).xorname();
+        let store_quote = self.get_store_quotes(std::iter::once(xor)).await?;
+        let total_cost = AttoTokens::from_atto(
+            store_quote
+                .0
+                .values()
+                .map(|quote| quote.price())
+                .sum::<Amount>(),
+        );
+        debug!("Calculated the cost to create scratchpad of {owner:?} is {total_cost}");
+        Ok(total_cost)
+    }
+
+    /// Apply `update` to the latest version of the scratchpad until a version based on the latest one is stored.
+    ///
+    /// Nodes only accept versions with a higher counter than theirs. When two writers race from the same version,
+    /// the nodes end up split between their versions at the same counter. After writing, the versions held by all
+    /// the nodes are read back until they reached the counter of the written version. Every writer then resolves
+    /// the race the same way as readers do: the writer of the version readers pick is done, the others apply
+    /// their update again on top of it.
+    async fn scratchpad_read_modify_write<F>(
+        &self,
+        key: &SecretKey,
+        expected_encoding: Option<DataEncoding>,
+        mut update: F,
+    ) -> Result<Scratchpad, ScratchpadError>
+    where
+        F: FnMut(Bytes) -> Result<Bytes, ContentError>,
+    {
+        let address = ScratchpadAddress::new(key.public_key());
+        let mut backoff = RetryStrategy::Quick.backoff().into_iter();
+        let mut current = self.scratchpad_get(&address).await?;
+
+        loop {
+            if let Some(expected) = expected_encoding {
+                check_data_encoding(&current, expected)?;
+            }
+            let data = current.decrypt_data(key)?;
+
+            let mut pad = current.clone();
+            let _ = pad.update_and_sign(update(data)?, key);
+            let record = Record {
+                key: pad.network_address().to_record_key(),
+                value: try_serialize_record(&pad, RecordKind::Scratchpad)
+                    .map_err(|_| ScratchpadError::Serialization)?
+                    .to_vec(),
+                publisher: None,
+                expires: None,
+            };
+            debug!(
+                "Updating scratchpad at {address:?} from counter {}",
+                current.count()
+            );
+            let put_result = self.network.put_record(record, &scratchpad_put_cfg()).await;
+
+            // read back from all the holders to tell whether this version or a concurrent one won
+            current = loop {
+                let versions = self.scratchpad_versions(&address, Quorum::All).await?;
+                match write_outcome(&pad, versions) {
+                    WriteOutcome::Stored => return Ok(pad),
+                    WriteOutcome::Conflict(winner) => break *winner,
+                    WriteOutcome::Pending => {
+                        debug!(
+                            "Scratchpad at {address:?} not yet at counter {} on all its holders",
+                            pad.count()
+                        );
+                    }
+                }
+                match backoff.next() {
+                    Some(Some(delay)) => ant_networking::sleep(delay).await,
+                    _ => {
+                        put_result.inspect_err(|err| {
+                            error!("Failed to put scratchpad {address:?} to the network with err: {err:?}")
+                        })?;
+                        return Err(ScratchpadError::UpdateUnconfirmed(address));
+                    }
+                }
+            };
+
+            warn!(
+                "Scratchpad at {address:?} was concurrently updated to counter {}, retrying",
+                current.count()
+            );
+            match backoff.next() {
+                Some(Some(delay)) => ant_networking::sleep(delay).await,
+                _ => return Err(ScratchpadError::UpdateConflict(address)),
+            }
+        }
+    }
+}
+
+/// What a writer learns from the versions of a scratchpad read back after writing `pad`.
+#[derive(Debug, PartialEq)]
+enum WriteOutcome {
+    /// `pad` is the version readers resolve to, or a newer version was built on it.
+    Stored,
+    /// Some holders are still behind the counter of `pad`.
+    Pending,
+    /// Another version won over `pad`, the update has to be applied again on top of it.
+    Conflict(Box<Scratchpad>),
+}
+
+fn write_outcome(pad: &Scratchpad, versions: Vec<Scratchpad>) -> WriteOutcome {
+    let versions: Vec<Scratchpad> = versions
+        .into_iter()
+        .filter(|version| is_valid_version(version, pad.address()))
+        .collect();
+
+    // a concurrent version at the same counter, whichever one readers pick wins
+    if versions
+        .iter()
+        .any(|version| version.count() == pad.count() && version != pad)
+    {
+        return match latest_scratchpad(versions, pad.address()) {
+            Some(winner) if winner != *pad => WriteOutcome::Conflict(Box::new(winner)),
+            _ => WriteOutcome::Stored,
+        };
+    }
+
+    if versions.iter().any(|version| version.count() > pad.count())
+        || (!versions.is_empty() && versions.iter().all(|version| version == pad))
+    {
+        WriteOutcome::Stored
+    } else {
+        WriteOutcome::Pending
+    }
+}
+
+fn scratchpad_put_cfg() -> PutRecordCfg {
+    PutRecordCfg {
+        put_quorum: Quorum::Majority,
+        retry_strategy: Some(RetryStrategy::Balanced),
+        use_put_record_to: None,
+        verification: Some((
+            VerificationKind::Crdt,
+            GetRecordCfg {
+                get_quorum: Quorum::Majority,
+                retry_strategy: None,
+                target_record: None,
+                expected_holders: HashSet::new(),
+                is_register: false,
+            },
+        )),
+    }
+}
+
+/// The valid scratchpad of `address` with the highest counter.
+///
+/// Versions with the same counter are told apart by their content, so that all the clients pick the same one.
+fn latest_scratchpad(pads: Vec<Scratchpad>, address: &ScratchpadAddress) -> Option<Scratchpad> {
+    pads.into_iter()
+        .filter(|pad| is_valid_version(pad, address))
+        .max_by_key(|pad| (pad.count(), pad.encrypted_data_hash()))
+}
+
+fn is_valid_version(pad: &Scratchpad, address: &ScratchpadAddress) -> bool {
+    let valid = pad.is_valid() && pad.address() == address;
+    if !valid {
+        warn!("Ignoring invalid scratchpad fetched for {address:?}");
+    }
+    valid
+}
+
+fn check_data_encoding(pad: &Scratchpad, expected: DataEncoding) -> Result<(), ContentError> {
+    if pad.data_encoding() != expected {
+        return Err(ContentError::DataEncodingMismatch {
+            expected,
+            found: pad.data_encoding(),
+        });
+    }
+    Ok(())
+}
+
+fn decode_content<T: ScratchpadContent>(
+    pad: &Scratchpad,
+    key: &SecretKey,
+) -> Result<T, ContentError> {
+    check_data_encoding(pad, T::data_encoding())?;
+    let data = pad.decrypt_data(key).map_err(|err| {
+        error!("Failed to decrypt scratchpad {:?}: {err}", pad.address());
+        ContentError::Decryption
+    })?;
+    Ok(rmp_serde::from_slice(&data)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Settings {
+        theme: String,
+    }
+
+    impl ScratchpadContent for Settings {
+        const ENCODING_NAME: &'static str = "test settings";
+    }
+
+    fn pad(key: &SecretKey, data: &'static [u8], updates: usize) -> Scratchpad {
+        let mut pad = Scratchpad::new(key.public_key(), 0);
+        for _ in 0..updates {
+            let _ = pad.update_and_sign(Bytes::from_static(data), key);
+        }
+        pad
+    }
+
+    #[test]
+    fn latest_valid_scratchpad_is_chosen() {
+        let key = SecretKey::random();
+        let address = ScratchpadAddress::new(key.public_key());
+        let old = pad(&key, b"old", 1);
+        let latest = pad(&key, b"latest", 2);
+        // a higher counter signed by someone else must be ignored
+        let mut forged = pad(&key, b"forged", 2);
+        let _ = forged.update_and_sign(Bytes::from_static(b"forged"), &SecretKey::random());
+
+        assert_eq!(
+            latest_scratchpad(vec![old.clone(), forged, latest.clone()], &address),
+            Some(latest.clone())
+        );
+
+        // concurrent versions with the same counter resolve the same way whatever the order
+        let concurrent = pad(&key, b"concurrent", 2);
+        assert_eq!(
+            latest_scratchpad(vec![latest.clone(), concurrent.clone()], &address),
+            latest_scratchpad(vec![concurrent, latest], &address)
+        );
+        assert_eq!(latest_scratchpad(vec![], &address), None);
+    }
+
+    /// Apply an update appending `suffix` to the data of `base`, like a writer does.
+    fn append(base: &Scratchpad, key: &SecretKey, suffix: &[u8]) -> Scratchpad {
+        let mut data = base.decrypt_data(key).expect("decrypt").to_vec();
+        data.extend_from_slice(suffix);
+        let mut pad = base.clone();
+        let _ = pad.update_and_sign(Bytes::from(data), key);
+        pad
+    }
+
+    /// A node keeps the first version it receives and only replaces it with a higher counter.
+    fn store(node: &mut Scratchpad, pad: &Scratchpad) {
+        if pad.count() > node.count() {
+            *node = pad.clone();
+        }
+    }
+
+    #[test]
+    fn racing_writers_never_lose_an_update() {
+        let key = SecretKey::random();
+        let base = pad(&key, b"base", 1);
+        let mut nodes = vec![base.clone(); 5];
+
+        // both writers start from the same version, the nodes keep whichever reaches them first
+        let a = append(&base, &key, b"+a");
+        let b = append(&base, &key, b"+b");
+        for node in &mut nodes[..3] {
+            store(node, &a);
+        }
+        for node in nodes.iter_mut().rev() {
+            store(node, &b);
+        }
+        // a writer reading back from a majority only would see its own version
+        assert!(nodes[..3].iter().all(|node| *node == a));
+        assert!(nodes[3..].iter().all(|node| *node == b));
+
+        // reading back from all the holders, exactly one of them has to write again
+        let (winner, loser, loser_suffix) = match (
+            write_outcome(&a, nodes.clone()),
+            write_outcome(&b, nodes.clone()),
+        ) {
+            (WriteOutcome::Stored, WriteOutcome::Conflict(winner)) => (*winner, b, b"+b"),
+            (WriteOutcome::Conflict(winner), WriteOutcome::Stored) => (*winner, a, b"+a"),
+            outcomes => panic!("Unexpected outcomes {outcomes:?}"),
+        };
+        assert_ne!(winner, loser);
+        assert_eq!(
+            latest_scratchpad(nodes.clone(), base.address()),
+            Some(winner.clone())
+        );
+
+        let retried = append(&winner, &key, loser_suffix);
+        for node in &mut nodes {
+            store(node, &retried);
+        }
+        assert_eq!(write_outcome(&retried, nodes.clone()), WriteOutcome::Stored);
+        // the winner is not fooled by the newer version built on it
+        assert_eq!(write_outcome(&winner, nodes.clone()), WriteOutcome::Stored);
+
+        let data = retried.decrypt_data(&key).expect("decrypt");
+        assert!(data == b"base+a+b".as_slice() || data == b"base+b+a".as_slice());
+    }
+
+    #[test]
+    fn writer_waits_for_the_holders_to_reach_its_counter() {
+        let key = SecretKey::random();
+        let base = pad(&key, b"base", 1);
+        let updated = append(&base, &key, b"+a");
+
+        // a stale read must not be taken as a success
+        assert_eq!(
+            write_outcome(&updated, vec![base.clone(), updated.clone()]),
+            WriteOutcome::Pending
+        );
+        assert_eq!(write_outcome(&updated, vec![base]), WriteOutcome::Pending);
+        assert_eq!(write_outcome(&updated, vec![]), WriteOutcome::Pending);
+        assert_eq!(
+            write_outcome(&updated, vec![updated.clone()]),
+            WriteOutcome::Stored
+        );
+    }
+
+    #[test]
+    fn typed_content_checks_the_data_encoding() -> Result<(), ContentError> {
+        let key = SecretKey::random();
+        let settings = Settings {
+            theme: "dark".to_string(),
+        };
+        let mut pad = Scratchpad::new(key.public_key(), Settings::data_encoding());
+        let _ = pad.update_and_sign(Bytes::from(rmp_serde::to_vec(&settings)?), &key);
+        assert_eq!(decode_content::<Settings>(&pad, &key)?, settings);
+
+        let untyped = Scratchpad::new(key.public_key(), 0);
+        assert!(matches!(
+            decode_content::<Settings>(&untyped, &key),
+            Err(ContentError::DataEncodingMismatch { found: 0, .. })
+        ));
+
+        let mut registry = DataEncodingRegistry::new();
+        let encoding = registry.register_type::<Settings>();
+        assert_eq!(encoding, Settings::data_encoding());
+        assert_eq!(registry.name(encoding), Some("test settings"));
+        assert_eq!(registry.name(0), None);
+
+        // the encodings of the scratchpads already stored must never change
+        assert_eq!(
+            data_encoding_from_name("ant-cli scratchpad"),
+            18296507786797595699
+        );
+        Ok(())
+    }
+}
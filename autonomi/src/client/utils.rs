@@ -6,7 +6,7 @@
 // KIND, either express or implied. Please review the Licences for the specific language governing
 // permissions and limitations relating to use of the SAFE Network Software.
 
-use crate::client::payment::{receipt_from_store_quotes, Receipt};
+use crate::client::payment::{receipt_from_store_quotes, PaymentTransactions, Receipt};
 use ant_evm::{EvmWallet, ProofOfPayment};
 use ant_networking::{GetRecordCfg, PutRecordCfg, VerificationKind};
 use ant_protocol::{
@@ -17,45 +17,35 @@ use bytes::Bytes;
 use futures::stream::{FuturesUnordered, StreamExt};
 use libp2p::kad::{Quorum, Record};
 use rand::{thread_rng, Rng};
-use self_encryption::{decrypt_full_set, DataMap, EncryptedChunk};
-use std::{future::Future, num::NonZero};
+use self_encryption::{decrypt_full_set, ChunkInfo, DataMap, EncryptedChunk};
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    future::Future,
+    num::NonZero,
+};
 use xor_name::XorName;
 
 use super::{
     data::{GetError, PayError, PutError, CHUNK_DOWNLOAD_BATCH_SIZE},
-    Client,
+    quote::StoreQuote,
+    Client, ClientEvent,
 };
-use crate::self_encryption::DataMapLevel;
+use crate::self_encryption::{recover_group, DataMapLevel, ParityMap};
 
 impl Client {
     /// Fetch and decrypt all chunks in the data map.
-    pub(crate) async fn fetch_from_data_map(&self, data_map: &DataMap) -> Result<Bytes, GetError> {
+    ///
+    /// With the parity of the data, chunks that cannot be fetched are rebuilt from the parity chunks.
+    pub(crate) async fn fetch_from_data_map(
+        &self,
+        data_map: &DataMap,
+        parity_map: Option<&ParityMap>,
+    ) -> Result<Bytes, GetError> {
         debug!("Fetching encrypted data chunks from data map {data_map:?}");
-        let mut download_tasks = vec![];
-        for info in data_map.infos() {
-            download_tasks.push(async move {
-                match self
-                    .chunk_get(info.dst_hash)
-                    .await
-                    .inspect_err(|err| error!("Error fetching chunk {:?}: {err:?}", info.dst_hash))
-                {
-                    Ok(chunk) => Ok(EncryptedChunk {
-                        index: info.index,
-                        content: chunk.value,
-                    }),
-                    Err(err) => {
-                        error!("Error fetching chunk {:?}: {err:?}", info.dst_hash);
-                        Err(err)
-                    }
-                }
-            });
-        }
+        let encrypted_chunks = self
+            .fetch_encrypted_chunks(&data_map.infos(), parity_map)
+            .await?;
         debug!("Successfully fetched all the encrypted chunks");
-        let encrypted_chunks =
-            process_tasks_with_max_concurrency(download_tasks, *CHUNK_DOWNLOAD_BATCH_SIZE)
-                .await
-                .into_iter()
-                .collect::<Result<Vec<EncryptedChunk>, GetError>>()?;
 
         let data = decrypt_full_set(data_map, &encrypted_chunks).map_err(|e| {
             error!("Error decrypting encrypted_chunks: {e:?}");
@@ -65,36 +55,150 @@ impl Client {
         Ok(data)
     }
 
+    /// Fetch the encrypted chunks described by the given chunk infos,
+    /// with at most [`CHUNK_DOWNLOAD_BATCH_SIZE`] downloads in flight.
+    ///
+    /// With the parity of the data, chunks that cannot be fetched are rebuilt from the other chunks of their
+    /// parity group, which are fetched as needed.
+    pub(crate) async fn fetch_encrypted_chunks(
+        &self,
+        infos: &[ChunkInfo],
+        parity_map: Option<&ParityMap>,
+    ) -> Result<Vec<EncryptedChunk>, GetError> {
+        let mut fetched = self
+            .fetch_chunks_tolerating_failures(infos.iter().map(|info| info.dst_hash).collect())
+            .await;
+
+        let mut missing: Vec<(XorName, GetError)> = vec![];
+        for info in infos {
+            if let Some(Err(_)) = fetched.get(&info.dst_hash) {
+                if let Some(Err(err)) = fetched.remove(&info.dst_hash) {
+                    missing.push((info.dst_hash, err));
+                }
+            }
+        }
+        let mut contents: HashMap<XorName, Bytes> = fetched
+            .into_iter()
+            .filter_map(|(xor_name, result)| Some((xor_name, result.ok()?)))
+            .collect();
+        let failed: HashSet<XorName> = missing.iter().map(|(xor_name, _)| *xor_name).collect();
+
+        for (xor_name, err) in missing {
+            if contents.contains_key(&xor_name) {
+                // already rebuilt along with another chunk of its group
+                continue;
+            }
+            let Some(group) = parity_map.and_then(|parity_map| parity_map.group_of(&xor_name))
+            else {
+                if parity_map.is_some() {
+                    error!("Chunk {xor_name:?} is not covered by any parity group");
+                }
+                return Err(err);
+            };
+            warn!("Failed to fetch chunk {xor_name:?}, rebuilding it from parity");
+
+            // the rest of the group may not have been needed so far
+            let others = group
+                .chunks
+                .iter()
+                .map(|(name, _)| *name)
+                .filter(|name| !contents.contains_key(name) && !failed.contains(name))
+                .chain(group.parity.iter().copied())
+                .collect();
+            let others = self.fetch_chunks_tolerating_failures(others).await;
+            let content = |name: &XorName| {
+                contents.get(name).cloned().or_else(|| {
+                    others
+                        .get(name)
+                        .and_then(|result| result.as_ref().ok().cloned())
+                })
+            };
+            let data_shards = group.chunks.iter().map(|(name, _)| content(name)).collect();
+            let parity_shards = group.parity.iter().map(content).collect();
+
+            match recover_group(group, data_shards, parity_shards) {
+                Ok(recovered) => {
+                    for ((name, _), content) in group.chunks.iter().zip(recovered) {
+                        let _ = contents.insert(*name, content);
+                    }
+                }
+                Err(recover_err) => {
+                    error!("Failed to rebuild chunk {xor_name:?} from parity: {recover_err:?}");
+                    return Err(err);
+                }
+            }
+        }
+
+        Ok(infos
+            .iter()
+            .filter_map(|info| {
+                Some(EncryptedChunk {
+                    index: info.index,
+                    content: contents.get(&info.dst_hash)?.clone(),
+                })
+            })
+            .collect())
+    }
+
     /// Unpack a wrapped data map and fetch all bytes using self-encryption.
+    ///
+    /// If the data was uploaded with parity, chunks that cannot be fetched are rebuilt from the parity chunks.
     pub(crate) async fn fetch_from_data_map_chunk(
         &self,
         data_map_bytes: &Bytes,
     ) -> Result<Bytes, GetError> {
+        let (data_map, parity_map) = self.restore_data_map_from_chunk(data_map_bytes).await?;
+        self.fetch_from_data_map(&data_map, parity_map.as_ref())
+            .await
+    }
+
+    /// Unpack a wrapped data map, fetching any additional levels from the network,
+    /// and return the data map of the source data, along with its parity if it was uploaded with parity.
+    pub(crate) async fn restore_data_map_from_chunk(
+        &self,
+        data_map_bytes: &Bytes,
+    ) -> Result<(DataMap, Option<ParityMap>), GetError> {
         let mut data_map_level: DataMapLevel = rmp_serde::from_slice(data_map_bytes)
             .map_err(GetError::InvalidDataMap)
             .inspect_err(|err| error!("Error deserializing data map: {err:?}"))?;
 
         loop {
-            let data_map = match &data_map_level {
-                DataMapLevel::First(map) => map,
-                DataMapLevel::Additional(map) => map,
-            };
-
-            let data = self.fetch_from_data_map(data_map).await?;
-
-            match &data_map_level {
-                DataMapLevel::First(_) => break Ok(data),
-                DataMapLevel::Additional(_) => {
+            match data_map_level {
+                DataMapLevel::First(map) => break Ok((map, None)),
+                DataMapLevel::FirstWithParity(map, parity_map) => {
+                    break Ok((map, Some(parity_map)))
+                }
+                DataMapLevel::Additional(map) => {
+                    let data = self.fetch_from_data_map(&map, None).await?;
                     data_map_level = rmp_serde::from_slice(&data).map_err(|err| {
                         error!("Error deserializing data map: {err:?}");
                         GetError::InvalidDataMap(err)
                     })?;
-                    continue;
                 }
-            };
+            }
         }
     }
 
+    /// Fetch the content of the given chunks, keeping the outcome of each fetch.
+    async fn fetch_chunks_tolerating_failures(
+        &self,
+        xor_names: Vec<XorName>,
+    ) -> HashMap<XorName, Result<Bytes, GetError>> {
+        let download_tasks = xor_names.into_iter().map(|xor_name| async move {
+            let result = self
+                .chunk_get(xor_name)
+                .await
+                .map(|chunk| chunk.value)
+                .inspect_err(|err| error!("Error fetching chunk {xor_name:?}: {err:?}"));
+            (xor_name, result)
+        });
+
+        process_tasks_with_max_concurrency(download_tasks, *CHUNK_DOWNLOAD_BATCH_SIZE)
+            .await
+            .into_iter()
+            .collect()
+    }
+
     pub(crate) async fn chunk_upload_with_payment(
         &self,
         chunk: &Chunk,
@@ -154,9 +258,14 @@ impl Client {
             use_put_record_to: Some(storing_nodes.clone()),
             verification,
         };
-        let payment_upload = Ok(self.network.put_record(record, &put_cfg).await?);
+        self.network.put_record(record, &put_cfg).await?;
         debug!("Successfully stored chunk: {chunk:?} to {storing_nodes:?}");
-        payment_upload
+
+        // The put only succeeds once the nodes proved they store the chunk
+        self.try_send_client_event(ClientEvent::ChunkVerified {
+            address: *chunk.name(),
+        });
+        Ok(())
     }
 
     /// Pay for the chunks and get the proof of payment.
@@ -164,9 +273,32 @@ impl Client {
         &self,
         content_addrs: impl Iterator<Item = XorName> + Clone,
         wallet: &EvmWallet,
-    ) -> Result<Receipt, PayError> {
+    ) -> Result<(Receipt, PaymentTransactions), PayError> {
         let number_of_content_addrs = content_addrs.clone().count();
         let quotes = self.get_store_quotes(content_addrs).await?;
+        let paid_chunks = quotes.len();
+        let payment = self.pay_for_store_quotes(quotes, wallet).await?;
+
+        let skipped_chunks = number_of_content_addrs - paid_chunks;
+        trace!(
+            "Chunk payments of {paid_chunks} chunks completed. {skipped_chunks} chunks were free / already paid for"
+        );
+
+        Ok(payment)
+    }
+
+    /// Pay for the given quotes and get the proof of payment, along with the transactions sent to pay.
+    pub(crate) async fn pay_for_store_quotes(
+        &self,
+        quotes: StoreQuote,
+        wallet: &EvmWallet,
+    ) -> Result<(Receipt, PaymentTransactions), PayError> {
+        for (address, quote) in quotes.0.iter() {
+            self.try_send_client_event(ClientEvent::ChunkQuoted {
+                address: *address,
+                price: quote.price(),
+            });
+        }
 
         // Make sure nobody else can use the wallet while we are paying
         debug!("Waiting for wallet lock");
@@ -176,8 +308,8 @@ impl Client {
         // TODO: the error might contain some succeeded quote payments as well. These should be returned on err, so that they can be skipped when retrying.
         // TODO: retry when it fails?
         // Execute chunk payments
-        let _payments = wallet
-            .pay_for_quotes(quotes.payments())
+        let (tx_hashes_by_quote, approve_tx) = wallet
+            .pay_for_quotes_with_approval(quotes.payments())
             .await
             .map_err(|err| PayError::from(err.0))?;
 
@@ -185,16 +317,46 @@ impl Client {
         drop(lock_guard);
         debug!("Unlocked wallet");
 
-        let skipped_chunks = number_of_content_addrs - quotes.len();
-        trace!(
-            "Chunk payments of {} chunks completed. {} chunks were free / already paid for",
-            quotes.len(),
-            skipped_chunks
-        );
+        for (address, quote) in quotes.0.iter() {
+            let tx_hash = quote
+                .0
+                .iter()
+                .find_map(|(_, quote, _)| tx_hashes_by_quote.get(&quote.hash()).copied());
+            self.try_send_client_event(ClientEvent::ChunkPaid {
+                address: *address,
+                tx_hash,
+            });
+        }
+
+        let tx_hashes: Vec<_> = approve_tx
+            .into_iter()
+            .chain(tx_hashes_by_quote.into_values())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        // The payment is made, failing to account for its gas must not fail it
+        let gas_spent = wallet
+            .gas_spent(tx_hashes.iter().copied())
+            .await
+            .inspect_err(|err| warn!("Failed to get the gas spent by the payment: {err}"))
+            .unwrap_or_default();
 
         let receipt = receipt_from_store_quotes(quotes);
+        // The payment is made, failing to save its receipt must not fail the upload
+        if let Some(store) = self.receipt_store.as_ref().filter(|_| !receipt.is_empty()) {
+            match store.save(&receipt) {
+                Ok(stored) => info!("Saved the receipt of the payment as {}", stored.id),
+                Err(err) => warn!("Failed to save the receipt of the payment: {err}"),
+            }
+        }
 
-        Ok(receipt)
+        Ok((
+            receipt,
+            PaymentTransactions {
+                tx_hashes,
+                gas_spent,
+            },
+        ))
     }
 }
 
@@ -17,7 +17,11 @@ use crate::client::vault::VaultError;
 use crate::client::vault::{app_name_to_vault_content_type, VaultContentType, VaultSecretKey};
 use crate::client::Client;
 use ant_evm::AttoTokens;
+use ant_protocol::storage::{
+    GraphEntryAddress, PointerAddress, RegisterAddress, ScratchpadAddress,
+};
 use ant_protocol::Bytes;
+use bls::SecretKey;
 use serde::{Deserialize, Serialize};
 use std::sync::LazyLock;
 
@@ -25,6 +29,11 @@ use std::sync::LazyLock;
 pub static USER_DATA_VAULT_CONTENT_IDENTIFIER: LazyLock<VaultContentType> =
     LazyLock::new(|| app_name_to_vault_content_type("UserData"));
 
+/// Version of the format [`UserData`] is written with.
+///
+/// Version 0 is the unversioned format holding only the file archives.
+pub const USER_DATA_VERSION: u32 = 1;
+
 /// UserData is stored in Vaults and contains most of a user's private data:
 /// It allows users to keep track of only the key to their User Data Vault
 /// while having the rest kept on the Network encrypted in a Vault for them
@@ -35,6 +44,66 @@ pub struct UserData {
     pub file_archives: HashMap<ArchiveAddr, String>,
     /// Owned private file archives, along with their names (can be empty)
     pub private_file_archives: HashMap<PrivateArchiveAccess, String>,
+    /// Owned register addresses, along with their names (can be empty)
+    pub registers: HashMap<RegisterAddress, String>,
+    /// Owned pointer addresses, along with their names (can be empty)
+    pub pointers: HashMap<PointerAddress, String>,
+    /// Addresses of the first entry of owned graphs, along with their names (can be empty)
+    pub graph_roots: HashMap<GraphEntryAddress, String>,
+    /// Owned scratchpad addresses, along with their names (can be empty)
+    pub scratchpads: HashMap<ScratchpadAddress, String>,
+    /// Keys used to sign registers, by name
+    #[serde(with = "secret_keys")]
+    pub register_keys: HashMap<String, SecretKey>,
+}
+
+/// The unversioned format of [`UserData`], written before [`USER_DATA_VERSION`] 1.
+#[derive(Deserialize)]
+struct UserDataV0 {
+    file_archives: HashMap<ArchiveAddr, String>,
+    private_file_archives: HashMap<PrivateArchiveAccess, String>,
+}
+
+impl From<UserDataV0> for UserData {
+    fn from(value: UserDataV0) -> Self {
+        Self {
+            file_archives: value.file_archives,
+            private_file_archives: value.private_file_archives,
+            ..Default::default()
+        }
+    }
+}
+
+/// Errors that can occur when reading [`UserData`] from bytes.
+#[derive(Debug, thiserror::Error)]
+pub enum UserDataFormatError {
+    #[error("Failed to deserialize user data: {0}")]
+    Deserialization(#[from] rmp_serde::decode::Error),
+    #[error("User data version {0} is not supported, please upgrade")]
+    UnsupportedVersion(u32),
+}
+
+/// Secret keys are not serializable by default, to avoid leaking them by accident.
+mod secret_keys {
+    use bls::{serde_impl::SerdeSecret, SecretKey};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::HashMap;
+
+    pub(super) fn serialize<S: Serializer>(
+        keys: &HashMap<String, SecretKey>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        keys.iter()
+            .map(|(name, key)| (name, SerdeSecret(key)))
+            .collect::<HashMap<_, _>>()
+            .serialize(serializer)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HashMap<String, SecretKey>, D::Error> {
+        HashMap::<String, SecretKey>::deserialize(deserializer)
+    }
 }
 
 /// Errors that can occur during the get operation.
@@ -94,16 +163,53 @@ impl UserData {
         self.private_file_archives.remove(&archive)
     }
 
-    /// To bytes
+    /// Merge `other` into this user data.
+    ///
+    /// Entries only in `other` are added. For entries in both, the name of this user data is kept unless it is
+    /// empty.
+    pub fn merge(&mut self, other: UserData) {
+        merge_names(&mut self.file_archives, other.file_archives);
+        merge_names(&mut self.private_file_archives, other.private_file_archives);
+        merge_names(&mut self.registers, other.registers);
+        merge_names(&mut self.pointers, other.pointers);
+        merge_names(&mut self.graph_roots, other.graph_roots);
+        merge_names(&mut self.scratchpads, other.scratchpads);
+        for (name, key) in other.register_keys {
+            self.register_keys.entry(name).or_insert(key);
+        }
+    }
+
+    /// To bytes, in the format of the current [`USER_DATA_VERSION`]
     pub fn to_bytes(&self) -> Result<Bytes, rmp_serde::encode::Error> {
-        let bytes = rmp_serde::to_vec(&self)?;
+        let data = Bytes::from(rmp_serde::to_vec(&self)?);
+        let bytes = rmp_serde::to_vec(&(USER_DATA_VERSION, data))?;
         Ok(Bytes::from(bytes))
     }
 
-    /// From bytes
-    pub fn from_bytes(bytes: Bytes) -> Result<Self, rmp_serde::decode::Error> {
-        let vault_content = rmp_serde::from_slice(&bytes)?;
-        Ok(vault_content)
+    /// From bytes, migrating the user data written with older versions
+    pub fn from_bytes(bytes: Bytes) -> Result<Self, UserDataFormatError> {
+        // the unversioned format does not start with a version number
+        let (version, data) = match rmp_serde::from_slice::<(u32, Bytes)>(&bytes) {
+            Ok(versioned) => versioned,
+            Err(_) => {
+                debug!("Migrating unversioned user data");
+                let vault_content: UserDataV0 = rmp_serde::from_slice(&bytes)?;
+                return Ok(vault_content.into());
+            }
+        };
+        match version {
+            USER_DATA_VERSION => Ok(rmp_serde::from_slice(&data)?),
+            version => Err(UserDataFormatError::UnsupportedVersion(version)),
+        }
+    }
+}
+
+fn merge_names<K: std::hash::Hash + Eq>(names: &mut HashMap<K, String>, other: HashMap<K, String>) {
+    for (key, other_name) in other {
+        let name = names.entry(key).or_default();
+        if name.is_empty() {
+            *name = other_name;
+        }
     }
 }
 
@@ -152,3 +258,70 @@ impl Client {
         Ok(total_cost)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use xor_name::XorName;
+
+    #[derive(Serialize)]
+    struct UnversionedUserData {
+        file_archives: HashMap<ArchiveAddr, String>,
+        private_file_archives: HashMap<PrivateArchiveAccess, String>,
+    }
+
+    #[test]
+    fn unversioned_user_data_is_migrated() -> Result<(), Box<dyn std::error::Error>> {
+        let archive = XorName::random(&mut rand::thread_rng());
+        let old = UnversionedUserData {
+            file_archives: HashMap::from([(archive, "photos".to_string())]),
+            private_file_archives: HashMap::new(),
+        };
+        let bytes = Bytes::from(rmp_serde::to_vec(&old)?);
+
+        let user_data = UserData::from_bytes(bytes)?;
+        assert_eq!(user_data.file_archives, old.file_archives);
+        assert!(user_data.registers.is_empty());
+
+        let too_new = Bytes::from(rmp_serde::to_vec(&(
+            USER_DATA_VERSION + 1,
+            Bytes::from_static(b""),
+        ))?);
+        assert!(matches!(
+            UserData::from_bytes(too_new),
+            Err(UserDataFormatError::UnsupportedVersion(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn user_data_round_trips_and_merges() -> Result<(), Box<dyn std::error::Error>> {
+        let key = SecretKey::random();
+        let archive = XorName::random(&mut rand::thread_rng());
+        let pointer = PointerAddress::from_owner(SecretKey::random().public_key());
+        let scratchpad = ScratchpadAddress::new(key.public_key());
+
+        let mut local = UserData::new();
+        let _ = local.add_file_archive(archive);
+        local.pointers.insert(pointer, "local name".to_string());
+        local
+            .register_keys
+            .insert("default".to_string(), key.clone());
+        assert_eq!(UserData::from_bytes(local.to_bytes()?)?, local);
+
+        let mut network = UserData::new();
+        let _ = network.add_file_archive_with_name(archive, "photos".to_string());
+        network.pointers.insert(pointer, "network name".to_string());
+        network.scratchpads.insert(scratchpad, "notes".to_string());
+        network
+            .register_keys
+            .insert("default".to_string(), SecretKey::random());
+
+        local.merge(network);
+        assert_eq!(local.file_archives[&archive], "photos");
+        assert_eq!(local.pointers[&pointer], "local name");
+        assert_eq!(local.scratchpads[&scratchpad], "notes");
+        assert_eq!(local.register_keys["default"], key);
+        Ok(())
+    }
+}
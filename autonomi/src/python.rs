@@ -197,6 +197,14 @@ impl Client {
             .map_err(|e| PyValueError::new_err(format!("Failed to put pointer: {e}")))
     }
 
+    fn pointer_update(&self, key: &PySecretKey, target: &PyPointerTarget) -> PyResult<PyPointer> {
+        let rt = tokio::runtime::Runtime::new().expect("Could not start tokio runtime");
+        let pointer = rt
+            .block_on(self.inner.pointer_update(&key.inner, target.inner.clone()))
+            .map_err(|e| PyValueError::new_err(format!("Failed to update pointer: {e}")))?;
+        Ok(PyPointer { inner: pointer })
+    }
+
     fn pointer_cost(&self, key: &PySecretKey) -> PyResult<String> {
         let rt = tokio::runtime::Runtime::new().expect("Could not start tokio runtime");
         let cost = rt
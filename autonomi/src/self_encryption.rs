@@ -9,9 +9,13 @@
 use ant_protocol::storage::Chunk;
 use bytes::{BufMut, Bytes, BytesMut};
 use rayon::prelude::*;
+use reed_solomon_erasure::galois_8::ReedSolomon;
 use self_encryption::{DataMap, MAX_CHUNK_SIZE};
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "fs")]
+use std::path::PathBuf;
 use tracing::debug;
+use xor_name::XorName;
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -19,6 +23,10 @@ pub enum Error {
     Encoding(#[from] rmp_serde::encode::Error),
     #[error(transparent)]
     SelfEncryption(#[from] self_encryption::Error),
+    #[error(transparent)]
+    ReedSolomon(#[from] reed_solomon_erasure::Error),
+    #[error("Failed to recover chunk {0:?} from parity")]
+    ParityRecovery(XorName),
 }
 
 #[derive(Serialize, Deserialize)]
@@ -29,6 +37,54 @@ pub(crate) enum DataMapLevel {
     // resulting from chunking up a previous level data map.
     // This happens when that previous level data map was too big to fit in a chunk itself.
     Additional(DataMap),
+    // Same as `First`, along with the Reed-Solomon parity chunks protecting the chunks of the data map.
+    FirstWithParity(DataMap, ParityMap),
+}
+
+/// Reed-Solomon settings of the parity upload mode, see [`crate::Client::data_put_with_parity`].
+///
+/// The chunks of the data are split in groups of `data_shards` chunks, each group being protected by
+/// `parity_shards` parity chunks: up to `parity_shards` chunks of a group can be lost and still be rebuilt.
+/// With no parity shards, the data is uploaded without parity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParityConfig {
+    pub data_shards: usize,
+    pub parity_shards: usize,
+}
+
+impl Default for ParityConfig {
+    fn default() -> Self {
+        Self {
+            data_shards: 8,
+            parity_shards: 2,
+        }
+    }
+}
+
+/// The parity chunks protecting the chunks of a data map.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct ParityMap {
+    pub(crate) groups: Vec<ParityGroup>,
+}
+
+/// A group of chunks and the parity chunks computed over them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct ParityGroup {
+    /// The data chunks of the group, in data map order, with their size before being padded to `shard_size`.
+    pub(crate) chunks: Vec<(XorName, usize)>,
+    /// The parity chunks of the group.
+    pub(crate) parity: Vec<XorName>,
+    /// Size of every shard of the group.
+    pub(crate) shard_size: usize,
+}
+
+impl ParityMap {
+    /// The group protecting the given chunk.
+    pub(crate) fn group_of(&self, xor_name: &XorName) -> Option<&ParityGroup> {
+        self.groups
+            .iter()
+            .find(|group| group.chunks.iter().any(|(name, _)| name == xor_name))
+    }
 }
 
 pub fn encrypt(data: Bytes) -> Result<(Chunk, Vec<Chunk>), Error> {
@@ -45,6 +101,194 @@ pub fn encrypt(data: Bytes) -> Result<(Chunk, Vec<Chunk>), Error> {
     Ok((data_map_chunk, chunks))
 }
 
+/// Same as [`encrypt`], additionally producing Reed-Solomon parity chunks over the self-encrypted chunks.
+///
+/// The parity chunks are returned along with the other chunks and recorded in the data map chunk,
+/// so that lost chunks can be rebuilt when downloading. Without parity shards, this is the same as [`encrypt`].
+pub fn encrypt_with_parity(
+    data: Bytes,
+    config: ParityConfig,
+) -> Result<(Chunk, Vec<Chunk>), Error> {
+    if config.parity_shards == 0 {
+        debug!("No parity shards requested, encrypting without parity");
+        return encrypt(data);
+    }
+
+    let (data_map, mut encrypted_chunks) = self_encryption::encrypt(data)?;
+    encrypted_chunks.sort_by_key(|c| c.index);
+    let contents: Vec<Bytes> = encrypted_chunks.into_iter().map(|c| c.content).collect();
+
+    let (parity_map, parity_chunks) = compute_parity(&contents, config)?;
+    let (data_map_chunk, additional_chunks) =
+        pack_data_map_level(DataMapLevel::FirstWithParity(data_map, parity_map))?;
+
+    let chunks: Vec<Chunk> = contents
+        .into_iter()
+        .map(Chunk::new)
+        .chain(parity_chunks)
+        .chain(additional_chunks)
+        .collect();
+
+    Ok((data_map_chunk, chunks))
+}
+
+// Split the chunks in groups and compute the parity chunks of each group.
+fn compute_parity(
+    contents: &[Bytes],
+    config: ParityConfig,
+) -> Result<(ParityMap, Vec<Chunk>), Error> {
+    let mut groups = vec![];
+    let mut parity_chunks = vec![];
+
+    for group in contents.chunks(config.data_shards.max(1)) {
+        let shard_size = group.iter().map(Bytes::len).max().unwrap_or_default();
+        let codec = ReedSolomon::new(group.len(), config.parity_shards)?;
+
+        let mut shards: Vec<Vec<u8>> = group
+            .iter()
+            .map(|content| {
+                let mut shard = content.to_vec();
+                shard.resize(shard_size, 0);
+                shard
+            })
+            .chain(std::iter::repeat_n(
+                vec![0; shard_size],
+                config.parity_shards,
+            ))
+            .collect();
+        codec.encode(&mut shards)?;
+
+        let parity: Vec<Chunk> = shards
+            .into_iter()
+            .skip(group.len())
+            .map(|shard| Chunk::new(Bytes::from(shard)))
+            .collect();
+        groups.push(ParityGroup {
+            chunks: group
+                .iter()
+                .map(|content| (XorName::from_content(content), content.len()))
+                .collect(),
+            parity: parity.iter().map(|chunk| *chunk.name()).collect(),
+            shard_size,
+        });
+        parity_chunks.extend(parity);
+    }
+    debug!(
+        "Computed {} parity chunks over {} chunks",
+        parity_chunks.len(),
+        contents.len()
+    );
+
+    Ok((ParityMap { groups }, parity_chunks))
+}
+
+/// Rebuild the missing data chunks of a parity group from the available data and parity chunks.
+///
+/// `data` and `parity` hold the content of the chunks of the group in order, `None` for the missing ones.
+/// Returns the content of all the data chunks of the group, each rebuilt chunk being checked against its [`XorName`].
+pub(crate) fn recover_group(
+    group: &ParityGroup,
+    data: Vec<Option<Bytes>>,
+    parity: Vec<Option<Bytes>>,
+) -> Result<Vec<Bytes>, Error> {
+    let codec = ReedSolomon::new(group.chunks.len(), group.parity.len())?;
+    let mut shards: Vec<Option<Vec<u8>>> = data
+        .into_iter()
+        .chain(parity)
+        .map(|content| {
+            content.map(|content| {
+                let mut shard = content.to_vec();
+                shard.resize(group.shard_size, 0);
+                shard
+            })
+        })
+        .collect();
+    codec.reconstruct_data(&mut shards)?;
+
+    group
+        .chunks
+        .iter()
+        .zip(shards)
+        .map(|((xor_name, size), shard)| {
+            let mut shard = shard.ok_or(Error::ParityRecovery(*xor_name))?;
+            shard.truncate(*size);
+            let content = Bytes::from(shard);
+            if XorName::from_content(&content) != *xor_name {
+                return Err(Error::ParityRecovery(*xor_name));
+            }
+            Ok(content)
+        })
+        .collect()
+}
+
+/// Self-encrypts a file on disk one chunk at a time, so that only the chunks
+/// requested through [`StreamEncryptor::next_batch`] are held in memory.
+///
+/// Produces exactly the same chunks and data map chunk as [`encrypt`] would for the file content.
+#[cfg(feature = "fs")]
+pub(crate) struct StreamEncryptor {
+    encryptor: self_encryption::StreamSelfEncryptor,
+    data_map: Option<DataMap>,
+}
+
+#[cfg(feature = "fs")]
+impl StreamEncryptor {
+    pub(crate) fn new(file_path: PathBuf) -> Result<Self, Error> {
+        let file_size = std::fs::metadata(&file_path)
+            .map_err(self_encryption::Error::from)?
+            .len();
+        if (file_size as usize) < self_encryption::MIN_ENCRYPTABLE_BYTES {
+            return Err(Error::SelfEncryption(self_encryption::Error::Generic(
+                format!(
+                    "Too small for self-encryption! Required size at least {}",
+                    self_encryption::MIN_ENCRYPTABLE_BYTES
+                ),
+            )));
+        }
+
+        let encryptor = self_encryption::StreamSelfEncryptor::encrypt_from_file(file_path, None)?;
+        Ok(Self {
+            encryptor,
+            data_map: None,
+        })
+    }
+
+    /// Encrypt up to `max_chunks` further chunks of the file.
+    /// Returns an empty list once the whole file has been encrypted.
+    pub(crate) fn next_batch(&mut self, max_chunks: usize) -> Result<Vec<Chunk>, Error> {
+        let mut chunks = Vec::with_capacity(max_chunks);
+        while self.data_map.is_none() && chunks.len() < max_chunks {
+            match self.encryptor.next_encryption()? {
+                (Some(encrypted_chunk), _) => chunks.push(Chunk::new(encrypted_chunk.content)),
+                (None, Some(data_map)) => self.data_map = Some(data_map),
+                (None, None) => {
+                    error!("Stream encryptor returned neither a chunk nor a data map");
+                    return Err(Error::SelfEncryption(self_encryption::Error::Encryption));
+                }
+            }
+        }
+        Ok(chunks)
+    }
+
+    /// Whether all chunks of the file have been produced.
+    pub(crate) fn is_done(&self) -> bool {
+        self.data_map.is_some()
+    }
+
+    /// Pack the data map once all chunks have been produced by [`StreamEncryptor::next_batch`].
+    ///
+    /// Returns the data map chunk and the additional chunks produced if the data map had to be chunked up itself.
+    pub(crate) fn finish(self) -> Result<(Chunk, Vec<Chunk>), Error> {
+        match self.data_map {
+            Some(data_map) => pack_data_map(data_map),
+            None => {
+                error!("Stream encryptor finished before all chunks were encrypted");
+                Err(Error::SelfEncryption(self_encryption::Error::Encryption))
+            }
+        }
+    }
+}
+
 // Produces a chunk out of the first `DataMap`, which is validated for its size.
 // If the chunk is too big, it is self-encrypted and the resulting (additional level) `DataMap` is put into a chunk.
 // The above step is repeated as many times as required until the chunk size is valid.
@@ -52,8 +296,12 @@ pub fn encrypt(data: Bytes) -> Result<(Chunk, Vec<Chunk>), Error> {
 // self encrypted into additional chunks, and now we have a new `DataMap`
 // which points to all of those additional chunks.. and so on.
 fn pack_data_map(data_map: DataMap) -> Result<(Chunk, Vec<Chunk>), Error> {
+    pack_data_map_level(DataMapLevel::First(data_map))
+}
+
+fn pack_data_map_level(first_level: DataMapLevel) -> Result<(Chunk, Vec<Chunk>), Error> {
     let mut chunks = vec![];
-    let mut chunk_content = wrap_data_map(&DataMapLevel::First(data_map))?;
+    let mut chunk_content = wrap_data_map(&first_level)?;
 
     let (data_map_chunk, additional_chunks) = loop {
         debug!("Max chunk size: {}", *MAX_CHUNK_SIZE);
@@ -92,3 +340,111 @@ fn wrap_data_map(data_map: &DataMapLevel) -> Result<Bytes, rmp_serde::encode::Er
         .inspect_err(|err| error!("Failed to serialize data map: {err:?}"))?;
     Ok(bytes.into_inner().freeze())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::RngCore;
+    #[cfg(feature = "fs")]
+    use std::io::Write;
+
+    #[test]
+    fn lost_chunks_are_recovered_from_parity() -> Result<(), Box<dyn std::error::Error>> {
+        let mut data = vec![0u8; 5 * *MAX_CHUNK_SIZE + 42];
+        rand::thread_rng().fill_bytes(&mut data);
+        let config = ParityConfig {
+            data_shards: 4,
+            parity_shards: 2,
+        };
+        let (data_map_chunk, chunks) = encrypt_with_parity(Bytes::from(data), config)?;
+
+        let DataMapLevel::FirstWithParity(data_map, parity_map) =
+            rmp_serde::from_slice(data_map_chunk.value())?
+        else {
+            return Err("expected a data map with parity".into());
+        };
+        // 6 chunks split in groups of 4 and 2 chunks, each with 2 parity chunks
+        assert_eq!(data_map.infos().len(), 6);
+        assert_eq!(parity_map.groups.len(), 2);
+        assert_eq!(chunks.len(), 6 + 4);
+
+        let content = |xor_name: &XorName| {
+            chunks
+                .iter()
+                .find(|chunk| chunk.name() == xor_name)
+                .map(|chunk| chunk.value().clone())
+        };
+        let group = &parity_map.groups[0];
+        let expected: Vec<Bytes> = group
+            .chunks
+            .iter()
+            .map(|(xor_name, _)| content(xor_name).expect("data chunk"))
+            .collect();
+
+        // lose two data chunks of the group
+        let mut data: Vec<Option<Bytes>> = expected.iter().cloned().map(Some).collect();
+        data[0] = None;
+        data[2] = None;
+        let parity = group.parity.iter().map(content).collect();
+        assert_eq!(recover_group(group, data, parity)?, expected);
+
+        // losing more chunks than there are parity chunks is not recoverable
+        let data = vec![
+            None,
+            None,
+            Some(expected[2].clone()),
+            Some(expected[3].clone()),
+        ];
+        let parity = vec![content(&group.parity[0]), None];
+        assert!(recover_group(group, data, parity).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn no_parity_shards_means_no_parity() -> Result<(), Box<dyn std::error::Error>> {
+        let mut data = vec![0u8; 3 * *MAX_CHUNK_SIZE];
+        rand::thread_rng().fill_bytes(&mut data);
+        let config = ParityConfig {
+            data_shards: 4,
+            parity_shards: 0,
+        };
+        let data = Bytes::from(data);
+        assert_eq!(encrypt_with_parity(data.clone(), config)?, encrypt(data)?);
+        Ok(())
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn stream_encryptor_matches_in_memory_encryption() -> Result<(), Box<dyn std::error::Error>> {
+        let mut data = vec![0u8; 5 * *MAX_CHUNK_SIZE + 42];
+        rand::thread_rng().fill_bytes(&mut data);
+        let mut file = tempfile::NamedTempFile::new()?;
+        file.write_all(&data)?;
+
+        let (expected_data_map_chunk, expected_chunks) = encrypt(Bytes::from(data))?;
+
+        let mut encryptor = StreamEncryptor::new(file.path().to_path_buf())?;
+        let mut chunks = vec![];
+        while !encryptor.is_done() {
+            let batch = encryptor.next_batch(2)?;
+            assert!(batch.len() <= 2);
+            chunks.extend(batch);
+        }
+        let (data_map_chunk, additional_chunks) = encryptor.finish()?;
+        chunks.extend(additional_chunks);
+
+        assert_eq!(data_map_chunk, expected_data_map_chunk);
+        assert_eq!(chunks, expected_chunks);
+        Ok(())
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn stream_encryptor_rejects_too_small_files() -> Result<(), Box<dyn std::error::Error>> {
+        let mut file = tempfile::NamedTempFile::new()?;
+        file.write_all(b"ab")?;
+
+        assert!(StreamEncryptor::new(file.path().to_path_buf()).is_err());
+        Ok(())
+    }
+}
@@ -26,3 +26,60 @@ async fn put() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn prepared_upload() -> Result<()> {
+    let _log_appender_guard = LogBuilder::init_single_threaded_tokio_test("prepared_upload", false);
+
+    let client = Client::init_local().await?;
+    let wallet = get_funded_wallet();
+    let data = gen_random_data(1024 * 1024 * 10);
+
+    // nothing is paid for an aborted upload
+    let upload = client.prepare_upload_public(data.clone()).await?;
+    assert_eq!(upload.paid_chunk_count(), upload.chunk_count());
+    let balance = wallet.balance_of_tokens().await?;
+    upload.abort();
+    assert_eq!(wallet.balance_of_tokens().await?, balance);
+
+    let upload = client.prepare_upload_public(data.clone()).await?;
+    let addr = upload.data_address();
+    let cost = upload.total_cost();
+    upload.commit(&wallet).await?;
+    assert_eq!(
+        wallet.balance_of_tokens().await?,
+        balance - cost.as_atto(),
+        "exactly the quoted cost should be paid"
+    );
+
+    let data_fetched = client.data_get_public(addr).await?;
+    assert_eq!(data, data_fetched, "data fetched should match data put");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn upload_session() -> Result<()> {
+    let _log_appender_guard = LogBuilder::init_single_threaded_tokio_test("upload_session", false);
+
+    let client = Client::init_local().await?;
+    let wallet = get_funded_wallet();
+    let files: Vec<_> = (0..10).map(|_| gen_random_data(1024 * 1024)).collect();
+
+    let mut session = client.upload_session();
+    let mut addrs = vec![];
+    for data in &files {
+        addrs.push(session.add_data_public(data.clone())?);
+    }
+    let private_data = gen_random_data(1024 * 1024);
+    let data_map = session.add_data(private_data.clone())?;
+    let receipt = session.upload(&wallet).await?;
+    assert!(!receipt.is_empty());
+
+    for (addr, data) in addrs.into_iter().zip(files) {
+        assert_eq!(client.data_get_public(addr).await?, data);
+    }
+    assert_eq!(client.data_get(data_map).await?, private_data);
+
+    Ok(())
+}
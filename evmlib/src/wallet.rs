@@ -129,6 +129,23 @@ impl Wallet {
         pay_for_quotes(self.wallet.clone(), &self.network, quote_payments).await
     }
 
+    /// Same as [`Wallet::pay_for_quotes`], also returning the transaction approving the payment vault to spend the
+    /// tokens of this wallet, when the allowance had to be raised.
+    pub async fn pay_for_quotes_with_approval<I: IntoIterator<Item = QuotePayment>>(
+        &self,
+        quote_payments: I,
+    ) -> Result<(BTreeMap<QuoteHash, TxHash>, Option<TxHash>), PayForQuotesError> {
+        pay_for_quotes_with_approval(self.wallet.clone(), &self.network, quote_payments).await
+    }
+
+    /// Returns the gas paid for the given transactions, in wei.
+    pub async fn gas_spent<I: IntoIterator<Item = TxHash>>(
+        &self,
+        tx_hashes: I,
+    ) -> Result<Amount, Error> {
+        gas_spent(&self.network, tx_hashes).await
+    }
+
     /// Build a provider using this wallet.
     pub fn to_provider(&self) -> ProviderWithWallet {
         http_provider_with_wallet(self.network.rpc_url().clone(), self.wallet.clone())
@@ -267,6 +284,25 @@ pub async fn transfer_gas_tokens(
     Ok(tx_hash)
 }
 
+/// Returns the gas paid for the given transactions, i.e. the gas they used times its effective price.
+/// Transactions that are not mined yet are not counted.
+pub async fn gas_spent<I: IntoIterator<Item = TxHash>>(
+    network: &Network,
+    tx_hashes: I,
+) -> Result<Amount, Error> {
+    let provider = http_provider(network.rpc_url().clone());
+    let mut total = Amount::ZERO;
+    for tx_hash in tx_hashes {
+        match provider.get_transaction_receipt(tx_hash).await? {
+            Some(receipt) => {
+                total += Amount::from(receipt.gas_used) * Amount::from(receipt.effective_gas_price)
+            }
+            None => debug!("No receipt yet for transaction {tx_hash}, its gas is not counted"),
+        }
+    }
+    Ok(total)
+}
+
 /// Contains the payment error and the already succeeded batch payments (if any).
 #[derive(Debug)]
 pub struct PayForQuotesError(pub Error, pub BTreeMap<QuoteHash, TxHash>);
@@ -278,6 +314,18 @@ pub async fn pay_for_quotes<T: IntoIterator<Item = QuotePayment>>(
     network: &Network,
     payments: T,
 ) -> Result<BTreeMap<QuoteHash, TxHash>, PayForQuotesError> {
+    pay_for_quotes_with_approval(wallet, network, payments)
+        .await
+        .map(|(tx_hashes_by_quote, _approve_tx)| tx_hashes_by_quote)
+}
+
+/// Same as [`pay_for_quotes`], also returning the hash of the approve transaction when the allowance of the payment
+/// vault had to be raised.
+pub async fn pay_for_quotes_with_approval<T: IntoIterator<Item = QuotePayment>>(
+    wallet: EthereumWallet,
+    network: &Network,
+    payments: T,
+) -> Result<(BTreeMap<QuoteHash, TxHash>, Option<TxHash>), PayForQuotesError> {
     let payments: Vec<_> = payments.into_iter().collect();
     info!("Paying for quotes of len: {}", payments.len());
 
@@ -306,9 +354,10 @@ pub async fn pay_for_quotes<T: IntoIterator<Item = QuotePayment>>(
     .map_err(|err| PayForQuotesError(Error::from(err), Default::default()))?;
 
     // TODO: Get rid of approvals altogether, by using permits or whatever..
+    let mut approve_tx = None;
     if allowance < total_amount_to_be_paid {
         // Approve the contract to spend all the client's tokens.
-        approve_to_spend_tokens(
+        let tx_hash = approve_to_spend_tokens(
             wallet.clone(),
             network,
             *network.data_payments_address(),
@@ -316,6 +365,7 @@ pub async fn pay_for_quotes<T: IntoIterator<Item = QuotePayment>>(
         )
         .await
         .map_err(|err| PayForQuotesError(Error::from(err), Default::default()))?;
+        approve_tx = Some(tx_hash);
     }
 
     let provider = http_provider_with_wallet(network.rpc_url().clone(), wallet);
@@ -352,7 +402,7 @@ pub async fn pay_for_quotes<T: IntoIterator<Item = QuotePayment>>(
         }
     }
 
-    Ok(tx_hashes_by_quote)
+    Ok((tx_hashes_by_quote, approve_tx))
 }
 
 #[cfg(test)]
@@ -28,6 +28,7 @@ pub enum Action {
     StorePortRange(u32, u32),
     StoreRewardsAddress(String),
     StoreNodesToStart(usize),
+    StoreStoragePerNode(usize),
 
     Tick,
     Render,
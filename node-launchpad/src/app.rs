@@ -14,9 +14,13 @@ use crate::{
         help::Help,
         options::Options,
         popup::{
-            change_drive::ChangeDrivePopup, connection_mode::ChangeConnectionModePopUp,
-            manage_nodes::ManageNodes, port_range::PortRangePopUp, reset_nodes::ResetNodesPopup,
-            rewards_address::RewardsAddress, upgrade_nodes::UpgradeNodesPopUp,
+            change_drive::ChangeDrivePopup,
+            connection_mode::ChangeConnectionModePopUp,
+            manage_nodes::{ManageNodes, GB_PER_NODE},
+            port_range::PortRangePopUp,
+            reset_nodes::ResetNodesPopup,
+            rewards_address::RewardsAddress,
+            upgrade_nodes::UpgradeNodesPopUp,
         },
         status::{Status, StatusConfig},
         Component,
@@ -88,6 +92,7 @@ impl App {
             .storage_drive
             .clone()
             .unwrap_or(get_primary_mount_point_name()?);
+        let storage_per_node_gb = app_data.storage_per_node_gb.unwrap_or(GB_PER_NODE);
 
         // Main Screens
         let status_config = StatusConfig {
@@ -100,6 +105,7 @@ impl App {
             connection_mode,
             port_from: Some(port_from),
             port_to: Some(port_to),
+            storage_per_node_gb,
         };
 
         let status = Status::new(status_config).await?;
@@ -116,9 +122,16 @@ impl App {
 
         // Popups
         let reset_nodes = ResetNodesPopup::default();
-        let manage_nodes = ManageNodes::new(app_data.nodes_to_start, storage_mountpoint.clone())?;
-        let change_drive =
-            ChangeDrivePopup::new(storage_mountpoint.clone(), app_data.nodes_to_start)?;
+        let manage_nodes = ManageNodes::new(
+            app_data.nodes_to_start,
+            storage_per_node_gb,
+            storage_mountpoint.clone(),
+        )?;
+        let change_drive = ChangeDrivePopup::new(
+            storage_mountpoint.clone(),
+            app_data.nodes_to_start,
+            storage_per_node_gb,
+        )?;
         let change_connection_mode = ChangeConnectionModePopUp::new(connection_mode)?;
         let port_range = PortRangePopUp::new(connection_mode, port_from, port_to);
         let rewards_address = RewardsAddress::new(app_data.discord_username.clone());
@@ -134,6 +147,7 @@ impl App {
                 connection_mode: Some(connection_mode),
                 port_from: Some(port_from),
                 port_to: Some(port_to),
+                storage_per_node_gb: Some(storage_per_node_gb),
             },
             tick_rate,
             frame_rate,
@@ -269,6 +283,11 @@ impl App {
                         self.app_data.storage_drive = Some(drive_name.as_str().to_string());
                         self.app_data.save(None)?;
                     }
+                    Action::StoreStoragePerNode(ref gb_per_node) => {
+                        debug!("Storing storage per node: {gb_per_node:?}GB");
+                        self.app_data.storage_per_node_gb = Some(*gb_per_node);
+                        self.app_data.save(None)?;
+                    }
                     Action::StoreConnectionMode(ref mode) => {
                         debug!("Storing connection mode: {mode:?}");
                         self.app_data.connection_mode = Some(*mode);
@@ -51,12 +51,18 @@ pub struct ChangeDrivePopup {
     drive_selection: DriveItem,
     drive_selection_initial_state: DriveItem,
     nodes_to_start: usize,
+    gb_per_node: usize,
+    gb_per_node_initial_state: usize,
     storage_mountpoint: PathBuf,
     can_select: bool, // Used to enable the "Change Drive" button based on conditions
 }
 
 impl ChangeDrivePopup {
-    pub fn new(storage_mountpoint: PathBuf, nodes_to_start: usize) -> Result<Self> {
+    pub fn new(
+        storage_mountpoint: PathBuf,
+        nodes_to_start: usize,
+        gb_per_node: usize,
+    ) -> Result<Self> {
         debug!("Drive Mountpoint in Config: {:?}", storage_mountpoint);
         Ok(ChangeDrivePopup {
             active: false,
@@ -65,6 +71,8 @@ impl ChangeDrivePopup {
             drive_selection: DriveItem::default(),
             drive_selection_initial_state: DriveItem::default(),
             nodes_to_start,
+            gb_per_node,
+            gb_per_node_initial_state: gb_per_node,
             storage_mountpoint,
             can_select: false,
         })
@@ -121,6 +129,31 @@ impl ChangeDrivePopup {
         DriveItem::default()
     }
 
+    // --- Storage allocated to each node ---
+
+    /// The largest storage each node can be given on the selected drive, in steps of `GB_PER_NODE`.
+    fn max_gb_per_node(&self) -> usize {
+        let available_gb = (self.drive_selection.available_space / GB as u64) as usize;
+        let max_gb_per_node = available_gb / self.nodes_to_start.max(1);
+        (max_gb_per_node / GB_PER_NODE * GB_PER_NODE).max(GB_PER_NODE)
+    }
+
+    /// Increases or decreases the storage allocated to each node, keeping it within the selected drive.
+    fn change_gb_per_node(&mut self, increase: bool) {
+        self.gb_per_node = if increase {
+            self.gb_per_node + GB_PER_NODE
+        } else {
+            self.gb_per_node.saturating_sub(GB_PER_NODE)
+        }
+        .clamp(GB_PER_NODE, self.max_gb_per_node());
+    }
+
+    /// Whether the highlighted drive can be selected to store the nodes.
+    /// The current drive can be selected again to change the storage allocated to each node.
+    fn is_selectable(drive: &DriveItem) -> bool {
+        drive.status != DriveStatus::NotAvailable && drive.status != DriveStatus::NotEnoughSpace
+    }
+
     /// Updates the drive items based on the current nodes_to_start value.
     fn update_drive_items(&mut self) -> Result<()> {
         let drives_and_space = system::get_list_of_available_drives_and_available_space()?;
@@ -134,11 +167,13 @@ impl ChangeDrivePopup {
                     name: drive_name.to_string(),
                     mountpoint: mountpoint.clone(),
                     size: size_str.clone(),
+                    available_space: *space,
                     status: if *mountpoint == self.storage_mountpoint {
                         self.drive_selection = DriveItem {
                             name: drive_name.to_string(),
                             mountpoint: mountpoint.clone(),
                             size: size_str.clone(),
+                            available_space: *space,
                             status: DriveStatus::Selected,
                         };
                         DriveStatus::Selected
@@ -306,6 +341,15 @@ impl ChangeDrivePopup {
             )])
             .alignment(Alignment::Center),
             Line::from(vec![]), // Empty line
+            Line::from(vec![
+                Span::styled("Storage per node: ", Style::default().fg(GHOST_WHITE)),
+                Span::styled(
+                    format!("◄ {} GB ► ", self.gb_per_node),
+                    Style::default().fg(VIVID_SKY_BLUE).bold(),
+                ),
+                Span::styled("[←→]", Style::default().fg(LIGHT_PERIWINKLE)),
+            ])
+            .alignment(Alignment::Center),
             Line::from(vec![]), // Empty line
             Line::from(vec![
                 Span::styled("You’ll need to ", Style::default().fg(GHOST_WHITE)),
@@ -389,7 +433,9 @@ impl Component for ChangeDrivePopup {
                             );
                             debug!("Drive selected: {:?}", drive.name);
                             self.drive_selection_initial_state = self.drive_selection.clone();
+                            self.gb_per_node_initial_state = self.gb_per_node;
                             self.assign_drive_selection();
+                            self.gb_per_node = self.gb_per_node.min(self.max_gb_per_node());
                             self.state = ChangeDriveState::ConfirmChange;
                             vec![]
                         } else {
@@ -406,10 +452,7 @@ impl Component for ChangeDrivePopup {
                             if items.items.len() > 1 {
                                 items.previous();
                                 let drive = self.return_selection();
-                                self.can_select = drive.mountpoint
-                                    != self.drive_selection.mountpoint
-                                    && drive.status != DriveStatus::NotAvailable
-                                    && drive.status != DriveStatus::NotEnoughSpace;
+                                self.can_select = Self::is_selectable(&drive);
                             }
                         }
                         vec![]
@@ -419,10 +462,7 @@ impl Component for ChangeDrivePopup {
                             if items.items.len() > 1 {
                                 items.next();
                                 let drive = self.return_selection();
-                                self.can_select = drive.mountpoint
-                                    != self.drive_selection.mountpoint
-                                    && drive.status != DriveStatus::NotAvailable
-                                    && drive.status != DriveStatus::NotEnoughSpace;
+                                self.can_select = Self::is_selectable(&drive);
                             }
                         }
                         vec![]
@@ -444,6 +484,7 @@ impl Component for ChangeDrivePopup {
                             // Taking in account if it's the default mountpoint
                             // (were the executable is)
                             vec![
+                                Action::StoreStoragePerNode(self.gb_per_node),
                                 Action::StoreStorageDrive(
                                     self.drive_selection.mountpoint.clone(),
                                     self.drive_selection.name.clone(),
@@ -457,6 +498,7 @@ impl Component for ChangeDrivePopup {
                         }
                         Err(e) => {
                             self.drive_selection = self.drive_selection_initial_state.clone();
+                            self.gb_per_node = self.gb_per_node_initial_state;
                             self.state = ChangeDriveState::Selection;
                             error!(
                                 "Error creating folder {:?}: {}",
@@ -469,9 +511,14 @@ impl Component for ChangeDrivePopup {
                 KeyCode::Esc => {
                     debug!("Got Esc, switching to Options");
                     self.drive_selection = self.drive_selection_initial_state.clone();
+                    self.gb_per_node = self.gb_per_node_initial_state;
                     self.state = ChangeDriveState::Selection;
                     vec![Action::SwitchScene(Scene::Options)]
                 }
+                KeyCode::Left | KeyCode::Right => {
+                    self.change_gb_per_node(key.code == KeyCode::Right);
+                    vec![]
+                }
                 _ => {
                     vec![]
                 }
@@ -485,10 +532,10 @@ impl Component for ChangeDrivePopup {
             Action::SwitchScene(scene) => match scene {
                 Scene::ChangeDrivePopUp => {
                     self.active = true;
-                    self.can_select = false;
                     self.state = ChangeDriveState::Selection;
                     let _ = self.update_drive_items();
                     self.select_drive();
+                    self.can_select = Self::is_selectable(&self.return_selection());
                     Some(Action::SwitchInputMode(InputMode::Entry))
                 }
                 _ => {
@@ -515,6 +562,10 @@ impl Component for ChangeDrivePopup {
                 self.select_drive();
                 None
             }
+            Action::StoreStoragePerNode(gb_per_node) => {
+                self.gb_per_node = gb_per_node;
+                None
+            }
 
             _ => None,
         };
@@ -613,6 +664,8 @@ pub struct DriveItem {
     name: String,
     mountpoint: PathBuf,
     size: String,
+    /// Available space in bytes.
+    available_space: u64,
     status: DriveStatus,
 }
 
@@ -24,14 +24,22 @@ use crate::{
 use super::super::{utils::centered_rect_fixed, Component};
 
 pub const GB_PER_NODE: usize = 35;
+/// Disk space used by a node besides its records, for its logs and bookkeeping.
+pub const GB_NODE_OVERHEAD: usize = 3;
 pub const MB: usize = 1000 * 1000;
 pub const GB: usize = MB * 1000;
 pub const MAX_NODE_COUNT: usize = 50;
 
+/// The storage budget of the records of a node, given the disk space allocated to the node.
+pub fn max_storage_bytes(gb_per_node: usize) -> u64 {
+    (gb_per_node.saturating_sub(GB_NODE_OVERHEAD) * GB) as u64
+}
+
 pub struct ManageNodes {
     /// Whether the component is active right now, capturing keystrokes + drawing things.
     active: bool,
     available_disk_space_gb: usize,
+    gb_per_node: usize,
     storage_mountpoint: PathBuf,
     nodes_to_start_input: Input,
     // cache the old value incase user presses Esc.
@@ -39,11 +47,16 @@ pub struct ManageNodes {
 }
 
 impl ManageNodes {
-    pub fn new(nodes_to_start: usize, storage_mountpoint: PathBuf) -> Result<Self> {
+    pub fn new(
+        nodes_to_start: usize,
+        gb_per_node: usize,
+        storage_mountpoint: PathBuf,
+    ) -> Result<Self> {
         let nodes_to_start = std::cmp::min(nodes_to_start, MAX_NODE_COUNT);
         let new = Self {
             active: false,
             available_disk_space_gb: get_available_space_b(&storage_mountpoint)? / GB,
+            gb_per_node,
             nodes_to_start_input: Input::default().with_value(nodes_to_start.to_string()),
             old_value: Default::default(),
             storage_mountpoint: storage_mountpoint.clone(),
@@ -58,7 +71,10 @@ impl ManageNodes {
     // Returns the max number of nodes to start
     // It is the minimum of the available disk space and the max nodes limit
     fn max_nodes_to_start(&self) -> usize {
-        std::cmp::min(self.available_disk_space_gb / GB_PER_NODE, MAX_NODE_COUNT)
+        std::cmp::min(
+            self.available_disk_space_gb / self.gb_per_node,
+            MAX_NODE_COUNT,
+        )
     }
 }
 
@@ -111,7 +127,7 @@ impl Component for ManageNodes {
                     .parse::<usize>()
                     .unwrap_or(0);
                 // if it might exceed the available space or if more than max_node_count, then enter the max
-                if new_value * GB_PER_NODE > self.available_disk_space_gb
+                if new_value * self.gb_per_node > self.available_disk_space_gb
                     || new_value > MAX_NODE_COUNT
                 {
                     self.nodes_to_start_input = self
@@ -134,7 +150,9 @@ impl Component for ManageNodes {
                     if key.code == KeyCode::Up {
                         if current_val + 1 >= MAX_NODE_COUNT {
                             MAX_NODE_COUNT
-                        } else if (current_val + 1) * GB_PER_NODE <= self.available_disk_space_gb {
+                        } else if (current_val + 1) * self.gb_per_node
+                            <= self.available_disk_space_gb
+                        {
                             current_val + 1
                         } else {
                             current_val
@@ -182,6 +200,10 @@ impl Component for ManageNodes {
                 self.available_disk_space_gb = get_available_space_b(&mountpoint)? / GB;
                 None
             }
+            Action::StoreStoragePerNode(gb_per_node) => {
+                self.gb_per_node = gb_per_node;
+                None
+            }
             _ => None,
         };
         Ok(send_back)
@@ -266,7 +288,7 @@ impl Component for ManageNodes {
         let info = Line::from(vec![
             Span::styled("Using", info_style),
             Span::styled(
-                format!(" {}GB ", self.get_nodes_to_start_val() * GB_PER_NODE),
+                format!(" {}GB ", self.get_nodes_to_start_val() * self.gb_per_node),
                 info_style.bold(),
             ),
             Span::styled(
@@ -9,8 +9,8 @@
 use super::footer::NodesToStart;
 use super::header::SelectedMenuItem;
 use super::{
-    footer::Footer, header::Header, popup::manage_nodes::GB_PER_NODE, utils::centered_rect_fixed,
-    Component, Frame,
+    footer::Footer, header::Header, popup::manage_nodes::max_storage_bytes,
+    utils::centered_rect_fixed, Component, Frame,
 };
 use crate::action::OptionsActions;
 use crate::components::popup::port_range::PORT_ALLOCATION;
@@ -106,6 +106,8 @@ pub struct Status<'a> {
     port_from: Option<u32>,
     // Port to
     port_to: Option<u32>,
+    // Storage allocated to each node, in GB
+    gb_per_node: usize,
     error_popup: Option<ErrorPopup>,
 }
 
@@ -127,6 +129,7 @@ pub struct StatusConfig {
     pub port_from: Option<u32>,
     pub port_to: Option<u32>,
     pub rewards_address: String,
+    pub storage_per_node_gb: usize,
 }
 
 impl Status<'_> {
@@ -152,6 +155,7 @@ impl Status<'_> {
             connection_mode: config.connection_mode,
             port_from: config.port_from,
             port_to: config.port_to,
+            gb_per_node: config.storage_per_node_gb,
             error_popup: None,
         };
 
@@ -431,6 +435,10 @@ impl Component for Status<'_> {
                         })?;
                 }
             }
+            Action::StoreStoragePerNode(gb_per_node) => {
+                debug!("Storing storage per node: {gb_per_node}GB");
+                self.gb_per_node = gb_per_node;
+            }
             Action::StoreStorageDrive(ref drive_mountpoint, ref _drive_name) => {
                 debug!("Setting lock_registry to ResettingNodes");
                 self.lock_registry = Some(LockRegistryState::ResettingNodes);
@@ -623,6 +631,7 @@ impl Component for Status<'_> {
                         connection_mode: self.connection_mode,
                         count: self.nodes_to_start as u16,
                         data_dir_path: Some(self.data_dir_path.clone()),
+                        max_storage: Some(max_storage_bytes(self.gb_per_node)),
                         network_id: self.network_id,
                         owner: self.rewards_address.clone(),
                         peers_args: self.peers_args.clone(),
@@ -781,7 +790,7 @@ impl Component for Status<'_> {
 
         let storage_allocated_row = Row::new(vec![
             Cell::new("Storage Allocated".to_string()).fg(GHOST_WHITE),
-            Cell::new(format!("{} GB", self.nodes_to_start * GB_PER_NODE)).fg(GHOST_WHITE),
+            Cell::new(format!("{} GB", self.nodes_to_start * self.gb_per_node)).fg(GHOST_WHITE),
         ]);
         let memory_use_val = if self.node_stats.total_memory_usage_mb as f64 / 1024_f64 > 1.0 {
             format!(
@@ -889,7 +898,7 @@ impl Component for Status<'_> {
                         "Each node will use {}GB of storage and a small amount of memory, \
                         CPU, and Network bandwidth. Most computers can run many nodes at once, \
                         but we recommend you add them gradually",
-                        GB_PER_NODE
+                        self.gb_per_node
                     ),
                     Style::default().fg(LIGHT_PERIWINKLE),
                 )]);
@@ -107,6 +107,7 @@ pub struct AppData {
     pub connection_mode: Option<ConnectionMode>,
     pub port_from: Option<u32>,
     pub port_to: Option<u32>,
+    pub storage_per_node_gb: Option<usize>,
 }
 
 impl Default for AppData {
@@ -119,6 +120,7 @@ impl Default for AppData {
             connection_mode: None,
             port_from: None,
             port_to: None,
+            storage_per_node_gb: None,
         }
     }
 }
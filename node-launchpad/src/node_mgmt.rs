@@ -127,6 +127,7 @@ pub struct MaintainNodesArgs {
     pub connection_mode: ConnectionMode,
     pub count: u16,
     pub data_dir_path: Option<PathBuf>,
+    pub max_storage: Option<u64>,
     pub network_id: Option<u8>,
     pub owner: String,
     pub peers_args: PeersArgs,
@@ -296,6 +297,7 @@ struct NodeConfig {
     custom_ports: Option<PortRange>,
     data_dir_path: Option<PathBuf>,
     home_network: bool,
+    max_storage: Option<u64>,
     network_id: Option<u8>,
     owner: Option<String>,
     peers_args: PeersArgs,
@@ -361,6 +363,7 @@ fn prepare_node_config(args: &MaintainNodesArgs) -> NodeConfig {
             Some(args.owner.clone())
         },
         home_network: args.connection_mode == ConnectionMode::HomeNetwork,
+        max_storage: args.max_storage,
         network_id: args.network_id,
         peers_args: args.peers_args.clone(),
         rewards_address: args.rewards_address.clone(),
@@ -426,6 +429,7 @@ async fn scale_down_nodes(config: &NodeConfig, count: u16) {
         None,
         None,
         None,
+        None,
         config.network_id,
         None,
         None, // We don't care about the port, as we are scaling down
@@ -499,6 +503,7 @@ async fn add_nodes(
             None,
             None,
             None,
+            config.max_storage,
             None,
             config.network_id,
             None,